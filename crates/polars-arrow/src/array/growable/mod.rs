@@ -16,6 +16,8 @@ mod primitive;
 pub use primitive::GrowablePrimitive;
 mod list;
 pub use list::GrowableList;
+mod map;
+pub use map::GrowableMap;
 mod structure;
 pub use structure::GrowableStruct;
 mod fixed_size_list;
@@ -163,6 +165,7 @@ pub fn make_growable<'a>(
                 ))
             })
         },
-        Union | Map => unimplemented!(),
+        Map => dyn_growable!(map::GrowableMap, arrays, use_validity, capacity),
+        Union => unimplemented!(),
     }
 }
@@ -2,6 +2,7 @@ use crate::array::*;
 use crate::bitmap::Bitmap;
 use crate::datatypes::PhysicalType;
 pub use crate::types::PrimitiveType;
+use crate::types::{i256, months_days_ns};
 use crate::{match_integer_type, with_match_primitive_type_full};
 fn validity_size(validity: Option<&Bitmap>) -> usize {
     validity.as_ref().map(|b| b.as_slice().0.len()).unwrap_or(0)
@@ -52,6 +53,21 @@ pub fn estimated_bytes_size(array: &dyn Array) -> usize {
             let array = array.as_any().downcast_ref::<DaysMsArray>().unwrap();
             array.values().len() * std::mem::size_of::<i32>() * 2 + validity_size(array.validity())
         },
+        Primitive(PrimitiveType::Int256) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<i256>>()
+                .unwrap();
+            array.values().len() * std::mem::size_of::<i256>() + validity_size(array.validity())
+        },
+        Primitive(PrimitiveType::MonthDayNano) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<months_days_ns>>()
+                .unwrap();
+            array.values().len() * std::mem::size_of::<months_days_ns>()
+                + validity_size(array.validity())
+        },
         Primitive(primitive) => with_match_primitive_type_full!(primitive, |$T| {
             let array = array
                 .as_any()
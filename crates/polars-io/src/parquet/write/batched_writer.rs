@@ -35,7 +35,7 @@ impl<W: Write> BatchedWriter<W> {
                     batch,
                     self.parquet_schema.fields(),
                     self.encodings.as_ref(),
-                    self.options,
+                    &self.options,
                 );
 
                 Some(row_group)
@@ -52,7 +52,7 @@ impl<W: Write> BatchedWriter<W> {
             df,
             &self.parquet_schema,
             &self.encodings,
-            self.options,
+            &self.options,
             self.parallel,
         );
         // Lock before looping so that order is maintained under contention.
@@ -92,7 +92,7 @@ fn prepare_rg_iter<'a>(
     df: &'a DataFrame,
     parquet_schema: &'a SchemaDescriptor,
     encodings: &'a [Vec<Encoding>],
-    options: WriteOptions,
+    options: &'a WriteOptions,
     parallel: bool,
 ) -> impl Iterator<Item = PolarsResult<RowGroupIterColumns<'static, PolarsError>>> + 'a {
     let rb_iter = df.iter_chunks(true);
@@ -109,7 +109,7 @@ fn prepare_rg_iter<'a>(
 
 fn pages_iter_to_compressor(
     encoded_columns: Vec<DynIter<'static, PolarsResult<Page>>>,
-    options: WriteOptions,
+    options: &WriteOptions,
 ) -> Vec<PolarsResult<DynStreamingIterator<'static, CompressedPage, PolarsError>>> {
     encoded_columns
         .into_iter()
@@ -125,6 +125,7 @@ fn pages_iter_to_compressor(
                     options.compression,
                     vec![],
                 )
+                .with_checksums(options.write_page_checksums)
                 .map_err(PolarsError::from),
             );
 
@@ -137,9 +138,10 @@ fn array_to_pages_iter(
     array: &ArrayRef,
     type_: &ParquetType,
     encoding: &[Encoding],
-    options: WriteOptions,
+    options: &WriteOptions,
 ) -> Vec<PolarsResult<DynStreamingIterator<'static, CompressedPage, PolarsError>>> {
-    let encoded_columns = array_to_columns(array, type_.clone(), options, encoding).unwrap();
+    let encoded_columns =
+        array_to_columns(array, type_.clone(), options.clone(), encoding).unwrap();
     pages_iter_to_compressor(encoded_columns, options)
 }
 
@@ -147,7 +149,7 @@ fn create_serializer(
     batch: RecordBatch,
     fields: &[ParquetType],
     encodings: &[Vec<Encoding>],
-    options: WriteOptions,
+    options: &WriteOptions,
     parallel: bool,
 ) -> PolarsResult<RowGroupIterColumns<'static, PolarsError>> {
     let func = move |((array, type_), encoding): ((&ArrayRef, &ParquetType), &Vec<Encoding>)| {
@@ -185,7 +187,7 @@ fn create_eager_serializer(
     batch: RecordBatch,
     fields: &[ParquetType],
     encodings: &[Vec<Encoding>],
-    options: WriteOptions,
+    options: &WriteOptions,
 ) -> PolarsResult<RowGroupIterColumns<'static, PolarsError>> {
     let func = move |((array, type_), encoding): ((&ArrayRef, &ParquetType), &Vec<Encoding>)| {
         array_to_pages_iter(array, type_, encoding, options)
@@ -86,8 +86,8 @@ where
 
         let parquet_schema = to_parquet_schema(&schema)?;
         let encodings = get_encodings(&schema);
-        let options = self.materialize_options();
-        let writer = Mutex::new(FileWriter::try_new(self.writer, schema, options)?);
+        let options = self.materialize_options()?;
+        let writer = Mutex::new(FileWriter::try_new(self.writer, schema, options.clone())?);
 
         Ok(BatchedWriter {
             writer,
@@ -98,13 +98,13 @@ where
         })
     }
 
-    fn materialize_options(&self) -> WriteOptions {
-        WriteOptions {
-            write_statistics: self.statistics,
-            compression: self.compression,
-            version: Version::V1,
-            data_pagesize_limit: self.data_page_size,
-        }
+    fn materialize_options(&self) -> PolarsResult<WriteOptions> {
+        WriteOptions::builder()
+            .write_statistics(self.statistics)
+            .compression(self.compression)
+            .version(Version::V1)
+            .data_pagesize_limit(self.data_page_size)
+            .build()
     }
 
     /// Write the given DataFrame in the writer `W`. Returns the total size of the file.
@@ -12,7 +12,7 @@ use super::super::utils::{
 };
 use super::super::{utils, PagesIter};
 use crate::parquet::deserialize::SliceFilteredIter;
-use crate::parquet::encoding::{hybrid_rle, Encoding};
+use crate::parquet::encoding::{byte_stream_split, hybrid_rle, Encoding};
 use crate::parquet::page::{split_buffer, DataPage, DictPage};
 use crate::parquet::types::{decode, NativeType as ParquetNativeType};
 
@@ -60,6 +60,30 @@ impl<'a> Values<'a> {
     }
 }
 
+/// The values of a byte stream split page, decoded upfront as its byte planes are interleaved.
+#[derive(Debug)]
+pub(super) struct ByteStreamSplitValues<T> {
+    pub values: std::vec::IntoIter<T>,
+}
+
+impl<T> ByteStreamSplitValues<T> {
+    pub fn try_new<P: ParquetNativeType, F: Fn(P) -> T>(
+        page: &DataPage,
+        op: F,
+    ) -> PolarsResult<Self> {
+        let (_, _, values) = split_buffer(page)?;
+        let values = byte_stream_split::decode::<P>(values)?;
+        Ok(Self {
+            values: values.into_iter().map(op).collect::<Vec<_>>().into_iter(),
+        })
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+}
+
 #[derive(Debug)]
 pub(super) struct ValuesDictionary<'a, T>
 where
@@ -97,6 +121,8 @@ where
     OptionalDictionary(OptionalPageValidity<'a>, ValuesDictionary<'a, T>),
     FilteredRequired(FilteredRequiredValues<'a>),
     FilteredOptional(FilteredOptionalPageValidity<'a>, Values<'a>),
+    RequiredByteStreamSplit(ByteStreamSplitValues<T>),
+    OptionalByteStreamSplit(OptionalPageValidity<'a>, ByteStreamSplitValues<T>),
 }
 
 impl<'a, T> utils::PageState<'a> for State<'a, T>
@@ -111,6 +137,8 @@ where
             State::OptionalDictionary(optional, _) => optional.len(),
             State::FilteredRequired(values) => values.len(),
             State::FilteredOptional(optional, _) => optional.len(),
+            State::RequiredByteStreamSplit(values) => values.len(),
+            State::OptionalByteStreamSplit(optional, _) => optional.len(),
         }
     }
 }
@@ -191,6 +219,13 @@ where
                 FilteredOptionalPageValidity::try_new(page)?,
                 Values::try_new::<P>(page)?,
             )),
+            (Encoding::ByteStreamSplit, _, false, false) => {
+                ByteStreamSplitValues::try_new(page, self.op).map(State::RequiredByteStreamSplit)
+            },
+            (Encoding::ByteStreamSplit, _, true, false) => Ok(State::OptionalByteStreamSplit(
+                OptionalPageValidity::try_new(page)?,
+                ByteStreamSplitValues::try_new(page, self.op)?,
+            )),
             _ => Err(utils::not_implemented(page)),
         }
     }
@@ -260,6 +295,18 @@ where
                     page_values.values.by_ref().map(decode).map(self.op),
                 );
             },
+            State::RequiredByteStreamSplit(page) => {
+                values.extend(page.values.by_ref().take(remaining));
+            },
+            State::OptionalByteStreamSplit(page_validity, page_values) => {
+                utils::extend_from_decoder(
+                    validity,
+                    page_validity,
+                    Some(remaining),
+                    values,
+                    page_values.values.by_ref(),
+                );
+            },
         }
         Ok(())
     }
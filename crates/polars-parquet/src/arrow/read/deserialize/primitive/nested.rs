@@ -10,7 +10,7 @@ use polars_utils::iter::FallibleIterator;
 use super::super::nested_utils::*;
 use super::super::utils::MaybeNext;
 use super::super::{utils, PagesIter};
-use super::basic::{deserialize_plain, Values, ValuesDictionary};
+use super::basic::{deserialize_plain, ByteStreamSplitValues, Values, ValuesDictionary};
 use crate::parquet::encoding::Encoding;
 use crate::parquet::page::{DataPage, DictPage};
 use crate::parquet::schema::Repetition;
@@ -27,6 +27,8 @@ where
     Required(Values<'a>),
     RequiredDictionary(ValuesDictionary<'a, T>),
     OptionalDictionary(ValuesDictionary<'a, T>),
+    RequiredByteStreamSplit(ByteStreamSplitValues<T>),
+    OptionalByteStreamSplit(ByteStreamSplitValues<T>),
 }
 
 impl<'a, T> utils::PageState<'a> for State<'a, T>
@@ -39,6 +41,8 @@ where
             State::Required(values) => values.len(),
             State::RequiredDictionary(values) => values.len(),
             State::OptionalDictionary(values) => values.len(),
+            State::RequiredByteStreamSplit(values) => values.len(),
+            State::OptionalByteStreamSplit(values) => values.len(),
         }
     }
 }
@@ -99,6 +103,12 @@ where
             },
             (Encoding::Plain, _, true, false) => Values::try_new::<P>(page).map(State::Optional),
             (Encoding::Plain, _, false, false) => Values::try_new::<P>(page).map(State::Required),
+            (Encoding::ByteStreamSplit, _, true, false) => {
+                ByteStreamSplitValues::try_new(page, self.op).map(State::OptionalByteStreamSplit)
+            },
+            (Encoding::ByteStreamSplit, _, false, false) => {
+                ByteStreamSplitValues::try_new(page, self.op).map(State::RequiredByteStreamSplit)
+            },
             _ => Err(utils::not_implemented(page)),
         }
     }
@@ -142,6 +152,13 @@ where
                 validity.push(true);
                 page.values.get_result()?;
             },
+            State::RequiredByteStreamSplit(page) => {
+                values.push(page.values.next().unwrap_or_default());
+            },
+            State::OptionalByteStreamSplit(page) => {
+                values.push(page.values.next().unwrap_or_default());
+                validity.push(true);
+            },
         }
         Ok(())
     }
@@ -168,6 +168,13 @@ fn from_fixed_len_byte_array(
     converted_type: Option<PrimitiveConvertedType>,
 ) -> ArrowDataType {
     match (logical_type, converted_type) {
+        // a `Decimal` holds at most 38 digits
+        (Some(PrimitiveLogicalType::Decimal(precision, scale)), _) if precision > 38 => {
+            ArrowDataType::Decimal256(precision, scale)
+        },
+        (None, Some(PrimitiveConvertedType::Decimal(precision, scale))) if precision > 38 => {
+            ArrowDataType::Decimal256(precision, scale)
+        },
         (Some(PrimitiveLogicalType::Decimal(precision, scale)), _) => {
             ArrowDataType::Decimal(precision, scale)
         },
@@ -470,6 +477,26 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_fixed_len_byte_array_decimals() -> PolarsResult<()> {
+        let message = "
+        message test_schema {
+            REQUIRED FIXED_LEN_BYTE_ARRAY (16) decimal (DECIMAL(38,2));
+            REQUIRED FIXED_LEN_BYTE_ARRAY (32) decimal256 (DECIMAL(50,2));
+        }
+        ";
+        let expected = vec![
+            Field::new("decimal", ArrowDataType::Decimal(38, 2), false),
+            Field::new("decimal256", ArrowDataType::Decimal256(50, 2), false),
+        ];
+
+        let parquet_schema = SchemaDescriptor::try_from_message(message)?;
+        let fields = parquet_to_arrow_schema(parquet_schema.fields());
+
+        assert_eq!(fields, expected);
+        Ok(())
+    }
+
     #[test]
     fn test_duplicate_fields() -> PolarsResult<()> {
         let message = "
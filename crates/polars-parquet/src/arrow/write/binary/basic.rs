@@ -1,7 +1,7 @@
 use arrow::array::{Array, BinaryArray, ValueSize};
 use arrow::bitmap::Bitmap;
-use arrow::offset::Offset;
-use polars_error::PolarsResult;
+use arrow::offset::{Offset, OffsetsBuffer};
+use polars_error::{polars_ensure, PolarsResult};
 
 use super::super::{utils, WriteOptions};
 use crate::arrow::read::schema::is_nullable;
@@ -27,24 +27,62 @@ pub(crate) fn encode_non_null_values<'a, I: Iterator<Item = &'a [u8]>>(
 
 pub(crate) fn encode_plain<O: Offset>(array: &BinaryArray<O>, buffer: &mut Vec<u8>) {
     let len_before = buffer.len();
-    let capacity =
-        array.get_values_size() + (array.len() - array.null_count()) * std::mem::size_of::<u32>();
+    let capacity = if array.null_count() == 0 {
+        array.get_values_size() + array.len() * std::mem::size_of::<u32>()
+    } else {
+        // null slots may own bytes that are not written
+        array
+            .non_null_values_iter()
+            .map(|x| x.len() + std::mem::size_of::<u32>())
+            .sum()
+    };
     buffer.reserve(capacity);
     encode_non_null_values(array.non_null_values_iter(), buffer);
     // Ensure we allocated properly.
     debug_assert_eq!(buffer.len() - len_before, capacity);
 }
 
+/// Checks that every value of `array` fits in a page, whose size is limited to `i32::MAX` bytes.
+/// Only arrays with 64-bit offsets can hold larger values.
+pub(crate) fn check_value_lengths<O: Offset>(array: &BinaryArray<O>) -> PolarsResult<()> {
+    if O::IS_LARGE {
+        check_offset_lengths(array.offsets(), array.validity())?;
+    }
+    Ok(())
+}
+
+/// Checks that every non-null value delimited by `offsets` fits in a page.
+fn check_offset_lengths<O: Offset>(
+    offsets: &OffsetsBuffer<O>,
+    validity: Option<&Bitmap>,
+) -> PolarsResult<()> {
+    let max_length = offsets
+        .lengths()
+        .enumerate()
+        .filter(|(i, _)| validity.map_or(true, |validity| validity.get_bit(*i)))
+        .map(|(_, length)| length)
+        .max()
+        .unwrap_or(0);
+    polars_ensure!(
+        max_length <= i32::MAX as usize,
+        ComputeError: "cannot write a binary value of {} bytes to parquet, the maximum is {} bytes",
+        max_length, i32::MAX
+    );
+    Ok(())
+}
+
 pub fn array_to_page<O: Offset>(
     array: &BinaryArray<O>,
     options: WriteOptions,
     type_: PrimitiveType,
     encoding: Encoding,
 ) -> PolarsResult<Page> {
+    check_value_lengths(array)?;
     let validity = array.validity();
     let is_optional = is_nullable(&type_.field_info);
 
-    let mut buffer = vec![];
+    let mut buffer = utils::page_buffer(&options);
+    let levels_start = buffer.len();
     utils::write_def_levels(
         &mut buffer,
         is_optional,
@@ -53,17 +91,11 @@ pub fn array_to_page<O: Offset>(
         options.version,
     )?;
 
-    let definition_levels_byte_length = buffer.len();
+    let definition_levels_byte_length = buffer.len() - levels_start;
 
     match encoding {
         Encoding::Plain => encode_plain(array, &mut buffer),
-        Encoding::DeltaLengthByteArray => encode_delta(
-            array.values(),
-            array.offsets().buffer(),
-            array.validity(),
-            is_optional,
-            &mut buffer,
-        ),
+        Encoding::DeltaLengthByteArray => encode_delta(array, is_optional, &mut buffer),
         _ => return Err(invalid_encoding(encoding, array.data_type())),
     }
 
@@ -110,36 +142,37 @@ pub(crate) fn build_statistics<O: Offset>(
     serialize_statistics(statistics)
 }
 
+/// Encodes `array` as `DELTA_LENGTH_BYTE_ARRAY`: the delta-bitpacked lengths followed by the
+/// concatenated bytes of every non-null value.
 pub(crate) fn encode_delta<O: Offset>(
-    values: &[u8],
-    offsets: &[O],
-    validity: Option<&Bitmap>,
+    array: &BinaryArray<O>,
     is_optional: bool,
     buffer: &mut Vec<u8>,
 ) {
-    if is_optional {
-        if let Some(validity) = validity {
-            let lengths = offsets
-                .windows(2)
-                .map(|w| (w[1] - w[0]).to_usize() as i64)
-                .zip(validity.iter())
-                .flat_map(|(x, is_valid)| if is_valid { Some(x) } else { None });
-            let length = offsets.len() - 1 - validity.unset_bits();
-            let lengths = utils::ExactSizedIter::new(lengths, length);
-
-            delta_bitpacked::encode(lengths, buffer);
-        } else {
-            let lengths = offsets.windows(2).map(|w| (w[1] - w[0]).to_usize() as i64);
-            delta_bitpacked::encode(lengths, buffer);
+    let offsets = array.offsets().buffer();
+    let validity = array
+        .validity()
+        .filter(|v| is_optional && v.unset_bits() > 0);
+
+    if validity.is_some() {
+        let lengths = array.non_null_values_iter().map(|x| x.len() as i64);
+        let length = array.len() - array.null_count();
+        let lengths = utils::ExactSizedIter::new(lengths, length);
+        delta_bitpacked::encode(lengths, buffer);
+
+        // Null slots may still own bytes in `values`, so only copy the valid ones.
+        for value in array.non_null_values_iter() {
+            buffer.extend_from_slice(value);
         }
     } else {
         let lengths = offsets.windows(2).map(|w| (w[1] - w[0]).to_usize() as i64);
         delta_bitpacked::encode(lengths, buffer);
-    }
 
-    buffer.extend_from_slice(
-        &values[offsets.first().unwrap().to_usize()..offsets.last().unwrap().to_usize()],
-    )
+        buffer.extend_from_slice(
+            &array.values()
+                [offsets.first().unwrap().to_usize()..offsets.last().unwrap().to_usize()],
+        );
+    }
 }
 
 /// Returns the ordering of two binary values. This corresponds to pyarrows' ordering
@@ -148,3 +181,69 @@ pub(crate) fn encode_delta<O: Offset>(
 pub(crate) fn ord_binary<'a>(a: &'a [u8], b: &'a [u8]) -> std::cmp::Ordering {
     a.cmp(b)
 }
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::Utf8Array;
+
+    use super::super::super::{array_to_page, Nested, Version};
+    use super::*;
+    use crate::parquet::page::Page;
+    use crate::parquet::schema::types::PhysicalType;
+
+    #[test]
+    fn statistics() {
+        let options = WriteOptions::builder()
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let array = Utf8Array::<i64>::from([Some("pear"), None, Some("apple"), Some("zucchini")]);
+        let type_ = PrimitiveType::from_physical("a".to_string(), PhysicalType::ByteArray);
+        let nested = [Nested::Primitive(
+            array.validity().cloned(),
+            true,
+            array.len(),
+        )];
+
+        let page = array_to_page(&array, type_, &nested, options, Encoding::Plain).unwrap();
+        let Page::Data(page) = page else {
+            unreachable!()
+        };
+        let statistics = page.statistics().unwrap().unwrap();
+        let statistics = statistics
+            .as_any()
+            .downcast_ref::<BinaryStatistics>()
+            .unwrap();
+
+        assert_eq!(statistics.null_count, Some(1));
+        assert_eq!(statistics.min_value, Some(b"apple".to_vec()));
+        assert_eq!(statistics.max_value, Some(b"zucchini".to_vec()));
+    }
+
+    #[test]
+    fn large_binary() {
+        let options = WriteOptions::builder()
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let type_ = PrimitiveType::from_physical("a".to_string(), PhysicalType::ByteArray);
+        let array = BinaryArray::<i64>::from([Some(b"ab".as_slice()), None, Some(b"")]);
+
+        let page =
+            super::array_to_page(&array, options.clone(), type_.clone(), Encoding::Plain).unwrap();
+        let Page::Data(page) = page else {
+            unreachable!()
+        };
+        let (_, def, values) = crate::parquet::page::split_buffer(&page).unwrap();
+        // a single bitpacked run of the definition levels [1, 0, 1]
+        assert_eq!(def, [3, 0b101]);
+        assert_eq!(values, [2, 0, 0, 0, b'a', b'b', 0, 0, 0, 0]);
+
+        // only the offsets are checked, so that a value too large for a page is not allocated
+        let length = i32::MAX as i64 + 1;
+        let offsets: OffsetsBuffer<i64> = vec![0, 1, 1 + length].try_into().unwrap();
+        let error = check_offset_lengths(&offsets, None).unwrap_err();
+        assert!(error.to_string().contains("maximum"), "{error}");
+        assert!(check_offset_lengths(&offsets, Some(&Bitmap::from([true, false]))).is_ok());
+    }
+}
@@ -3,26 +3,37 @@ use arrow::offset::Offset;
 use polars_error::PolarsResult;
 
 use super::super::{nested, utils, WriteOptions};
-use super::basic::{build_statistics, encode_plain};
+use super::basic::{build_statistics, check_value_lengths, encode_delta, encode_plain};
 use crate::arrow::write::Nested;
 use crate::parquet::encoding::Encoding;
 use crate::parquet::page::DataPage;
 use crate::parquet::schema::types::PrimitiveType;
+use crate::write::utils::invalid_encoding;
 
 pub fn array_to_page<O>(
     array: &BinaryArray<O>,
     options: WriteOptions,
     type_: PrimitiveType,
     nested: &[Nested],
+    encoding: Encoding,
 ) -> PolarsResult<DataPage>
 where
     O: Offset,
 {
-    let mut buffer = vec![];
+    check_value_lengths(array)?;
+
+    let mut buffer = utils::page_buffer(&options);
+    let info = nested::NestedInfo::new(nested);
     let (repetition_levels_byte_length, definition_levels_byte_length) =
-        nested::write_rep_and_def(options.version, nested, &mut buffer)?;
+        nested::write_rep_and_def_with_info(options.version, nested, &info, &mut buffer)?;
 
-    encode_plain(array, &mut buffer);
+    match encoding {
+        Encoding::Plain => encode_plain(array, &mut buffer),
+        Encoding::DeltaLengthByteArray => {
+            encode_delta(array, nested::is_optional_leaf(&type_, array), &mut buffer)
+        },
+        _ => return Err(invalid_encoding(encoding, array.data_type())),
+    }
 
     let statistics = if options.write_statistics {
         Some(build_statistics(array, type_.clone()))
@@ -32,7 +43,7 @@ where
 
     utils::build_plain_page(
         buffer,
-        nested::num_values(nested),
+        info.value_count,
         nested[0].len(),
         array.null_count(),
         repetition_levels_byte_length,
@@ -40,6 +51,86 @@ where
         statistics,
         type_,
         options,
-        Encoding::Plain,
+        encoding,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use arrow::bitmap::Bitmap;
+    use arrow::datatypes::ArrowDataType;
+
+    use super::super::super::pages::ListNested;
+    use super::super::super::{array_to_page, Version};
+    use super::*;
+    use crate::parquet::encoding::delta_length_byte_array::Decoder;
+    use crate::parquet::page::{split_buffer, Page};
+    use crate::parquet::schema::types::PhysicalType;
+
+    // ["hello", "", None, "world"], where the null slot still owns the bytes "xyz".
+    fn array() -> BinaryArray<i64> {
+        BinaryArray::new(
+            ArrowDataType::LargeBinary,
+            vec![0i64, 5, 5, 8, 13].try_into().unwrap(),
+            b"helloxyzworld".to_vec().into(),
+            Some(Bitmap::from([true, true, false, true])),
+        )
+    }
+
+    fn decode(array: &BinaryArray<i64>, nested: &[Nested]) -> (Vec<i32>, Vec<u8>) {
+        let options = WriteOptions::builder()
+            .write_statistics(false)
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let type_ = PrimitiveType::from_physical("a".to_string(), PhysicalType::ByteArray);
+        let page = array_to_page(
+            array,
+            type_,
+            nested,
+            options,
+            Encoding::DeltaLengthByteArray,
+        );
+        let Page::Data(page) = page.unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(page.encoding(), Encoding::DeltaLengthByteArray);
+
+        let (_, _, values) = split_buffer(&page).unwrap();
+        let mut decoder = Decoder::try_new(values).unwrap();
+        let lengths = decoder.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        (lengths, decoder.into_values().to_vec())
+    }
+
+    #[test]
+    fn delta_length_byte_array() {
+        let array = array();
+        let nested = [Nested::Primitive(
+            array.validity().cloned(),
+            true,
+            array.len(),
+        )];
+        assert_eq!(
+            decode(&array, &nested),
+            (vec![5, 0, 5], b"helloworld".to_vec())
+        );
+    }
+
+    #[test]
+    fn delta_length_byte_array_nested() {
+        // [["hello", ""], [], [None, "world"]]
+        let array = array();
+        let nested = [
+            Nested::LargeList(ListNested::new(
+                vec![0i64, 2, 2, 4].try_into().unwrap(),
+                None,
+                true,
+            )),
+            Nested::Primitive(array.validity().cloned(), true, array.len()),
+        ];
+        assert_eq!(
+            decode(&array, &nested),
+            (vec![5, 0, 5], b"helloworld".to_vec())
+        );
+    }
+}
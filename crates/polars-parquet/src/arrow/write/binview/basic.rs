@@ -12,8 +12,15 @@ use crate::write::utils::invalid_encoding;
 use crate::write::{utils, Encoding, Page, WriteOptions};
 
 pub(crate) fn encode_plain(array: &BinaryViewArray, buffer: &mut Vec<u8>) {
-    let capacity =
-        array.total_bytes_len() + (array.len() - array.null_count()) * std::mem::size_of::<u32>();
+    let capacity = if array.null_count() == 0 {
+        array.total_bytes_len() + array.len() * std::mem::size_of::<u32>()
+    } else {
+        // the views of null slots may have a length, but their bytes are not written
+        array
+            .non_null_views_iter()
+            .map(|v| v.length as usize + std::mem::size_of::<u32>())
+            .sum()
+    };
 
     let len_before = buffer.len();
     buffer.reserve(capacity);
@@ -40,7 +47,8 @@ pub fn array_to_page(
 ) -> PolarsResult<Page> {
     let is_optional = is_nullable(&type_.field_info);
 
-    let mut buffer = vec![];
+    let mut buffer = utils::page_buffer(&options);
+    let levels_start = buffer.len();
     // TODO! reserve capacity
     utils::write_def_levels(
         &mut buffer,
@@ -50,7 +58,7 @@ pub fn array_to_page(
         options.version,
     )?;
 
-    let definition_levels_byte_length = buffer.len();
+    let definition_levels_byte_length = buffer.len() - levels_start;
 
     match encoding {
         Encoding::Plain => encode_plain(array, &mut buffer),
@@ -101,3 +109,41 @@ pub(crate) fn build_statistics(
     } as &dyn Statistics;
     serialize_statistics(statistics)
 }
+
+#[cfg(test)]
+mod tests {
+    use arrow::bitmap::Bitmap;
+
+    use super::*;
+    use crate::parquet::page::split_buffer;
+    use crate::parquet::schema::types::PhysicalType;
+    use crate::write::Version;
+
+    #[test]
+    fn inlined_and_out_of_line_views() {
+        let long = b"a value longer than twelve bytes".as_slice();
+        let array = BinaryViewArray::from_slice_values([b"short".as_slice(), b"hidden", long])
+            .with_validity(Some(Bitmap::from([true, false, true])));
+        let options = WriteOptions::builder()
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let type_ = PrimitiveType::from_physical("a".to_string(), PhysicalType::ByteArray);
+
+        let Page::Data(page) = array_to_page(&array, options, type_, Encoding::Plain).unwrap()
+        else {
+            unreachable!()
+        };
+        let (_, def, values) = split_buffer(&page).unwrap();
+        assert_eq!(def, [3, 0b101]);
+        // the bytes of the null slot are not written
+        let expected = [
+            5u32.to_le_bytes().as_slice(),
+            b"short",
+            &(long.len() as u32).to_le_bytes(),
+            long,
+        ]
+        .concat();
+        assert_eq!(values, expected);
+    }
+}
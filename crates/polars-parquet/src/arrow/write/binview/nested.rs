@@ -2,23 +2,30 @@ use arrow::array::{Array, BinaryViewArray};
 use polars_error::PolarsResult;
 
 use super::super::{nested, utils, WriteOptions};
-use super::basic::{build_statistics, encode_plain};
+use super::basic::{build_statistics, encode_delta, encode_plain};
 use crate::arrow::write::Nested;
 use crate::parquet::encoding::Encoding;
 use crate::parquet::page::DataPage;
 use crate::parquet::schema::types::PrimitiveType;
+use crate::write::utils::invalid_encoding;
 
 pub fn array_to_page(
     array: &BinaryViewArray,
     options: WriteOptions,
     type_: PrimitiveType,
     nested: &[Nested],
+    encoding: Encoding,
 ) -> PolarsResult<DataPage> {
-    let mut buffer = vec![];
+    let mut buffer = utils::page_buffer(&options);
+    let info = nested::NestedInfo::new(nested);
     let (repetition_levels_byte_length, definition_levels_byte_length) =
-        nested::write_rep_and_def(options.version, nested, &mut buffer)?;
+        nested::write_rep_and_def_with_info(options.version, nested, &info, &mut buffer)?;
 
-    encode_plain(array, &mut buffer);
+    match encoding {
+        Encoding::Plain => encode_plain(array, &mut buffer),
+        Encoding::DeltaLengthByteArray => encode_delta(array, &mut buffer),
+        _ => return Err(invalid_encoding(encoding, array.data_type())),
+    }
 
     let statistics = if options.write_statistics {
         Some(build_statistics(array, type_.clone()))
@@ -28,7 +35,7 @@ pub fn array_to_page(
 
     utils::build_plain_page(
         buffer,
-        nested::num_values(nested),
+        info.value_count,
         nested[0].len(),
         array.null_count(),
         repetition_levels_byte_length,
@@ -36,6 +43,6 @@ pub fn array_to_page(
         statistics,
         type_,
         options,
-        Encoding::Plain,
+        encoding,
     )
 }
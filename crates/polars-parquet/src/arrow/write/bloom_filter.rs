@@ -0,0 +1,248 @@
+use arrow::array::{
+    Array, BinaryArray, BinaryViewArray, DictionaryArray, PrimitiveArray, Utf8Array, Utf8ViewArray,
+};
+use arrow::datatypes::ArrowDataType;
+use arrow::match_integer_type;
+use arrow::types::NativeType;
+use polars_error::{polars_bail, polars_ensure, PolarsResult};
+
+use super::{BloomFilterOptions, DynIter, Page};
+use crate::parquet::bloom_filter::{hash_byte, hash_native, insert};
+use crate::parquet::types::NativeType as ParquetNativeType;
+
+/// The maximum size of a bloom filter, as recommended by the specification.
+const MAX_NUM_BYTES: usize = 128 * 1024 * 1024;
+
+/// Returns the number of bytes of a split-block bloom filter that holds `num_distinct_values`
+/// with a false positive probability of `false_positive_probability`.
+///
+/// The result is a power of two between 32 bytes (a single block) and 128 MiB.
+pub fn bloom_filter_num_bytes(
+    num_distinct_values: usize,
+    false_positive_probability: f64,
+) -> usize {
+    if false_positive_probability <= 0.0 {
+        return MAX_NUM_BYTES;
+    }
+    let num_bits =
+        -8.0 * num_distinct_values as f64 / (1.0 - false_positive_probability.powf(1.0 / 8.0)).ln();
+    let num_bytes = (num_bits / 8.0).ceil() as usize;
+    num_bytes.clamp(32, MAX_NUM_BYTES).next_power_of_two()
+}
+
+fn insert_primitive<T, P>(bitset: &mut [u8], array: &dyn Array)
+where
+    T: NativeType + num_traits::AsPrimitive<P>,
+    P: ParquetNativeType,
+{
+    let array = array.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+    array
+        .iter()
+        .flatten()
+        .for_each(|x| insert(bitset, hash_native::<P>(x.as_())));
+}
+
+fn insert_bytes<'a, I: Iterator<Item = Option<&'a [u8]>>>(bitset: &mut [u8], iter: I) {
+    iter.flatten().for_each(|x| insert(bitset, hash_byte(x)));
+}
+
+fn is_supported(data_type: &ArrowDataType) -> bool {
+    use ArrowDataType::*;
+    match data_type.to_logical_type() {
+        Dictionary(_, values, _) => is_supported(values),
+        Int8 | Int16 | Int32 | Int64 | UInt8 | UInt16 | UInt32 | UInt64 | Utf8 | LargeUtf8
+        | Binary | LargeBinary | Utf8View | BinaryView => true,
+        _ => false,
+    }
+}
+
+/// Returns `pages` with the bloom filter of the values of `leaves` attached to their first
+/// data page, or `pages` unchanged if the type of the leaves is not supported.
+pub(super) fn with_bloom_filter(
+    pages: DynIter<'static, PolarsResult<Page>>,
+    leaves: &[&dyn Array],
+    options: &BloomFilterOptions,
+) -> PolarsResult<DynIter<'static, PolarsResult<Page>>> {
+    if !leaves.iter().all(|leaf| is_supported(leaf.data_type())) {
+        return Ok(pages);
+    }
+
+    let num_values = leaves
+        .iter()
+        .map(|leaf| leaf.len() - leaf.null_count())
+        .sum();
+    let mut bitset =
+        vec![0; bloom_filter_num_bytes(num_values, options.false_positive_probability)];
+    for leaf in leaves {
+        insert_into_bloom_filter(&mut bitset, *leaf)?;
+    }
+
+    let mut bitset = Some(bitset);
+    Ok(DynIter::new(pages.map(move |page| {
+        page.map(|mut page| {
+            if let Page::Data(page) = &mut page {
+                if bitset.is_some() {
+                    page.bloom_filter = bitset.take();
+                }
+            }
+            page
+        })
+    })))
+}
+
+/// Inserts every non-null value of `array` into the split-block bloom filter `bitset`.
+///
+/// Values are hashed as their parquet physical type (e.g. an `Int8` as an `INT32`), which is
+/// how readers look them up. Every value of the dictionary of a dictionary array is inserted,
+/// including the values that no key references.
+/// # Errors
+/// Errors if `bitset` is not made of 32-byte blocks or if the type of `array` is not supported.
+pub fn insert_into_bloom_filter(bitset: &mut [u8], array: &dyn Array) -> PolarsResult<()> {
+    polars_ensure!(
+        !bitset.is_empty() && bitset.len() % 32 == 0,
+        InvalidOperation: "a bloom filter must be made of 32-byte blocks, got {} bytes", bitset.len()
+    );
+
+    use ArrowDataType::*;
+    match array.data_type().to_logical_type() {
+        Int8 => insert_primitive::<i8, i32>(bitset, array),
+        Int16 => insert_primitive::<i16, i32>(bitset, array),
+        Int32 => insert_primitive::<i32, i32>(bitset, array),
+        Int64 => insert_primitive::<i64, i64>(bitset, array),
+        UInt8 => insert_primitive::<u8, i32>(bitset, array),
+        UInt16 => insert_primitive::<u16, i32>(bitset, array),
+        UInt32 => insert_primitive::<u32, i32>(bitset, array),
+        UInt64 => insert_primitive::<u64, i64>(bitset, array),
+        Utf8 => {
+            let array = array.as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
+            insert_bytes(bitset, array.iter().map(|x| x.map(|x| x.as_bytes())))
+        },
+        LargeUtf8 => {
+            let array = array.as_any().downcast_ref::<Utf8Array<i64>>().unwrap();
+            insert_bytes(bitset, array.iter().map(|x| x.map(|x| x.as_bytes())))
+        },
+        Binary => {
+            let array = array.as_any().downcast_ref::<BinaryArray<i32>>().unwrap();
+            insert_bytes(bitset, array.iter())
+        },
+        LargeBinary => {
+            let array = array.as_any().downcast_ref::<BinaryArray<i64>>().unwrap();
+            insert_bytes(bitset, array.iter())
+        },
+        Utf8View => {
+            let array = array.as_any().downcast_ref::<Utf8ViewArray>().unwrap();
+            insert_bytes(bitset, array.iter().map(|x| x.map(|x| x.as_bytes())))
+        },
+        BinaryView => {
+            let array = array.as_any().downcast_ref::<BinaryViewArray>().unwrap();
+            insert_bytes(bitset, array.iter())
+        },
+        Dictionary(key_type, _, _) => match_integer_type!(key_type, |$T| {
+            let array = array
+                .as_any()
+                .downcast_ref::<DictionaryArray<$T>>()
+                .unwrap();
+            insert_into_bloom_filter(bitset, array.values().as_ref())?
+        }),
+        other => polars_bail!(nyi = "Bloom filters for data type {other:?}"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::Int8Array;
+
+    use super::*;
+    use crate::parquet::bloom_filter::is_in_set;
+
+    #[test]
+    fn num_bytes() {
+        assert_eq!(bloom_filter_num_bytes(0, 0.01), 32);
+        assert_eq!(bloom_filter_num_bytes(1_000, 0.01), 2048);
+        assert_eq!(bloom_filter_num_bytes(usize::MAX, 0.01), MAX_NUM_BYTES);
+        assert_eq!(bloom_filter_num_bytes(1_000, 0.0), MAX_NUM_BYTES);
+    }
+
+    #[test]
+    fn utf8() {
+        let array = Utf8Array::<i32>::from([Some("apple"), None, Some("pear"), Some("fig")]);
+        let mut bitset = vec![0; bloom_filter_num_bytes(array.len(), 0.01)];
+        insert_into_bloom_filter(&mut bitset, &array).unwrap();
+
+        for value in ["apple", "pear", "fig"] {
+            assert!(is_in_set(&bitset, hash_byte(value)));
+        }
+        assert!(!is_in_set(&bitset, hash_byte("plum")));
+    }
+
+    #[test]
+    fn integers() {
+        let array = Int8Array::from([Some(1), None, Some(-5), Some(100)]);
+        let mut bitset = vec![0; bloom_filter_num_bytes(array.len(), 0.01)];
+        insert_into_bloom_filter(&mut bitset, &array).unwrap();
+
+        // hashed as INT32
+        for value in [1i32, -5, 100] {
+            assert!(is_in_set(&bitset, hash_native(value)));
+        }
+        assert!(!is_in_set(&bitset, hash_native(7i32)));
+    }
+
+    #[test]
+    fn written_and_read() {
+        use std::io::Cursor;
+
+        use arrow::datatypes::{ArrowSchema, Field};
+        use arrow::record_batch::RecordBatchT;
+
+        use crate::arrow::read::{read_metadata, FileReader};
+        use crate::arrow::write::{Encoding, FileWriter, RowGroupIterator, Version, WriteOptions};
+        use crate::parquet::bloom_filter::read;
+
+        let array = Utf8ViewArray::from_slice([Some("apple"), None, Some("pear")]);
+        let schema = ArrowSchema::from(vec![Field::new("a", array.data_type().clone(), true)]);
+        let options = WriteOptions::builder()
+            .version(Version::V2)
+            .bloom_filter(Some(BloomFilterOptions {
+                false_positive_probability: 0.01,
+            }))
+            .build()
+            .unwrap();
+
+        let batch = RecordBatchT::new(vec![array.clone().boxed()]);
+        let row_groups = RowGroupIterator::try_new(
+            vec![Ok(batch)].into_iter(),
+            &schema,
+            options.clone(),
+            vec![vec![Encoding::Plain]],
+        )
+        .unwrap();
+        let mut writer = FileWriter::try_new(vec![], schema.clone(), options).unwrap();
+        for group in row_groups {
+            writer.write(group.unwrap()).unwrap();
+        }
+        writer.end(None).unwrap();
+        let mut file = Cursor::new(writer.into_inner());
+
+        let metadata = read_metadata(&mut file).unwrap();
+        let column = &metadata.row_groups[0].columns()[0];
+        let mut bitset = vec![];
+        read(column, &mut file, &mut bitset).unwrap();
+        assert!(is_in_set(&bitset, hash_byte("apple")));
+        assert!(is_in_set(&bitset, hash_byte("pear")));
+        assert!(!is_in_set(&bitset, hash_byte("plum")));
+
+        // the pages are still readable
+        let reader = FileReader::new(file, metadata.row_groups, schema, None, None, None);
+        let chunks = reader.collect::<PolarsResult<Vec<_>>>().unwrap();
+        assert_eq!(chunks[0].arrays()[0].as_ref(), &array as &dyn Array);
+    }
+
+    #[test]
+    fn invalid_bitset() {
+        let array = Int8Array::from([Some(1)]);
+        assert!(insert_into_bloom_filter(&mut [0; 31], &array).is_err());
+        assert!(insert_into_bloom_filter(&mut [], &array).is_err());
+    }
+}
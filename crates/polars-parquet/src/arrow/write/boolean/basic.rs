@@ -16,7 +16,8 @@ fn encode(iterator: impl Iterator<Item = bool>, buffer: &mut Vec<u8>) -> PolarsR
     let len = buffer.len();
     let mut buffer = std::io::Cursor::new(buffer);
     buffer.set_position(len as u64);
-    Ok(bitpacked_encode(&mut buffer, iterator)?)
+    bitpacked_encode(&mut buffer, iterator)?;
+    Ok(())
 }
 
 pub(super) fn encode_plain(
@@ -48,7 +49,8 @@ pub fn array_to_page(
 
     let validity = array.validity();
 
-    let mut buffer = vec![];
+    let mut buffer = utils::page_buffer(&options);
+    let levels_start = buffer.len();
     utils::write_def_levels(
         &mut buffer,
         is_optional,
@@ -57,7 +59,7 @@ pub fn array_to_page(
         options.version,
     )?;
 
-    let definition_levels_byte_length = buffer.len();
+    let definition_levels_byte_length = buffer.len() - levels_start;
 
     encode_plain(array, is_optional, &mut buffer)?;
 
@@ -3,7 +3,6 @@ use polars_error::PolarsResult;
 
 use super::super::{nested, utils, WriteOptions};
 use super::basic::{build_statistics, encode_plain};
-use crate::arrow::read::schema::is_nullable;
 use crate::arrow::write::Nested;
 use crate::parquet::encoding::Encoding;
 use crate::parquet::page::DataPage;
@@ -15,11 +14,12 @@ pub fn array_to_page(
     type_: PrimitiveType,
     nested: &[Nested],
 ) -> PolarsResult<DataPage> {
-    let is_optional = is_nullable(&type_.field_info);
+    let is_optional = nested::is_optional_leaf(&type_, array);
 
-    let mut buffer = vec![];
+    let mut buffer = utils::page_buffer(&options);
+    let info = nested::NestedInfo::new(nested);
     let (repetition_levels_byte_length, definition_levels_byte_length) =
-        nested::write_rep_and_def(options.version, nested, &mut buffer)?;
+        nested::write_rep_and_def_with_info(options.version, nested, &info, &mut buffer)?;
 
     encode_plain(array, is_optional, &mut buffer)?;
 
@@ -31,7 +31,7 @@ pub fn array_to_page(
 
     utils::build_plain_page(
         buffer,
-        nested::num_values(nested),
+        info.value_count,
         nested[0].len(),
         array.null_count(),
         repetition_levels_byte_length,
@@ -1,7 +1,18 @@
-use arrow::array::{Array, BinaryViewArray, DictionaryArray, DictionaryKey, Utf8ViewArray};
+use std::cmp::Ordering;
+
+use arrow::array::growable::make_growable;
+use arrow::array::{
+    Array, BinaryArray, BinaryViewArray, BooleanArray, DictionaryArray, DictionaryKey,
+    FixedSizeBinaryArray, PrimitiveArray, Utf8Array, Utf8ViewArray,
+};
 use arrow::bitmap::{Bitmap, MutableBitmap};
-use arrow::datatypes::{ArrowDataType, IntegerType};
+use arrow::datatypes::{ArrowDataType, IntegerType, PhysicalType, PrimitiveType as ArrowPrimitive};
+use arrow::offset::Offset;
+use arrow::types::NativeType;
+use arrow::with_match_primitive_type;
 use polars_error::{polars_bail, PolarsResult};
+use polars_utils::total_ord::TotalOrd;
+use polars_utils::IdxSize;
 
 use super::binary::{
     build_statistics as binary_build_statistics, encode_plain as binary_encode_plain,
@@ -15,7 +26,7 @@ use super::primitive::{
 use super::{binview, nested, Nested, WriteOptions};
 use crate::arrow::read::schema::is_nullable;
 use crate::arrow::write::{slice_nested_leaf, utils};
-use crate::parquet::encoding::hybrid_rle::encode;
+use crate::parquet::encoding::hybrid_rle::{encode, encode_constant_u32};
 use crate::parquet::encoding::Encoding;
 use crate::parquet::page::{DictPage, Page};
 use crate::parquet::schema::types::PrimitiveType;
@@ -30,6 +41,16 @@ pub(crate) fn encode_as_dictionary_optional(
 ) -> Option<PolarsResult<DynIter<'static, PolarsResult<Page>>>> {
     let dtype = Box::new(array.data_type().clone());
 
+    if let Some(array) = constant_as_dictionary(array) {
+        return Some(array_to_pages(
+            &array,
+            type_,
+            nested,
+            options,
+            Encoding::RleDictionary,
+        ));
+    }
+
     let len_before = array.len();
     // This does the group by.
     let array = arrow::compute::cast::cast(
@@ -47,6 +68,9 @@ pub(crate) fn encode_as_dictionary_optional(
     if (array.values().len() as f64) / (len_before as f64) > 0.75 {
         return None;
     }
+    if exceeds_size_limit(array.values().as_ref(), &options) {
+        return None;
+    }
 
     Some(array_to_pages(
         array,
@@ -57,11 +81,183 @@ pub(crate) fn encode_as_dictionary_optional(
     ))
 }
 
+/// Returns whether the plain-encoded dictionary `values` are larger than the
+/// [`WriteOptions::dictionary_size_limit`] of `options`.
+fn exceeds_size_limit(values: &dyn Array, options: &WriteOptions) -> bool {
+    options
+        .dictionary_size_limit
+        .is_some_and(|limit| super::estimated_values_size(values, Encoding::Plain) > limit)
+}
+
+/// Returns the values of `array` at its keys if its dictionary exceeds the
+/// [`WriteOptions::dictionary_size_limit`] of `options`, so that they are plain-encoded instead.
+pub(super) fn decode_over_size_limit<K: DictionaryKey>(
+    array: &DictionaryArray<K>,
+    options: &WriteOptions,
+) -> Option<Box<dyn Array>> {
+    let values = array.values().as_ref();
+    if !exceeds_size_limit(values, options) {
+        return None;
+    }
+
+    let mut decoded = make_growable(&[values], true, array.len());
+    for key in array.keys_iter() {
+        match key {
+            // SAFETY: the keys of a dictionary array are indices of its values.
+            Some(key) => unsafe { decoded.extend(0, key, 1) },
+            None => decoded.extend_validity(1),
+        }
+    }
+    Some(decoded.as_box())
+}
+
+/// Returns the index of the first non-null value of `array` if all its non-null values are
+/// bitwise equal, and `None` if it has no non-null values.
+fn constant_index<T: NativeType>(array: &PrimitiveArray<T>) -> Option<usize> {
+    let mut values = array
+        .iter()
+        .enumerate()
+        .filter_map(|(i, value)| value.map(|value| (i, value.to_le_bytes())));
+    let (first, value) = values.next()?;
+    values
+        .all(|(_, other)| other.as_ref() == value.as_ref())
+        .then_some(first)
+}
+
+/// Converts a primitive `array` whose non-null values are all equal to a [`DictionaryArray`]
+/// with a single value, without hashing its values.
+fn constant_as_dictionary(array: &dyn Array) -> Option<DictionaryArray<u32>> {
+    use ArrowPrimitive::*;
+    let PhysicalType::Primitive(
+        primitive @ (Int8 | Int16 | Int32 | Int64 | UInt8 | UInt16 | UInt32 | UInt64 | Float32
+        | Float64),
+    ) = array.data_type().to_physical_type()
+    else {
+        return None;
+    };
+    if array.len() < 2 {
+        return None;
+    }
+    let first = with_match_primitive_type!(primitive, |$T| {
+        constant_index::<$T>(array.as_any().downcast_ref().unwrap())
+    })?;
+
+    let keys = PrimitiveArray::<u32>::new(
+        ArrowDataType::UInt32,
+        vec![0; array.len()].into(),
+        array.validity().cloned(),
+    );
+    let values = array.sliced(first, 1).with_validity(None);
+    let data_type = ArrowDataType::Dictionary(
+        IntegerType::UInt32,
+        Box::new(array.data_type().clone()),
+        false,
+    );
+    Some(DictionaryArray::try_new(data_type, keys, values).unwrap())
+}
+
+/// A comparison of the values at two indices of an array.
+pub(super) type Comparator<'a> = Box<dyn Fn(usize, usize) -> Ordering + 'a>;
+
+/// Returns the comparison of the values of `values` in their parquet sort order, or `None` if
+/// their type has no sort order. The validity of `values` is ignored.
+pub(super) fn value_comparator(values: &dyn Array) -> Option<Comparator<'_>> {
+    use ArrowPrimitive::*;
+
+    fn by<'a, T: Ord + ?Sized + 'a>(value: impl Fn(usize) -> &'a T + 'a) -> Comparator<'a> {
+        Box::new(move |a, b| value(a).cmp(value(b)))
+    }
+
+    let physical_type = values.data_type().to_physical_type();
+    let values = values.as_any();
+    Some(match physical_type {
+        PhysicalType::Primitive(
+            primitive @ (Int8 | Int16 | Int32 | Int64 | UInt8 | UInt16 | UInt32 | UInt64 | Float32
+            | Float64),
+        ) => with_match_primitive_type!(primitive, |$T| {
+            let values = values.downcast_ref::<PrimitiveArray<$T>>().unwrap().values();
+            let compare: Comparator = Box::new(move |a, b| values[a].tot_cmp(&values[b]));
+            compare
+        }),
+        PhysicalType::Boolean => {
+            let values = values.downcast_ref::<BooleanArray>().unwrap();
+            Box::new(move |a, b| values.value(a).cmp(&values.value(b)))
+        },
+        PhysicalType::Utf8 => {
+            let values = values.downcast_ref::<Utf8Array<i32>>().unwrap();
+            by(move |i| values.value(i).as_bytes())
+        },
+        PhysicalType::LargeUtf8 => {
+            let values = values.downcast_ref::<Utf8Array<i64>>().unwrap();
+            by(move |i| values.value(i).as_bytes())
+        },
+        PhysicalType::Binary => {
+            let values = values.downcast_ref::<BinaryArray<i32>>().unwrap();
+            by(move |i| values.value(i))
+        },
+        PhysicalType::LargeBinary => {
+            let values = values.downcast_ref::<BinaryArray<i64>>().unwrap();
+            by(move |i| values.value(i))
+        },
+        PhysicalType::Utf8View => {
+            let values = values.downcast_ref::<Utf8ViewArray>().unwrap();
+            by(move |i| values.value(i).as_bytes())
+        },
+        PhysicalType::BinaryView => {
+            let values = values.downcast_ref::<BinaryViewArray>().unwrap();
+            by(move |i| values.value(i))
+        },
+        PhysicalType::FixedSizeBinary => {
+            let values = values.downcast_ref::<FixedSizeBinaryArray>().unwrap();
+            by(move |i| values.value(i))
+        },
+        _ => return None,
+    })
+}
+
+/// Returns the permutation that sorts the `values` of a dictionary by their parquet sort order,
+/// or `None` if their type has no sort order.
+fn sort_permutation(values: &dyn Array) -> Option<Vec<IdxSize>> {
+    let compare = value_comparator(values)?;
+    let mut indices = (0..values.len() as IdxSize).collect::<Vec<_>>();
+    indices.sort_by(|&a, &b| compare(a as usize, b as usize));
+    Some(indices)
+}
+
+/// Sorts the values of an ordered dictionary `array` and remaps its keys accordingly, so that
+/// its dictionary page can be marked as sorted. Returns `None` if the values can't be sorted.
+fn sort_dictionary<K: DictionaryKey>(array: &DictionaryArray<K>) -> Option<DictionaryArray<K>> {
+    let permutation = sort_permutation(array.values().as_ref())?;
+
+    let mut ranks = vec![0; permutation.len()];
+    for (rank, &index) in permutation.iter().enumerate() {
+        ranks[index as usize] = rank;
+    }
+    let keys = array
+        .keys_values_iter()
+        .map(|key| K::try_from(ranks[key]).unwrap_or_else(|_| unreachable!()))
+        .collect::<Vec<_>>();
+    let keys = PrimitiveArray::<K>::new(
+        array.keys().data_type().clone(),
+        keys.into(),
+        array.keys().validity().cloned(),
+    );
+    let values = array.values().as_ref();
+    let mut sorted = make_growable(&[values], values.null_count() > 0, permutation.len());
+    for index in permutation {
+        // SAFETY: the permutation only contains indices of the values.
+        unsafe { sorted.extend(0, index as usize, 1) };
+    }
+    let values = sorted.as_box();
+
+    Some(DictionaryArray::try_new(array.data_type().clone(), keys, values).unwrap())
+}
+
 fn serialize_def_levels_simple(
     validity: Option<&Bitmap>,
     length: usize,
     is_optional: bool,
-    options: WriteOptions,
+    options: &WriteOptions,
     buffer: &mut Vec<u8>,
 ) -> PolarsResult<()> {
     utils::write_def_levels(buffer, is_optional, validity, length, options.version)
@@ -72,6 +268,14 @@ fn serialize_keys_values<K: DictionaryKey>(
     validity: Option<&Bitmap>,
     buffer: &mut Vec<u8>,
 ) -> PolarsResult<()> {
+    if array.values().len() == 1 {
+        // every non-null key points at the single value: a single run of zeros.
+        let length = array.len() - validity.map_or(0, |v| v.unset_bits());
+        buffer.push(0);
+        encode_constant_u32(buffer, 0, length, 0)?;
+        return Ok(());
+    }
+
     let keys = array.keys_values_iter().map(|x| x as u32);
     if let Some(validity) = validity {
         // discard indices whose values are null.
@@ -87,7 +291,8 @@ fn serialize_keys_values<K: DictionaryKey>(
         buffer.push(num_bits as u8);
 
         // followed by the encoded indices.
-        Ok(encode::<u32, _, _>(buffer, keys, num_bits)?)
+        encode::<u32, _, _>(buffer, keys, num_bits)?;
+        Ok(())
     } else {
         let num_bits = utils::get_bit_width(keys.clone().max().unwrap_or(0) as u64);
 
@@ -95,7 +300,8 @@ fn serialize_keys_values<K: DictionaryKey>(
         buffer.push(num_bits as u8);
 
         // followed by the encoded indices.
-        Ok(encode::<u32, _, _>(buffer, keys, num_bits)?)
+        encode::<u32, _, _>(buffer, keys, num_bits)?;
+        Ok(())
     }
 }
 
@@ -104,16 +310,18 @@ fn serialize_levels(
     length: usize,
     type_: &PrimitiveType,
     nested: &[Nested],
-    options: WriteOptions,
+    info: &nested::NestedInfo,
+    options: &WriteOptions,
     buffer: &mut Vec<u8>,
 ) -> PolarsResult<(usize, usize)> {
     if nested.len() == 1 {
         let is_optional = is_nullable(&type_.field_info);
+        let levels_start = buffer.len();
         serialize_def_levels_simple(validity, length, is_optional, options, buffer)?;
-        let definition_levels_byte_length = buffer.len();
+        let definition_levels_byte_length = buffer.len() - levels_start;
         Ok((0, definition_levels_byte_length))
     } else {
-        nested::write_rep_and_def(options.version, nested, buffer)
+        nested::write_rep_and_def_with_info(options.version, nested, info, buffer)
     }
 }
 
@@ -122,8 +330,11 @@ fn normalized_validity<K: DictionaryKey>(array: &DictionaryArray<K>) -> Option<B
         (None, None) => None,
         (keys, None) => keys.cloned(),
         // The values can have a different length than the keys
-        (_, Some(_values)) => {
-            let iter = (0..array.len()).map(|i| unsafe { !array.is_null_unchecked(i) });
+        (keys, Some(values)) => {
+            let iter = array
+                .keys_values_iter()
+                .enumerate()
+                .map(|(i, key)| keys.map_or(true, |keys| keys.get_bit(i)) && values.get_bit(key));
             MutableBitmap::from_trusted_len_iter(iter).into()
         },
     }
@@ -136,28 +347,32 @@ fn serialize_keys<K: DictionaryKey>(
     statistics: Option<ParquetStatistics>,
     options: WriteOptions,
 ) -> PolarsResult<Page> {
-    let mut buffer = vec![];
+    let mut buffer = utils::page_buffer(&options);
 
     let (start, len) = slice_nested_leaf(nested);
 
     let mut nested = nested.to_vec();
     let array = array.clone().sliced(start, len);
-    if let Some(Nested::Primitive(_, _, c)) = nested.last_mut() {
-        *c = len;
-    } else {
-        unreachable!("")
-    }
     // Parquet only accepts a single validity - we "&" the validities into a single one
     // and ignore keys whose _value_ is null.
     // It's important that we slice before normalizing.
     let validity = normalized_validity(&array);
+    if let Some(Nested::Primitive(leaf_validity, _, c)) = nested.last_mut() {
+        // the definition levels of a nested column must also account for null values
+        leaf_validity.clone_from(&validity);
+        *c = len;
+    } else {
+        unreachable!("")
+    }
 
+    let info = nested::NestedInfo::new(&nested);
     let (repetition_levels_byte_length, definition_levels_byte_length) = serialize_levels(
         validity.as_ref(),
         array.len(),
         &type_,
         &nested,
-        options,
+        &info,
+        &options,
         &mut buffer,
     )?;
 
@@ -166,14 +381,14 @@ fn serialize_keys<K: DictionaryKey>(
     let (num_values, num_rows) = if nested.len() == 1 {
         (array.len(), array.len())
     } else {
-        (nested::num_values(&nested), nested[0].len())
+        (info.value_count, nested[0].len())
     };
 
     utils::build_plain_page(
         buffer,
         num_values,
         num_rows,
-        array.null_count(),
+        validity.as_ref().map_or(0, |v| v.unset_bits()),
         repetition_levels_byte_length,
         definition_levels_byte_length,
         statistics,
@@ -202,6 +417,21 @@ macro_rules! dyn_prim {
     }};
 }
 
+fn binary_dict_page<O: Offset>(
+    values: &BinaryArray<O>,
+    type_: &PrimitiveType,
+    options: &WriteOptions,
+) -> (DictPage, Option<ParquetStatistics>) {
+    let mut buffer = vec![];
+    binary_encode_plain::<O>(&values.clone().with_validity(None), &mut buffer);
+    let stats = if options.write_statistics {
+        Some(binary_build_statistics(values, type_.clone()))
+    } else {
+        None
+    };
+    (DictPage::new(buffer, values.len(), false), stats)
+}
+
 pub fn array_to_pages<K: DictionaryKey>(
     array: &DictionaryArray<K>,
     type_: PrimitiveType,
@@ -211,8 +441,14 @@ pub fn array_to_pages<K: DictionaryKey>(
 ) -> PolarsResult<DynIter<'static, PolarsResult<Page>>> {
     match encoding {
         Encoding::PlainDictionary | Encoding::RleDictionary => {
-            // write DictPage
-            let (dict_page, mut statistics): (_, Option<ParquetStatistics>) =
+            // the values of an ordered dictionary are written sorted, so that readers can rely
+            // on the order of the dictionary page
+            let sorted = array.is_ordered().then(|| sort_dictionary(array)).flatten();
+            let array = sorted.as_ref().unwrap_or(array);
+
+            // write DictPage. Every value is written, including nulls, so that the keys keep
+            // pointing at the right values; the keys of null values are written as nulls.
+            let (mut dict_page, mut statistics): (_, Option<ParquetStatistics>) =
                 match array.values().data_type().to_logical_type() {
                     ArrowDataType::Int8 => dyn_prim!(i8, i32, array, options, type_),
                     ArrowDataType::Int16 => dyn_prim!(i16, i32, array, options, type_),
@@ -230,6 +466,19 @@ pub fn array_to_pages<K: DictionaryKey>(
                     ArrowDataType::UInt64 => dyn_prim!(u64, i64, array, options, type_),
                     ArrowDataType::Float32 => dyn_prim!(f32, f32, array, options, type_),
                     ArrowDataType::Float64 => dyn_prim!(f64, f64, array, options, type_),
+                    ArrowDataType::Utf8 => {
+                        let array = array
+                            .values()
+                            .as_any()
+                            .downcast_ref::<Utf8Array<i32>>()
+                            .unwrap()
+                            .to_binary();
+                        binary_dict_page(&array, &type_, &options)
+                    },
+                    ArrowDataType::Binary => {
+                        let values = array.values().as_any().downcast_ref().unwrap();
+                        binary_dict_page::<i32>(values, &type_, &options)
+                    },
                     ArrowDataType::LargeUtf8 => {
                         let array = arrow::compute::cast::cast(
                             array.values().as_ref(),
@@ -237,10 +486,10 @@ pub fn array_to_pages<K: DictionaryKey>(
                             Default::default(),
                         )
                         .unwrap();
-                        let array = array.as_any().downcast_ref().unwrap();
+                        let array = array.as_any().downcast_ref::<BinaryArray<i64>>().unwrap();
 
                         let mut buffer = vec![];
-                        binary_encode_plain::<i64>(array, &mut buffer);
+                        binary_encode_plain::<i64>(&array.clone().with_validity(None), &mut buffer);
                         let stats = if options.write_statistics {
                             Some(binary_build_statistics(array, type_.clone()))
                         } else {
@@ -255,7 +504,7 @@ pub fn array_to_pages<K: DictionaryKey>(
                             .downcast_ref::<BinaryViewArray>()
                             .unwrap();
                         let mut buffer = vec![];
-                        binview::encode_plain(array, &mut buffer);
+                        binview::encode_plain(&array.clone().with_validity(None), &mut buffer);
 
                         let stats = if options.write_statistics {
                             Some(binview::build_statistics(array, type_.clone()))
@@ -272,7 +521,7 @@ pub fn array_to_pages<K: DictionaryKey>(
                             .unwrap()
                             .to_binview();
                         let mut buffer = vec![];
-                        binview::encode_plain(&array, &mut buffer);
+                        binview::encode_plain(&array.clone().with_validity(None), &mut buffer);
 
                         let stats = if options.write_statistics {
                             Some(binview::build_statistics(&array, type_.clone()))
@@ -282,10 +531,17 @@ pub fn array_to_pages<K: DictionaryKey>(
                         (DictPage::new(buffer, array.len(), false), stats)
                     },
                     ArrowDataType::LargeBinary => {
-                        let values = array.values().as_any().downcast_ref().unwrap();
+                        let values = array
+                            .values()
+                            .as_any()
+                            .downcast_ref::<BinaryArray<i64>>()
+                            .unwrap();
 
                         let mut buffer = vec![];
-                        binary_encode_plain::<i64>(values, &mut buffer);
+                        binary_encode_plain::<i64>(
+                            &values.clone().with_validity(None),
+                            &mut buffer,
+                        );
                         let stats = if options.write_statistics {
                             Some(binary_build_statistics(values, type_.clone()))
                         } else {
@@ -312,6 +568,7 @@ pub fn array_to_pages<K: DictionaryKey>(
                     },
                 };
 
+            dict_page.is_sorted = sorted.is_some();
             if let Some(stats) = &mut statistics {
                 stats.null_count = Some(array.null_count() as i64)
             }
@@ -329,3 +586,368 @@ pub fn array_to_pages<K: DictionaryKey>(
         _ => polars_bail!(nyi = "Dictionary arrays only support dictionary encoding"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{DictionaryArray, Float32Array, Int32Array, Int64Array, ListArray};
+    use arrow::datatypes::{ArrowDataType, Field, IntegerType};
+
+    use super::super::{array_to_columns, to_parquet_type, Version};
+    use super::*;
+    use crate::parquet::encoding::hybrid_rle::decode_u32;
+    use crate::parquet::page::split_buffer;
+    use crate::parquet::schema::types::ParquetType;
+
+    fn options() -> WriteOptions {
+        WriteOptions::builder()
+            .write_statistics(false)
+            .version(Version::V2)
+            .build()
+            .unwrap()
+    }
+
+    /// Writes `array` as a flat column with dictionary encoding, returning the dictionary page,
+    /// the bit width of the indices and the indices.
+    fn write(array: &dyn Array) -> (DictPage, u8, Vec<u32>) {
+        let field = Field::new("a", array.data_type().clone(), true);
+        let ParquetType::PrimitiveType(type_) = to_parquet_type(&field).unwrap() else {
+            unreachable!()
+        };
+        let nested = [Nested::Primitive(
+            array.validity().cloned(),
+            true,
+            array.len(),
+        )];
+        let mut pages =
+            super::super::array_to_pages(array, type_, &nested, options(), Encoding::RleDictionary)
+                .unwrap();
+
+        let Page::Dict(dict_page) = pages.next().unwrap().unwrap() else {
+            panic!("the first page must be a dictionary page")
+        };
+        let Page::Data(data_page) = pages.next().unwrap().unwrap() else {
+            unreachable!()
+        };
+        assert!(pages.next().is_none());
+        assert_eq!(data_page.encoding(), Encoding::RleDictionary);
+
+        let (_, _, values) = split_buffer(&data_page).unwrap();
+        let num_bits = values[0];
+        let num_valid = array.len() - array.null_count();
+        let indices = decode_u32(&mut &values[1..], num_bits as u32, num_valid).unwrap();
+        (dict_page, num_bits, indices)
+    }
+
+    /// The plain encoding of `values` as byte arrays.
+    fn plain(values: &[&str]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|v| (v.len() as u32).to_le_bytes().into_iter().chain(v.bytes()))
+            .collect()
+    }
+
+    #[test]
+    fn utf8_repeated_values() {
+        let array = Utf8ViewArray::from_slice([
+            Some("a"),
+            Some("b"),
+            None,
+            Some("a"),
+            Some("c"),
+            Some("b"),
+            Some("a"),
+            Some("a"),
+        ]);
+
+        let (dict_page, num_bits, indices) = write(&array);
+        assert_eq!(dict_page.num_values, 3);
+        assert_eq!(dict_page.buffer, plain(&["a", "b", "c"]));
+        // ceil(log2(3))
+        assert_eq!(num_bits, 2);
+        assert_eq!(indices, vec![0, 1, 0, 2, 1, 0, 0]);
+    }
+
+    #[test]
+    fn dictionary_array() {
+        let values = Utf8Array::<i32>::from_slice(["x", "y"]).boxed();
+        let keys = Int32Array::from([Some(1), Some(1), None, Some(0), Some(1)]);
+        let array = DictionaryArray::try_new(
+            ArrowDataType::Dictionary(IntegerType::Int32, Box::new(ArrowDataType::Utf8), false),
+            keys,
+            values,
+        )
+        .unwrap();
+
+        let (dict_page, num_bits, indices) = write(&array);
+        assert_eq!(dict_page.num_values, 2);
+        assert_eq!(dict_page.buffer, plain(&["x", "y"]));
+        assert_eq!(num_bits, 1);
+        assert_eq!(indices, vec![1, 1, 0, 1]);
+    }
+
+    #[test]
+    fn ordered_dictionary() {
+        let dictionary = |values: Box<dyn Array>, is_ordered| {
+            let keys = Int32Array::from([Some(0), Some(2), None, Some(1), Some(0)]);
+            let data_type = ArrowDataType::Dictionary(
+                IntegerType::Int32,
+                Box::new(values.data_type().clone()),
+                is_ordered,
+            );
+            DictionaryArray::try_new(data_type, keys, values).unwrap()
+        };
+
+        let values = Utf8Array::<i32>::from_slice(["c", "a", "é"]).boxed();
+        let (dict_page, _, indices) = write(&dictionary(values.clone(), false));
+        assert!(!dict_page.is_sorted);
+        assert_eq!(dict_page.buffer, plain(&["c", "a", "é"]));
+        assert_eq!(indices, vec![0, 2, 1, 0]);
+
+        // the values are sorted as unsigned bytes and the keys remapped
+        let (dict_page, _, indices) = write(&dictionary(values, true));
+        assert!(dict_page.is_sorted);
+        assert_eq!(dict_page.buffer, plain(&["a", "c", "é"]));
+        assert_eq!(indices, vec![1, 2, 0, 1]);
+
+        let values = Int32Array::from_slice([3, -1, 2]).boxed();
+        let (dict_page, _, indices) = write(&dictionary(values, true));
+        assert!(dict_page.is_sorted);
+        let expected = [-1i32, 2, 3]
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .collect::<Vec<_>>();
+        assert_eq!(dict_page.buffer, expected);
+        assert_eq!(indices, vec![2, 1, 0, 2]);
+    }
+
+    #[test]
+    fn ordered_dictionary_binary_values() {
+        let keys = Int32Array::from([Some(0), Some(2), None, Some(1), Some(0)]);
+        let write_sorted = |values: Box<dyn Array>| {
+            let data_type = ArrowDataType::Dictionary(
+                IntegerType::Int32,
+                Box::new(values.data_type().clone()),
+                true,
+            );
+            let array = DictionaryArray::try_new(data_type, keys.clone(), values).unwrap();
+            let (dict_page, _, indices) = write(&array);
+            assert!(dict_page.is_sorted);
+            assert_eq!(indices, vec![1, 2, 0, 1]);
+            dict_page.buffer
+        };
+
+        let expected = plain(&["a", "c", "é"]);
+        let values = Utf8Array::<i64>::from_slice(["c", "a", "é"]).boxed();
+        assert_eq!(write_sorted(values), expected);
+        let values = BinaryArray::<i32>::from_slice([b"c", b"a", "é".as_bytes()]).boxed();
+        assert_eq!(write_sorted(values), expected);
+        let values = BinaryArray::<i64>::from_slice([b"c", b"a", "é".as_bytes()]).boxed();
+        assert_eq!(write_sorted(values), expected);
+
+        let values = FixedSizeBinaryArray::new(
+            ArrowDataType::FixedSizeBinary(2),
+            vec![b'c', b'c', b'a', b'a', b'e', b'e'].into(),
+            None,
+        )
+        .boxed();
+        assert_eq!(write_sorted(values), b"aaccee");
+    }
+
+    #[test]
+    fn constant_column() {
+        let array = Int32Array::from_vec(vec![7; 1_000_000]);
+
+        let (dict_page, num_bits, indices) = write(&array);
+        assert_eq!(dict_page.num_values, 1);
+        assert_eq!(dict_page.buffer, 7i32.to_le_bytes());
+        assert_eq!(num_bits, 0);
+        assert_eq!(indices.len(), 1_000_000);
+        assert!(indices.iter().all(|x| *x == 0));
+
+        // both the definition levels and the indices are a single run
+        let field = Field::new("a", ArrowDataType::Int32, true);
+        let ParquetType::PrimitiveType(type_) = to_parquet_type(&field).unwrap() else {
+            unreachable!()
+        };
+        let nested = [Nested::Primitive(None, true, array.len())];
+        let mut pages = super::super::array_to_pages(
+            &array,
+            type_,
+            &nested,
+            options(),
+            Encoding::RleDictionary,
+        )
+        .unwrap();
+        pages.next();
+        let Page::Data(data_page) = pages.next().unwrap().unwrap() else {
+            unreachable!()
+        };
+        assert!(data_page.buffer().len() < 16);
+    }
+
+    #[test]
+    fn constant_column_with_nulls() {
+        let array = Int32Array::from([Some(3), None, Some(3), Some(3), None, Some(3)]);
+
+        let (dict_page, num_bits, indices) = write(&array);
+        assert_eq!(dict_page.num_values, 1);
+        assert_eq!(dict_page.buffer, 3i32.to_le_bytes());
+        assert_eq!(num_bits, 0);
+        assert_eq!(indices, vec![0; 4]);
+
+        assert_eq!(
+            constant_index(&Int32Array::from([None, Some(1), Some(1)])),
+            Some(1)
+        );
+        assert_eq!(constant_index(&Int32Array::from([None, None])), None);
+        // values are compared bitwise
+        assert_eq!(constant_index(&Float32Array::from_slice([0.0, -0.0])), None);
+        assert_eq!(
+            constant_index(&Float32Array::from_slice([f32::NAN; 2])),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn list_of_dictionary() {
+        // [["x", None], None, [], [None, "y"]], where the first null is a null key and the
+        // second one a key of a null value.
+        let values = Utf8Array::<i32>::from([Some("x"), Some("y"), None]).boxed();
+        let keys = Int32Array::from([Some(0), None, Some(2), Some(1)]);
+        let dict_type =
+            ArrowDataType::Dictionary(IntegerType::Int32, Box::new(ArrowDataType::Utf8), false);
+        let dict = DictionaryArray::try_new(dict_type.clone(), keys, values).unwrap();
+        let list_type = ArrowDataType::List(Box::new(Field::new("item", dict_type, true)));
+        let array = ListArray::<i32>::new(
+            list_type.clone(),
+            vec![0, 2, 2, 2, 4].try_into().unwrap(),
+            dict.boxed(),
+            Some([true, false, true, true].into()),
+        );
+
+        let type_ = to_parquet_type(&Field::new("l", list_type, true)).unwrap();
+        let mut pages =
+            array_to_columns(array.boxed(), type_, options(), &[Encoding::RleDictionary])
+                .unwrap()
+                .pop()
+                .unwrap();
+        let Page::Dict(dict_page) = pages.next().unwrap().unwrap() else {
+            panic!("the first page must be a dictionary page")
+        };
+        assert_eq!(dict_page.num_values, 3);
+        assert_eq!(dict_page.buffer, plain(&["x", "y", ""]));
+        let Page::Data(data_page) = pages.next().unwrap().unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(data_page.num_values(), 6);
+
+        let (rep, def, values) = split_buffer(&data_page).unwrap();
+        assert_eq!(
+            decode_u32(&mut &rep[..], 1, 6).unwrap(),
+            vec![0, 1, 0, 0, 0, 1]
+        );
+        // the null key and the null value are both at the "element null" level
+        assert_eq!(
+            decode_u32(&mut &def[..], 2, 6).unwrap(),
+            vec![3, 2, 0, 1, 2, 3]
+        );
+        let indices = decode_u32(&mut &values[1..], values[0] as u32, 2).unwrap();
+        assert_eq!(indices, vec![0, 1]);
+    }
+
+    #[test]
+    fn dictionary_size_limit() {
+        // 500 distinct values of 8 bytes, well below the cardinality threshold
+        let array = Int64Array::from_values((0..2000).map(|i| i % 500));
+        let field = Field::new("a", array.data_type().clone(), false);
+        let ParquetType::PrimitiveType(type_) = to_parquet_type(&field).unwrap() else {
+            unreachable!()
+        };
+        let nested = [Nested::Primitive(None, false, array.len())];
+        let encodings = |limit| {
+            let options = WriteOptions::builder()
+                .write_statistics(false)
+                .version(Version::V2)
+                .dictionary_size_limit(limit)
+                .build()
+                .unwrap();
+            super::super::array_to_pages(
+                &array,
+                type_.clone(),
+                &nested,
+                options,
+                Encoding::RleDictionary,
+            )
+            .unwrap()
+            .map(|page| match page.unwrap() {
+                Page::Dict(page) => {
+                    assert_eq!(page.buffer.len(), 500 * 8);
+                    None
+                },
+                Page::Data(page) => Some(page.encoding()),
+            })
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(encodings(None), vec![None, Some(Encoding::RleDictionary)]);
+        assert_eq!(
+            encodings(Some(500 * 8)),
+            vec![None, Some(Encoding::RleDictionary)]
+        );
+        // the dictionary would be larger than the limit: the column falls back to plain
+        assert_eq!(encodings(Some(500 * 8 - 1)), vec![Some(Encoding::Plain)]);
+    }
+
+    #[test]
+    fn dictionary_array_size_limit() {
+        let keys = Int32Array::from_iter((0..2000).map(|i| (i % 7 != 0).then_some(i % 500)));
+        let values = Int64Array::from_values(0..500).boxed();
+        let data_type =
+            ArrowDataType::Dictionary(IntegerType::Int32, Box::new(ArrowDataType::Int64), false);
+        let array = DictionaryArray::try_new(data_type, keys.clone(), values).unwrap();
+        let decoded = Int64Array::from_iter(keys.iter().map(|key| key.map(|key| *key as i64)));
+
+        let field = Field::new("a", ArrowDataType::Int64, true);
+        let ParquetType::PrimitiveType(type_) = to_parquet_type(&field).unwrap() else {
+            unreachable!()
+        };
+        let nested = [Nested::Primitive(
+            array.validity().cloned(),
+            true,
+            array.len(),
+        )];
+        let pages = |array: &dyn Array, limit, encoding| {
+            let options = WriteOptions::builder()
+                .write_statistics(false)
+                .version(Version::V2)
+                .dictionary_size_limit(limit)
+                .build()
+                .unwrap();
+            super::super::array_to_pages(array, type_.clone(), &nested, options, encoding)
+                .unwrap()
+                .map(|page| match page.unwrap() {
+                    Page::Dict(_) => (None, vec![]),
+                    Page::Data(page) => (Some(page.encoding()), page.buffer().to_vec()),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let encodings = |limit| {
+            pages(&array, limit, Encoding::RleDictionary)
+                .into_iter()
+                .map(|(encoding, _)| encoding)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(encodings(None), vec![None, Some(Encoding::RleDictionary)]);
+        assert_eq!(
+            encodings(Some(500 * 8)),
+            vec![None, Some(Encoding::RleDictionary)]
+        );
+
+        // the dictionary is larger than the limit: its values are decoded and plain-encoded
+        assert_eq!(
+            pages(&array, Some(500 * 8 - 1), Encoding::RleDictionary),
+            pages(&decoded, None, Encoding::Plain)
+        );
+    }
+}
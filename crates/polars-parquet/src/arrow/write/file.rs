@@ -5,7 +5,7 @@ use polars_error::{PolarsError, PolarsResult};
 
 use super::schema::schema_to_metadata_key;
 use super::{to_parquet_schema, ThriftFileMetaData, WriteOptions};
-use crate::parquet::metadata::{KeyValue, SchemaDescriptor};
+use crate::parquet::metadata::{KeyValue, SchemaDescriptor, SortingColumn};
 use crate::parquet::write::{RowGroupIterColumns, WriteOptions as FileWriteOptions};
 
 /// Attaches [`ArrowSchema`] to `key_value_metadata`
@@ -32,7 +32,7 @@ pub struct FileWriter<W: Write> {
 impl<W: Write> FileWriter<W> {
     /// The options assigned to the file
     pub fn options(&self) -> WriteOptions {
-        self.options
+        self.options.clone()
     }
 
     /// The [`SchemaDescriptor`] assigned to this file
@@ -70,6 +70,13 @@ impl<W: Write> FileWriter<W> {
         })
     }
 
+    /// Sets the columns by which the rows of every row group written after this call are
+    /// sorted. The columns encoded with [`array_to_columns_sorted`](super::array_to_columns_sorted)
+    /// are added to them.
+    pub fn set_sorting_columns(&mut self, sorting_columns: Option<Vec<SortingColumn>>) {
+        self.writer.set_sorting_columns(sorting_columns)
+    }
+
     /// Writes a row group to the file.
     pub fn write(&mut self, row_group: RowGroupIterColumns<'_, PolarsError>) -> PolarsResult<()> {
         Ok(self.writer.write(row_group)?)
@@ -1,13 +1,13 @@
 use arrow::array::{Array, FixedSizeBinaryArray, PrimitiveArray};
 use arrow::types::i256;
-use polars_error::PolarsResult;
+use polars_error::{polars_ensure, PolarsResult};
 
 use super::binary::ord_binary;
-use super::{utils, WriteOptions};
+use super::{decimal_length_from_precision, nested, utils, Nested, WriteOptions};
 use crate::arrow::read::schema::is_nullable;
 use crate::parquet::encoding::Encoding;
 use crate::parquet::page::DataPage;
-use crate::parquet::schema::types::PrimitiveType;
+use crate::parquet::schema::types::{PhysicalType, PrimitiveLogicalType, PrimitiveType};
 use crate::parquet::statistics::{serialize_statistics, FixedLenStatistics};
 
 pub(crate) fn encode_plain(array: &FixedSizeBinaryArray, is_optional: bool, buffer: &mut Vec<u8>) {
@@ -23,6 +23,27 @@ pub(crate) fn encode_plain(array: &FixedSizeBinaryArray, is_optional: bool, buff
     }
 }
 
+/// Checks that `array` can be written as `type_`.
+///
+/// The physical type must be a `FIXED_LEN_BYTE_ARRAY` of the array's width and, when annotated
+/// as a decimal, that width must hold its precision.
+pub(super) fn check_type(array: &FixedSizeBinaryArray, type_: &PrimitiveType) -> PolarsResult<()> {
+    let size = array.size();
+    polars_ensure!(
+        type_.physical_type == PhysicalType::FixedLenByteArray(size),
+        SchemaMismatch: "cannot write a FixedSizeBinary({}) array as parquet type {:?}",
+        size, type_.physical_type
+    );
+    if let Some(PrimitiveLogicalType::Decimal(precision, _)) = type_.logical_type {
+        polars_ensure!(
+            decimal_length_from_precision(precision) <= size,
+            SchemaMismatch: "a decimal of precision {} does not fit in FIXED_LEN_BYTE_ARRAY({})",
+            precision, size
+        );
+    }
+    Ok(())
+}
+
 pub fn array_to_page(
     array: &FixedSizeBinaryArray,
     options: WriteOptions,
@@ -32,7 +53,8 @@ pub fn array_to_page(
     let is_optional = is_nullable(&type_.field_info);
     let validity = array.validity();
 
-    let mut buffer = vec![];
+    let mut buffer = utils::page_buffer(&options);
+    let levels_start = buffer.len();
     utils::write_def_levels(
         &mut buffer,
         is_optional,
@@ -41,7 +63,7 @@ pub fn array_to_page(
         options.version,
     )?;
 
-    let definition_levels_byte_length = buffer.len();
+    let definition_levels_byte_length = buffer.len() - levels_start;
 
     encode_plain(array, is_optional, &mut buffer);
 
@@ -59,6 +81,36 @@ pub fn array_to_page(
     )
 }
 
+pub fn nested_array_to_page(
+    array: &FixedSizeBinaryArray,
+    options: WriteOptions,
+    type_: PrimitiveType,
+    nested: &[Nested],
+    statistics: Option<FixedLenStatistics>,
+) -> PolarsResult<DataPage> {
+    let is_optional = nested::is_optional_leaf(&type_, array);
+
+    let mut buffer = utils::page_buffer(&options);
+    let info = nested::NestedInfo::new(nested);
+    let (repetition_levels_byte_length, definition_levels_byte_length) =
+        nested::write_rep_and_def_with_info(options.version, nested, &info, &mut buffer)?;
+
+    encode_plain(array, is_optional, &mut buffer);
+
+    utils::build_plain_page(
+        buffer,
+        info.value_count,
+        nested[0].len(),
+        array.null_count(),
+        repetition_levels_byte_length,
+        definition_levels_byte_length,
+        statistics.map(|x| serialize_statistics(&x)),
+        type_,
+        options,
+        Encoding::Plain,
+    )
+}
+
 pub(super) fn build_statistics(
     array: &FixedSizeBinaryArray,
     primitive_type: PrimitiveType,
@@ -145,3 +197,74 @@ pub(super) fn build_statistics_decimal256(
             .map(|x| x.0.to_be_bytes()[32 - size..].to_vec()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use arrow::datatypes::ArrowDataType;
+
+    use super::super::{array_to_page, to_parquet_leaves, Nested};
+    use super::*;
+    use crate::parquet::schema::types::{ParquetType, PrimitiveConvertedType};
+    use crate::parquet::schema::Repetition;
+
+    fn options() -> WriteOptions {
+        WriteOptions::builder().build().unwrap()
+    }
+
+    fn decimal128(size: usize, precision: usize) -> (FixedSizeBinaryArray, ParquetType) {
+        let values = [1i128, -2]
+            .iter()
+            .flat_map(|x| x.to_be_bytes()[16 - size..].to_vec())
+            .collect::<Vec<_>>();
+        let array =
+            FixedSizeBinaryArray::new(ArrowDataType::FixedSizeBinary(size), values.into(), None);
+        let type_ = ParquetType::try_from_primitive(
+            "d".to_string(),
+            PhysicalType::FixedLenByteArray(size),
+            Repetition::Required,
+            Some(PrimitiveConvertedType::Decimal(precision, 2)),
+            Some(PrimitiveLogicalType::Decimal(precision, 2)),
+            None,
+        )
+        .unwrap();
+        (array, type_)
+    }
+
+    #[test]
+    fn decimal() {
+        let (array, type_) = decimal128(16, 38);
+
+        let leaves = to_parquet_leaves(type_);
+        assert_eq!(leaves.len(), 1);
+        let leaf = leaves.into_iter().next().unwrap();
+        assert_eq!(leaf.physical_type, PhysicalType::FixedLenByteArray(16));
+        assert_eq!(
+            leaf.logical_type,
+            Some(PrimitiveLogicalType::Decimal(38, 2))
+        );
+
+        let nested = [Nested::Primitive(None, false, array.len())];
+        assert!(array_to_page(&array, leaf, &nested, options(), Encoding::Plain).is_ok());
+    }
+
+    #[test]
+    fn decimal_width_mismatch() {
+        let (array, _) = decimal128(16, 38);
+        let (_, type_) = decimal128(12, 20);
+        let leaf = to_parquet_leaves(type_).pop().unwrap();
+
+        let nested = [Nested::Primitive(None, false, array.len())];
+        assert!(array_to_page(&array, leaf, &nested, options(), Encoding::Plain).is_err());
+    }
+
+    #[test]
+    fn decimal_precision_mismatch() {
+        let (array, type_) = decimal128(4, 9);
+        let mut leaf = to_parquet_leaves(type_).pop().unwrap();
+        // bypasses the invariants checked when building the type
+        leaf.logical_type = Some(PrimitiveLogicalType::Decimal(20, 2));
+
+        let nested = [Nested::Primitive(None, false, array.len())];
+        assert!(array_to_page(&array, leaf, &nested, options(), Encoding::Plain).is_err());
+    }
+}
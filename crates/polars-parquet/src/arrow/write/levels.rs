@@ -0,0 +1,110 @@
+//! The computation of the repetition and definition levels of a column, for writers that
+//! encode the values of a [`Nested`] path themselves.
+//!
+//! A path describes a single leaf column, from the outermost array to the leaf, as returned
+//! by [`to_nested`](super::to_nested). Its last [`Nested`] must be a [`Nested::Primitive`]:
+//! the functions of this module return an error otherwise.
+
+use polars_error::{polars_ensure, PolarsResult};
+
+use super::nested;
+pub use super::pages::{ListNested, Nested};
+use crate::parquet::write::Version;
+
+fn check_path(nested: &[Nested]) -> PolarsResult<()> {
+    polars_ensure!(
+        matches!(nested.last(), Some(Nested::Primitive(_, _, _))),
+        InvalidOperation: "the last nested of a column must be a primitive"
+    );
+    Ok(())
+}
+
+/// Returns the `(repetition, definition)` levels of `nested`.
+pub fn to_levels(nested: &[Nested]) -> PolarsResult<(Vec<u32>, Vec<u32>)> {
+    check_path(nested)?;
+    nested::compute_levels(nested)
+}
+
+/// Returns the repetition levels of `nested`.
+pub fn calculate_rep_levels(nested: &[Nested]) -> PolarsResult<Vec<u32>> {
+    check_path(nested)?;
+    Ok(nested::rep_levels(nested))
+}
+
+/// Returns the definition levels of `nested`.
+pub fn calculate_def_levels(nested: &[Nested]) -> PolarsResult<Vec<u32>> {
+    check_path(nested)?;
+    Ok(nested::def_levels(nested))
+}
+
+/// Returns the number of levels of `nested`, i.e. the number of leaf values plus one per null
+/// or empty list above them.
+pub fn num_values(nested: &[Nested]) -> PolarsResult<usize> {
+    check_path(nested)?;
+    Ok(nested::num_values(nested))
+}
+
+/// Returns the maximum `(definition, repetition)` levels of `nested`.
+pub fn max_level(nested: &[Nested]) -> PolarsResult<(u32, u32)> {
+    check_path(nested)?;
+    Ok(nested::max_level(nested))
+}
+
+/// Writes the levels of `nested` to `buffer` as they are laid out in a data page of
+/// `page_version`, and returns the byte lengths of its repetition and definition levels.
+pub fn write_rep_and_def(
+    page_version: Version,
+    nested: &[Nested],
+    buffer: &mut Vec<u8>,
+) -> PolarsResult<(usize, usize)> {
+    check_path(nested)?;
+    nested::write_rep_and_def(page_version, nested, buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn external_path() {
+        // [[1, None], None, [], [4]]
+        let nested = vec![
+            Nested::List(ListNested::new(
+                vec![0, 2, 2, 2, 3].try_into().unwrap(),
+                Some([true, false, true, true].into()),
+                true,
+            )),
+            Nested::Primitive(Some([true, false, true].into()), true, 3),
+        ];
+
+        let rep = calculate_rep_levels(&nested).unwrap();
+        let def = calculate_def_levels(&nested).unwrap();
+        assert_eq!(rep, vec![0, 1, 0, 0, 0]);
+        assert_eq!(def, vec![3, 2, 0, 1, 3]);
+        assert_eq!(num_values(&nested).unwrap(), rep.len());
+        assert_eq!(to_levels(&nested).unwrap(), (rep, def));
+        assert_eq!(max_level(&nested).unwrap(), (3, 1));
+
+        let mut buffer = vec![];
+        let (rep_length, def_length) =
+            write_rep_and_def(Version::V2, &nested, &mut buffer).unwrap();
+        assert_eq!(rep_length + def_length, buffer.len());
+    }
+
+    #[test]
+    fn path_without_primitive() {
+        let nested = vec![Nested::List(ListNested::new(
+            vec![0, 1].try_into().unwrap(),
+            None,
+            false,
+        ))];
+
+        assert!(to_levels(&nested).is_err());
+        assert!(calculate_rep_levels(&nested).is_err());
+        assert!(calculate_def_levels(&nested).is_err());
+        assert!(num_values(&nested).is_err());
+        assert!(max_level(&nested).is_err());
+        assert!(write_rep_and_def(Version::V1, &nested, &mut vec![]).is_err());
+        assert!(to_levels(&[]).is_err());
+    }
+}
@@ -9,15 +9,21 @@
 //! * `ArrowDataType::Duration`
 //! * `ArrowDataType::Date64`
 //! * `ArrowDataType::Time32(TimeUnit::Second)`
+//! * `ArrowDataType::Float16`, whose `FLOAT16` logical type was introduced in parquet-format
+//!   v2.10. It is written as a `FIXED_LEN_BYTE_ARRAY(2)` of the little-endian IEEE 754 half
+//!   precision bytes, as that logical type specifies.
 //!
 //! The use of these arrow types will result in no logical type being stored within a parquet file.
 
 mod binary;
 mod binview;
+#[cfg(feature = "bloom_filter")]
+mod bloom_filter;
 mod boolean;
 mod dictionary;
 mod file;
 mod fixed_len_bytes;
+pub mod levels;
 mod nested;
 mod pages;
 mod primitive;
@@ -29,15 +35,24 @@ mod utils;
 
 use arrow::array::*;
 use arrow::datatypes::*;
-use arrow::types::{days_ms, i256, NativeType};
-pub use nested::{num_values, write_rep_and_def};
-pub use pages::{to_leaves, to_nested, to_parquet_leaves};
+use arrow::types::{days_ms, f16, i256, months_days_ns, NativeType};
+#[cfg(feature = "bloom_filter")]
+use bloom_filter::with_bloom_filter;
+#[cfg(feature = "bloom_filter")]
+pub use bloom_filter::{bloom_filter_num_bytes, insert_into_bloom_filter};
+pub use nested::{
+    bit_width, compute_levels, compute_write_plan, deserialize_nested, is_fully_defined,
+    leaf_record_boundary_rep_level, leaf_repetition_depth, max_level, num_values, page_null_counts,
+    plan_level_widths, serialize_nested, to_levels_with_offsets, validate_levels, validate_nested,
+    write_rep_and_def, written_value_count, WritePlan,
+};
+pub use pages::{leaf_paths, nested_from_arrow, to_leaves, to_nested, to_parquet_leaves};
 pub use utils::write_def_levels;
 
 pub use crate::parquet::compression::{BrotliLevel, CompressionOptions, GzipLevel, ZstdLevel};
 pub use crate::parquet::encoding::Encoding;
 pub use crate::parquet::metadata::{
-    Descriptor, FileMetaData, KeyValue, SchemaDescriptor, ThriftFileMetaData,
+    Descriptor, FileMetaData, KeyValue, SchemaDescriptor, SortingColumn, ThriftFileMetaData,
 };
 pub use crate::parquet::page::{CompressedDataPage, CompressedPage, Page};
 use crate::parquet::schema::types::PrimitiveType as ParquetPrimitiveType;
@@ -50,8 +65,16 @@ pub use crate::parquet::write::{
 };
 pub use crate::parquet::{fallible_streaming_iterator, FallibleStreamingIterator};
 
+/// Options of the split-block bloom filter written for every column chunk.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BloomFilterOptions {
+    /// The false positive probability the filter is sized for, assuming that every value of
+    /// the column chunk is distinct.
+    pub false_positive_probability: f64,
+}
+
 /// Currently supported options to write to parquet
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct WriteOptions {
     /// Whether to write statistics
     pub write_statistics: bool,
@@ -61,13 +84,176 @@ pub struct WriteOptions {
     pub compression: CompressionOptions,
     /// The size to flush a page, defaults to 1024 * 1024 if None
     pub data_pagesize_limit: Option<usize>,
+    /// Bytes written at the start of every data page, before its levels.
+    ///
+    /// This is a compatibility escape hatch for readers that expect the level stream to
+    /// start with specific bytes; the resulting pages are not readable by other readers.
+    /// The bytes are accounted as part of the repetition levels in v2 pages.
+    pub level_stream_prefix: Option<Vec<u8>>,
+    /// Whether to error when a required field has nulls that are not inherited from a null
+    /// parent, instead of writing them as if they were valid.
+    pub strict: bool,
+    /// Whether to write the CRC32 checksum of every (compressed) page in its header, so
+    /// that readers can detect corrupted pages.
+    pub write_page_checksums: bool,
+    /// The number of bytes above which the plain-encoded dictionary of a dictionary-encoded
+    /// column makes it fall back to the plain encoding for the whole column. This applies both
+    /// to the columns that the writer dictionary-encodes and to dictionary arrays, whose values
+    /// are then decoded. `None` means that only the cardinality of the column is considered.
+    pub dictionary_size_limit: Option<usize>,
+    /// Whether to write a bloom filter for every column chunk whose type supports one.
+    /// Requires the `bloom_filter` feature.
+    pub bloom_filter: Option<BloomFilterOptions>,
+}
+
+impl WriteOptions {
+    /// Returns a [`WriteOptionsBuilder`], whose options are validated when built.
+    pub fn builder() -> WriteOptionsBuilder {
+        WriteOptionsBuilder::default()
+    }
+}
+
+/// A builder of [`WriteOptions`] that validates their coherence in [`Self::build`], instead of
+/// when the first page is written.
+///
+/// By default, statistics are written to uncompressed v1 pages of the default size.
+#[derive(Debug, Clone)]
+pub struct WriteOptionsBuilder {
+    options: WriteOptions,
+}
+
+impl Default for WriteOptionsBuilder {
+    fn default() -> Self {
+        Self {
+            options: WriteOptions {
+                write_statistics: true,
+                version: Version::V1,
+                compression: CompressionOptions::Uncompressed,
+                data_pagesize_limit: None,
+                level_stream_prefix: None,
+                strict: false,
+                write_page_checksums: false,
+                dictionary_size_limit: None,
+                bloom_filter: None,
+            },
+        }
+    }
+}
+
+impl WriteOptionsBuilder {
+    /// Sets [`WriteOptions::write_statistics`].
+    pub fn write_statistics(mut self, write_statistics: bool) -> Self {
+        self.options.write_statistics = write_statistics;
+        self
+    }
+
+    /// Sets [`WriteOptions::version`].
+    pub fn version(mut self, version: Version) -> Self {
+        self.options.version = version;
+        self
+    }
+
+    /// Sets [`WriteOptions::compression`].
+    pub fn compression(mut self, compression: CompressionOptions) -> Self {
+        self.options.compression = compression;
+        self
+    }
+
+    /// Sets [`WriteOptions::data_pagesize_limit`].
+    pub fn data_pagesize_limit(mut self, data_pagesize_limit: Option<usize>) -> Self {
+        self.options.data_pagesize_limit = data_pagesize_limit;
+        self
+    }
+
+    /// Sets [`WriteOptions::level_stream_prefix`].
+    pub fn level_stream_prefix(mut self, level_stream_prefix: Option<Vec<u8>>) -> Self {
+        self.options.level_stream_prefix = level_stream_prefix;
+        self
+    }
+
+    /// Sets [`WriteOptions::strict`].
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.options.strict = strict;
+        self
+    }
+
+    /// Sets [`WriteOptions::write_page_checksums`].
+    pub fn write_page_checksums(mut self, write_page_checksums: bool) -> Self {
+        self.options.write_page_checksums = write_page_checksums;
+        self
+    }
+
+    /// Sets [`WriteOptions::dictionary_size_limit`].
+    pub fn dictionary_size_limit(mut self, dictionary_size_limit: Option<usize>) -> Self {
+        self.options.dictionary_size_limit = dictionary_size_limit;
+        self
+    }
+
+    /// Sets [`WriteOptions::bloom_filter`].
+    pub fn bloom_filter(mut self, bloom_filter: Option<BloomFilterOptions>) -> Self {
+        self.options.bloom_filter = bloom_filter;
+        self
+    }
+
+    /// Returns the [`WriteOptions`].
+    /// # Errors
+    /// Errors if
+    /// * the compression cannot be written, either because it is deprecated or because the
+    ///   feature of its codec is not active
+    /// * the page size limit is zero or larger than the `i32::MAX` bytes a page can hold
+    /// * a bloom filter is requested without the `bloom_filter` feature, or with a false
+    ///   positive probability outside of `(0, 1)`
+    pub fn build(self) -> PolarsResult<WriteOptions> {
+        let options = self.options;
+        let codec_is_active = match options.compression {
+            CompressionOptions::Uncompressed => true,
+            CompressionOptions::Snappy => cfg!(feature = "snappy"),
+            CompressionOptions::Gzip(_) => cfg!(feature = "gzip"),
+            CompressionOptions::Brotli(_) => cfg!(feature = "brotli"),
+            CompressionOptions::Zstd(_) => cfg!(feature = "zstd"),
+            CompressionOptions::Lz4Raw => cfg!(feature = "lz4"),
+            CompressionOptions::Lzo | CompressionOptions::Lz4 => polars_bail!(InvalidOperation:
+                "the compression {:?} cannot be written, as it is deprecated by the parquet format",
+                options.compression
+            ),
+        };
+        polars_ensure!(
+            codec_is_active,
+            InvalidOperation: "the compression {:?} requires its feature to be active",
+            options.compression
+        );
+        if let Some(limit) = options.data_pagesize_limit {
+            polars_ensure!(
+                limit > 0 && limit <= i32::MAX as usize,
+                InvalidOperation: "the page size limit must be between 1 and {} bytes, got {}",
+                i32::MAX, limit
+            );
+        }
+        if let Some(bloom_filter) = options.bloom_filter {
+            polars_ensure!(
+                cfg!(feature = "bloom_filter"),
+                InvalidOperation: "writing bloom filters requires the `bloom_filter` feature"
+            );
+            let fpp = bloom_filter.false_positive_probability;
+            polars_ensure!(
+                fpp > 0.0 && fpp < 1.0,
+                InvalidOperation: "the false positive probability of a bloom filter must be between 0 and 1, got {}",
+                fpp
+            );
+        }
+        Ok(options)
+    }
 }
 
 use arrow::compute::aggregate::estimated_bytes_size;
 use arrow::match_integer_type;
+use arrow::offset::{Offset, OffsetsBuffer};
 pub use file::FileWriter;
-pub use pages::{array_to_columns, arrays_to_columns, Nested};
-use polars_error::{polars_bail, PolarsResult};
+pub use pages::{
+    array_to_columns, array_to_columns_sorted, array_to_columns_with, arrays_to_columns,
+    record_batch_to_columns, ColumnSortOrder, Nested,
+};
+use polars_error::{polars_bail, polars_ensure, PolarsResult};
 pub use row_group::{row_group_iter, RowGroupIterator};
 pub use schema::to_parquet_type;
 #[cfg(feature = "async")]
@@ -87,7 +273,7 @@ pub fn slice_nested_leaf(nested: &[Nested]) -> (usize, usize) {
                 let end = *l_nested.offsets.last();
                 return (start as usize, (end - start) as usize);
             },
-            Nested::List(l_nested) => {
+            Nested::List(l_nested) | Nested::Map(l_nested, _) => {
                 let start = *l_nested.offsets.first();
                 let end = *l_nested.offsets.last();
                 return (start as usize, (end - start) as usize);
@@ -129,55 +315,69 @@ pub fn slice_parquet_array(
     mut current_length: usize,
 ) {
     for nested in nested.iter_mut() {
-        match nested {
-            Nested::LargeList(l_nested) => {
-                l_nested.offsets.slice(current_offset, current_length + 1);
-                if let Some(validity) = l_nested.validity.as_mut() {
-                    validity.slice(current_offset, current_length)
-                };
-
-                // Update the offset/ length so that the Primitive is sliced properly.
-                current_length = l_nested.offsets.range() as usize;
-                current_offset = *l_nested.offsets.first() as usize;
-            },
-            Nested::List(l_nested) => {
-                l_nested.offsets.slice(current_offset, current_length + 1);
-                if let Some(validity) = l_nested.validity.as_mut() {
-                    validity.slice(current_offset, current_length)
-                };
-
-                // Update the offset/ length so that the Primitive is sliced properly.
-                current_length = l_nested.offsets.range() as usize;
-                current_offset = *l_nested.offsets.first() as usize;
-            },
-            Nested::Struct(validity, _, length) => {
-                *length = current_length;
-                if let Some(validity) = validity.as_mut() {
-                    validity.slice(current_offset, current_length)
-                };
-            },
-            Nested::Primitive(validity, _, length) => {
-                *length = current_length;
-                if let Some(validity) = validity.as_mut() {
-                    validity.slice(current_offset, current_length)
-                };
-                primitive_array.slice(current_offset, current_length);
-            },
-            Nested::FixedSizeList {
-                validity,
-                len,
-                width,
-                ..
-            } => {
-                if let Some(validity) = validity.as_mut() {
-                    validity.slice(current_offset, current_length)
-                };
-                *len = current_length;
-                // Update the offset/ length so that the Primitive is sliced properly.
-                current_length *= *width;
-                current_offset *= *width;
-            },
+        if let Nested::Primitive(_, _, _) = nested {
+            primitive_array.slice(current_offset, current_length);
         }
+        // Update the offset/ length so that the Primitive is sliced properly.
+        (current_offset, current_length) = slice_nested(nested, current_offset, current_length);
+    }
+}
+
+/// Slices `nested` to `length` rows starting at `offset`, and returns the offset and length of
+/// the slice of its children.
+fn slice_nested(nested: &mut Nested, offset: usize, length: usize) -> (usize, usize) {
+    match nested {
+        Nested::LargeList(l_nested) => {
+            l_nested.offsets.slice(offset, length + 1);
+            if let Some(validity) = l_nested.validity.as_mut() {
+                validity.slice(offset, length)
+            };
+            (
+                *l_nested.offsets.first() as usize,
+                l_nested.offsets.range() as usize,
+            )
+        },
+        Nested::List(l_nested) => {
+            l_nested.offsets.slice(offset, length + 1);
+            if let Some(validity) = l_nested.validity.as_mut() {
+                validity.slice(offset, length)
+            };
+            (
+                *l_nested.offsets.first() as usize,
+                l_nested.offsets.range() as usize,
+            )
+        },
+        Nested::Map(l_nested, entries) => {
+            l_nested.offsets.slice(offset, length + 1);
+            if let Some(validity) = l_nested.validity.as_mut() {
+                validity.slice(offset, length)
+            };
+            let offset = *l_nested.offsets.first() as usize;
+            let length = l_nested.offsets.range() as usize;
+            let (key, value) = entries.as_mut();
+            slice_nested(key, offset, length);
+            slice_nested(value, offset, length);
+            (offset, length)
+        },
+        Nested::Struct(validity, _, len) | Nested::Primitive(validity, _, len) => {
+            *len = length;
+            if let Some(validity) = validity.as_mut() {
+                validity.slice(offset, length)
+            };
+            (offset, length)
+        },
+        Nested::FixedSizeList {
+            validity,
+            len,
+            width,
+            ..
+        } => {
+            if let Some(validity) = validity.as_mut() {
+                validity.slice(offset, length)
+            };
+            *len = length;
+            (offset * *width, length * *width)
+        },
     }
 }
 
@@ -187,7 +387,9 @@ pub fn get_max_length(nested: &[Nested]) -> usize {
     for nested in nested.iter() {
         match nested {
             Nested::LargeList(l_nested) => length += l_nested.offsets.range() as usize,
-            Nested::List(l_nested) => length += l_nested.offsets.range() as usize,
+            Nested::List(l_nested) | Nested::Map(l_nested, _) => {
+                length += l_nested.offsets.range() as usize
+            },
             Nested::FixedSizeList { len, width, .. } => length += *len * *width,
             _ => {},
         }
@@ -196,15 +398,69 @@ pub fn get_max_length(nested: &[Nested]) -> usize {
 }
 
 /// Returns an iterator of [`Page`].
+///
+/// When [`WriteOptions::bloom_filter`] is set, the first data page carries the bloom filter of
+/// the values of `primitive_array` that `nested` selects.
 pub fn array_to_pages(
+    primitive_array: &dyn Array,
+    type_: ParquetPrimitiveType,
+    nested: &[Nested],
+    options: WriteOptions,
+    encoding: Encoding,
+) -> PolarsResult<DynIter<'static, PolarsResult<Page>>> {
+    let bloom_filter = options.bloom_filter;
+    let pages = array_to_pages_impl(primitive_array, type_, nested, options, encoding)?;
+    match bloom_filter {
+        Some(bloom_filter) => {
+            let (start, length) = slice_nested_leaf(nested);
+            let leaf = primitive_array.sliced(start, length);
+            with_bloom_filter(pages, &[leaf.as_ref()], &bloom_filter)
+        },
+        None => Ok(pages),
+    }
+}
+
+#[cfg(not(feature = "bloom_filter"))]
+fn with_bloom_filter(
+    _: DynIter<'static, PolarsResult<Page>>,
+    _: &[&dyn Array],
+    _: &BloomFilterOptions,
+) -> PolarsResult<DynIter<'static, PolarsResult<Page>>> {
+    polars_bail!(InvalidOperation: "writing bloom filters requires the `bloom_filter` feature")
+}
+
+fn array_to_pages_impl(
     primitive_array: &dyn Array,
     type_: ParquetPrimitiveType,
     nested: &[Nested],
     options: WriteOptions,
     mut encoding: Encoding,
 ) -> PolarsResult<DynIter<'static, PolarsResult<Page>>> {
+    let with_parent_nulls = nested::with_parent_nulls(primitive_array, &type_, nested);
+    let primitive_array = with_parent_nulls.as_deref().unwrap_or(primitive_array);
+
     if let ArrowDataType::Dictionary(key_type, _, _) = primitive_array.data_type().to_logical_type()
     {
+        if matches!(
+            encoding,
+            Encoding::PlainDictionary | Encoding::RleDictionary
+        ) {
+            let decoded = match_integer_type!(key_type, |$T| {
+                dictionary::decode_over_size_limit::<$T>(
+                    primitive_array.as_any().downcast_ref().unwrap(),
+                    &options,
+                )
+            });
+            if let Some(decoded) = decoded {
+                return array_to_pages_impl(
+                    decoded.as_ref(),
+                    type_,
+                    nested,
+                    options,
+                    Encoding::Plain,
+                );
+            }
+        }
         return match_integer_type!(key_type, |$T| {
             dictionary::array_to_pages::<$T>(
                 primitive_array.as_any().downcast_ref().unwrap(),
@@ -218,9 +474,12 @@ pub fn array_to_pages(
     if let Encoding::RleDictionary = encoding {
         // Only take this path for primitive columns
         if matches!(nested.first(), Some(Nested::Primitive(_, _, _))) {
-            if let Some(result) =
-                encode_as_dictionary_optional(primitive_array, nested, type_.clone(), options)
-            {
+            if let Some(result) = encode_as_dictionary_optional(
+                primitive_array,
+                nested,
+                type_.clone(),
+                options.clone(),
+            ) {
                 return result;
             }
         }
@@ -235,7 +494,12 @@ pub fn array_to_pages(
 
     // note: this is not correct if the array is sliced - the estimation should happen on the
     // primitive after sliced for parquet
-    let byte_size = estimated_bytes_size(primitive_array);
+    let mut byte_size = estimated_bytes_size(primitive_array);
+    if nested.len() > 1 {
+        // pages are split by rows, so that a list is never split across pages. The levels of
+        // nested columns can take a significant part of such a page.
+        byte_size += nested::estimated_levels_size(&nested);
+    }
 
     const DEFAULT_PAGE_SIZE: usize = 1024 * 1024;
     let max_page_size = options.data_pagesize_limit.unwrap_or(DEFAULT_PAGE_SIZE);
@@ -269,13 +533,107 @@ pub fn array_to_pages(
             right_array.as_ref(),
             type_.clone(),
             &right_nested,
-            options,
+            options.clone(),
             encoding,
         )
     });
     Ok(DynIter::new(pages))
 }
 
+/// Estimates the number of bytes of the leaf `array` with nesting `nested` once encoded with
+/// `encoding`, without encoding it.
+///
+/// The estimate is the size of the bitpacked repetition and definition levels plus the size of
+/// the values. Encodings whose size depends on the values themselves (e.g. the delta encodings)
+/// are estimated by their plain size, except for the lengths of byte arrays under
+/// [`Encoding::DeltaLengthByteArray`]. Non-dictionary arrays are estimated by their plain size
+/// for [`Encoding::RleDictionary`], as the writer falls back to it for high cardinality.
+/// Page headers, statistics and compression are not accounted for.
+pub fn estimate_encoded_size(array: &dyn Array, nested: &[Nested], encoding: Encoding) -> usize {
+    nested::estimated_levels_size(nested) + estimated_values_size(array, encoding)
+}
+
+fn estimated_values_size(array: &dyn Array, encoding: Encoding) -> usize {
+    use ArrowDataType::*;
+    let num_valid = array.len() - array.null_count();
+    let binary = |bytes: usize, max_length: usize| match encoding {
+        Encoding::DeltaLengthByteArray => {
+            bytes + (num_valid * utils::get_bit_width(max_length as u64) as usize).div_ceil(8)
+        },
+        _ => bytes + num_valid * std::mem::size_of::<u32>(),
+    };
+    // the number of bytes and the maximum length of the values of a byte array
+    fn bytes_and_max_length<O: Offset>(offsets: &OffsetsBuffer<O>) -> (usize, usize) {
+        let max_length = offsets.lengths().max().unwrap_or(0);
+        (offsets.range().to_usize(), max_length)
+    }
+
+    match array.data_type().to_logical_type() {
+        Null => 0,
+        Boolean => num_valid.div_ceil(8),
+        Int8 | Int16 | Int32 | UInt8 | UInt16 | UInt32 | Date32 | Time32(_) | Float32 => {
+            num_valid * std::mem::size_of::<i32>()
+        },
+        Int64 | UInt64 | Date64 | Time64(_) | Timestamp(_, _) | Duration(_) | Float64 => {
+            num_valid * std::mem::size_of::<i64>()
+        },
+        Decimal(precision, _) | Decimal256(precision, _) => {
+            let size = match *precision {
+                ..=9 => 4,
+                10..=18 => 8,
+                precision => decimal_length_from_precision(precision),
+            };
+            num_valid * size
+        },
+        Float16 => num_valid * std::mem::size_of::<f16>(),
+        Interval(_) => num_valid * 12,
+        FixedSizeBinary(size) => num_valid * size,
+        Binary => {
+            let array = array.as_any().downcast_ref::<BinaryArray<i32>>().unwrap();
+            let (bytes, max_length) = bytes_and_max_length(array.offsets());
+            binary(bytes, max_length)
+        },
+        LargeBinary => {
+            let array = array.as_any().downcast_ref::<BinaryArray<i64>>().unwrap();
+            let (bytes, max_length) = bytes_and_max_length(array.offsets());
+            binary(bytes, max_length)
+        },
+        Utf8 => {
+            let array = array.as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
+            let (bytes, max_length) = bytes_and_max_length(array.offsets());
+            binary(bytes, max_length)
+        },
+        LargeUtf8 => {
+            let array = array.as_any().downcast_ref::<Utf8Array<i64>>().unwrap();
+            let (bytes, max_length) = bytes_and_max_length(array.offsets());
+            binary(bytes, max_length)
+        },
+        BinaryView | Utf8View => {
+            let array = if let Utf8View = array.data_type() {
+                let array = array.as_any().downcast_ref::<Utf8ViewArray>().unwrap();
+                array.to_binview()
+            } else {
+                array
+                    .as_any()
+                    .downcast_ref::<BinaryViewArray>()
+                    .unwrap()
+                    .clone()
+            };
+            let max_length = array.len_iter().max().unwrap_or(0);
+            binary(array.total_bytes_len(), max_length as usize)
+        },
+        Dictionary(key_type, _, _) => match_integer_type!(key_type, |$T| {
+            let array = array.as_any().downcast_ref::<DictionaryArray<$T>>().unwrap();
+            let values = array.values();
+            let num_bits = utils::get_bit_width(values.len().saturating_sub(1) as u64);
+            // the bit width, the indices, and the dictionary page
+            1 + (num_valid * num_bits as usize).div_ceil(8)
+                + estimated_values_size(values.as_ref(), Encoding::Plain)
+        }),
+        _ => estimated_bytes_size(array),
+    }
+}
+
 /// Converts an [`Array`] to a [`CompressedPage`] based on options, descriptor and `encoding`.
 pub fn array_to_page(
     array: &dyn Array,
@@ -373,16 +731,22 @@ pub fn array_to_page_simple(
                 encoding,
             )
         },
-        ArrowDataType::Float32 => primitive::array_to_page_plain::<f32, f32>(
-            array.as_any().downcast_ref().unwrap(),
-            options,
-            type_,
-        ),
-        ArrowDataType::Float64 => primitive::array_to_page_plain::<f64, f64>(
-            array.as_any().downcast_ref().unwrap(),
-            options,
-            type_,
-        ),
+        ArrowDataType::Float32 => {
+            return primitive::array_to_page_float::<f32, f32>(
+                array.as_any().downcast_ref().unwrap(),
+                options,
+                type_,
+                encoding,
+            )
+        },
+        ArrowDataType::Float64 => {
+            return primitive::array_to_page_float::<f64, f64>(
+                array.as_any().downcast_ref().unwrap(),
+                options,
+                type_,
+                encoding,
+            )
+        },
         ArrowDataType::LargeUtf8 => {
             let array =
                 arrow::compute::cast::cast(array, &ArrowDataType::LargeBinary, Default::default())
@@ -425,22 +789,8 @@ pub fn array_to_page_simple(
             let array = Int32Array::new_null(ArrowDataType::Int32, array.len());
             primitive::array_to_page_plain::<i32, i32>(&array, options, type_)
         },
-        ArrowDataType::Interval(IntervalUnit::YearMonth) => {
-            let array = array
-                .as_any()
-                .downcast_ref::<PrimitiveArray<i32>>()
-                .unwrap();
-            let mut values = Vec::<u8>::with_capacity(12 * array.len());
-            array.values().iter().for_each(|x| {
-                let bytes = &x.to_le_bytes();
-                values.extend_from_slice(bytes);
-                values.extend_from_slice(&[0; 8]);
-            });
-            let array = FixedSizeBinaryArray::new(
-                ArrowDataType::FixedSizeBinary(12),
-                values.into(),
-                array.validity().cloned(),
-            );
+        ArrowDataType::Interval(unit) => {
+            let array = interval_to_fixed_size_binary(array, *unit)?;
             let statistics = if options.write_statistics {
                 Some(fixed_len_bytes::build_statistics(&array, type_.clone()))
             } else {
@@ -448,31 +798,15 @@ pub fn array_to_page_simple(
             };
             fixed_len_bytes::array_to_page(&array, options, type_, statistics)
         },
-        ArrowDataType::Interval(IntervalUnit::DayTime) => {
-            let array = array
-                .as_any()
-                .downcast_ref::<PrimitiveArray<days_ms>>()
-                .unwrap();
-            let mut values = Vec::<u8>::with_capacity(12 * array.len());
-            array.values().iter().for_each(|x| {
-                let bytes = &x.to_le_bytes();
-                values.extend_from_slice(&[0; 4]); // months
-                values.extend_from_slice(bytes); // days and seconds
-            });
-            let array = FixedSizeBinaryArray::new(
-                ArrowDataType::FixedSizeBinary(12),
-                values.into(),
-                array.validity().cloned(),
-            );
-            let statistics = if options.write_statistics {
-                Some(fixed_len_bytes::build_statistics(&array, type_.clone()))
-            } else {
-                None
-            };
-            fixed_len_bytes::array_to_page(&array, options, type_, statistics)
+        ArrowDataType::Float16 => {
+            let array = float16_to_fixed_size_binary(array.as_any().downcast_ref().unwrap());
+            fixed_len_bytes::check_type(&array, &type_)?;
+            // the bytes of a float do not sort as the float, so no statistics are written
+            fixed_len_bytes::array_to_page(&array, options, type_, None)
         },
         ArrowDataType::FixedSizeBinary(_) => {
             let array = array.as_any().downcast_ref().unwrap();
+            fixed_len_bytes::check_type(array, &type_)?;
             let statistics = if options.write_statistics {
                 Some(fixed_len_bytes::build_statistics(array, type_.clone()))
             } else {
@@ -542,6 +876,7 @@ pub fn array_to_page_simple(
                     values.into(),
                     array.validity().cloned(),
                 );
+                fixed_len_bytes::check_type(&array, &type_)?;
                 fixed_len_bytes::array_to_page(&array, options, type_, statistics)
             } else {
                 let size = 32;
@@ -567,6 +902,7 @@ pub fn array_to_page_simple(
                     array.validity().cloned(),
                 );
 
+                fixed_len_bytes::check_type(&array, &type_)?;
                 fixed_len_bytes::array_to_page(&array, options, type_, statistics)
             }
         },
@@ -637,12 +973,102 @@ pub fn array_to_page_simple(
     .map(Page::Data)
 }
 
+/// Converts `array` to the `FIXED_LEN_BYTE_ARRAY(2)` of the little-endian bytes of its values.
+fn float16_to_fixed_size_binary(array: &PrimitiveArray<f16>) -> FixedSizeBinaryArray {
+    let values = array
+        .values()
+        .iter()
+        .flat_map(|x| x.to_le_bytes())
+        .collect::<Vec<_>>();
+    FixedSizeBinaryArray::new(
+        ArrowDataType::FixedSizeBinary(2),
+        values.into(),
+        array.validity().cloned(),
+    )
+}
+
+/// Converts an interval array to parquet's `INTERVAL` representation: 12 bytes holding the
+/// months, days and milliseconds as little-endian unsigned 4-byte integers.
+///
+/// The nanoseconds of a [`IntervalUnit::MonthDayNano`] are truncated to milliseconds. Errors if
+/// a valid interval has a negative component, which parquet can't represent.
+fn interval_to_fixed_size_binary(
+    array: &dyn Array,
+    unit: IntervalUnit,
+) -> PolarsResult<FixedSizeBinaryArray> {
+    let mut values = Vec::<u8>::with_capacity(12 * array.len());
+    match unit {
+        IntervalUnit::YearMonth => {
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<i32>>()
+                .unwrap();
+            for x in array.iter() {
+                let Some(x) = x else {
+                    values.extend_from_slice(&[0; 12]);
+                    continue;
+                };
+                extend_interval(&mut values, [*x as i64, 0, 0])?;
+            }
+        },
+        IntervalUnit::DayTime => {
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<days_ms>>()
+                .unwrap();
+            for x in array.iter() {
+                let Some(x) = x else {
+                    values.extend_from_slice(&[0; 12]);
+                    continue;
+                };
+                extend_interval(&mut values, [0, x.days() as i64, x.milliseconds() as i64])?;
+            }
+        },
+        IntervalUnit::MonthDayNano => {
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<months_days_ns>>()
+                .unwrap();
+            for x in array.iter() {
+                let Some(x) = x else {
+                    values.extend_from_slice(&[0; 12]);
+                    continue;
+                };
+                let components = [x.months() as i64, x.days() as i64, x.ns() / 1_000_000];
+                extend_interval(&mut values, components)?;
+            }
+        },
+    }
+    Ok(FixedSizeBinaryArray::new(
+        ArrowDataType::FixedSizeBinary(12),
+        values.into(),
+        array.validity().cloned(),
+    ))
+}
+
+/// Writes the months, days and milliseconds of an interval as unsigned 4-byte integers.
+fn extend_interval(values: &mut Vec<u8>, components: [i64; 3]) -> PolarsResult<()> {
+    for (component, name) in components
+        .into_iter()
+        .zip(["months", "days", "milliseconds"])
+    {
+        let Ok(component) = u32::try_from(component) else {
+            polars_bail!(ComputeError:
+                "an interval of {} {} does not fit the unsigned 4 bytes of a parquet INTERVAL",
+                component, name
+            )
+        };
+        values.extend_from_slice(&component.to_le_bytes());
+    }
+    Ok(())
+}
+
 fn array_to_page_nested(
     array: &dyn Array,
     type_: ParquetPrimitiveType,
     nested: &[Nested],
     options: WriteOptions,
-    _encoding: Encoding,
+    encoding: Encoding,
 ) -> PolarsResult<Page> {
     use ArrowDataType::*;
     match array.data_type().to_logical_type() {
@@ -658,60 +1084,106 @@ fn array_to_page_nested(
             let array =
                 arrow::compute::cast::cast(array, &LargeBinary, Default::default()).unwrap();
             let array = array.as_any().downcast_ref().unwrap();
-            binary::nested_array_to_page::<i64>(array, options, type_, nested)
+            binary::nested_array_to_page::<i64>(array, options, type_, nested, encoding)
         },
         LargeBinary => {
             let array = array.as_any().downcast_ref().unwrap();
-            binary::nested_array_to_page::<i64>(array, options, type_, nested)
+            binary::nested_array_to_page::<i64>(array, options, type_, nested, encoding)
         },
         BinaryView => {
             let array = array.as_any().downcast_ref().unwrap();
-            binview::nested_array_to_page(array, options, type_, nested)
+            binview::nested_array_to_page(array, options, type_, nested, encoding)
         },
         Utf8View => {
             let array = arrow::compute::cast::cast(array, &BinaryView, Default::default()).unwrap();
             let array = array.as_any().downcast_ref().unwrap();
-            binview::nested_array_to_page(array, options, type_, nested)
+            binview::nested_array_to_page(array, options, type_, nested, encoding)
         },
         UInt8 => {
             let array = array.as_any().downcast_ref().unwrap();
-            primitive::nested_array_to_page::<u8, i32>(array, options, type_, nested)
+            primitive::nested_array_to_page_integer::<u8, i32>(
+                array, options, type_, nested, encoding,
+            )
         },
         UInt16 => {
             let array = array.as_any().downcast_ref().unwrap();
-            primitive::nested_array_to_page::<u16, i32>(array, options, type_, nested)
+            primitive::nested_array_to_page_integer::<u16, i32>(
+                array, options, type_, nested, encoding,
+            )
         },
         UInt32 => {
             let array = array.as_any().downcast_ref().unwrap();
-            primitive::nested_array_to_page::<u32, i32>(array, options, type_, nested)
+            primitive::nested_array_to_page_integer::<u32, i32>(
+                array, options, type_, nested, encoding,
+            )
         },
         UInt64 => {
             let array = array.as_any().downcast_ref().unwrap();
-            primitive::nested_array_to_page::<u64, i64>(array, options, type_, nested)
+            primitive::nested_array_to_page_integer::<u64, i64>(
+                array, options, type_, nested, encoding,
+            )
         },
         Int8 => {
             let array = array.as_any().downcast_ref().unwrap();
-            primitive::nested_array_to_page::<i8, i32>(array, options, type_, nested)
+            primitive::nested_array_to_page_integer::<i8, i32>(
+                array, options, type_, nested, encoding,
+            )
         },
         Int16 => {
             let array = array.as_any().downcast_ref().unwrap();
-            primitive::nested_array_to_page::<i16, i32>(array, options, type_, nested)
+            primitive::nested_array_to_page_integer::<i16, i32>(
+                array, options, type_, nested, encoding,
+            )
         },
         Int32 | Date32 | Time32(_) => {
             let array = array.as_any().downcast_ref().unwrap();
-            primitive::nested_array_to_page::<i32, i32>(array, options, type_, nested)
+            primitive::nested_array_to_page_integer::<i32, i32>(
+                array, options, type_, nested, encoding,
+            )
         },
         Int64 | Date64 | Time64(_) | Timestamp(_, _) | Duration(_) => {
             let array = array.as_any().downcast_ref().unwrap();
-            primitive::nested_array_to_page::<i64, i64>(array, options, type_, nested)
+            primitive::nested_array_to_page_integer::<i64, i64>(
+                array, options, type_, nested, encoding,
+            )
         },
         Float32 => {
             let array = array.as_any().downcast_ref().unwrap();
-            primitive::nested_array_to_page::<f32, f32>(array, options, type_, nested)
+            primitive::nested_array_to_page_float::<f32, f32>(
+                array, options, type_, nested, encoding,
+            )
         },
         Float64 => {
             let array = array.as_any().downcast_ref().unwrap();
-            primitive::nested_array_to_page::<f64, f64>(array, options, type_, nested)
+            primitive::nested_array_to_page_float::<f64, f64>(
+                array, options, type_, nested, encoding,
+            )
+        },
+        Float16 => {
+            let array = float16_to_fixed_size_binary(array.as_any().downcast_ref().unwrap());
+            fixed_len_bytes::check_type(&array, &type_)?;
+            fixed_len_bytes::nested_array_to_page(&array, options, type_, nested, None)
+        },
+        FixedSizeBinary(_) => {
+            let array = array.as_any().downcast_ref().unwrap();
+            fixed_len_bytes::check_type(array, &type_)?;
+            let statistics = if options.write_statistics {
+                Some(fixed_len_bytes::build_statistics(array, type_.clone()))
+            } else {
+                None
+            };
+
+            fixed_len_bytes::nested_array_to_page(array, options, type_, nested, statistics)
+        },
+        Interval(unit) => {
+            let array = interval_to_fixed_size_binary(array, *unit)?;
+            let statistics = if options.write_statistics {
+                Some(fixed_len_bytes::build_statistics(&array, type_.clone()))
+            } else {
+                None
+            };
+
+            fixed_len_bytes::nested_array_to_page(&array, options, type_, nested, statistics)
         },
         Decimal(precision, _) => {
             let precision = *precision;
@@ -768,7 +1240,8 @@ fn array_to_page_nested(
                     values.into(),
                     array.validity().cloned(),
                 );
-                fixed_len_bytes::array_to_page(&array, options, type_, statistics)
+                fixed_len_bytes::check_type(&array, &type_)?;
+                fixed_len_bytes::nested_array_to_page(&array, options, type_, nested, statistics)
             }
         },
         Decimal256(precision, _) => {
@@ -828,7 +1301,8 @@ fn array_to_page_nested(
                     values.into(),
                     array.validity().cloned(),
                 );
-                fixed_len_bytes::array_to_page(&array, options, type_, statistics)
+                fixed_len_bytes::check_type(&array, &type_)?;
+                fixed_len_bytes::nested_array_to_page(&array, options, type_, nested, statistics)
             } else {
                 let size = 32;
                 let array = array
@@ -853,7 +1327,8 @@ fn array_to_page_nested(
                     array.validity().cloned(),
                 );
 
-                fixed_len_bytes::array_to_page(&array, options, type_, statistics)
+                fixed_len_bytes::check_type(&array, &type_)?;
+                fixed_len_bytes::nested_array_to_page(&array, options, type_, nested, statistics)
             }
         },
         other => polars_bail!(nyi = "Writing nested parquet pages for data type {other:?}"),
@@ -919,3 +1394,369 @@ pub fn transverse<T, F: Fn(&ArrowDataType) -> T + Clone>(
     transverse_recursive(data_type, map, &mut encodings);
     encodings
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_options_builder() {
+        let options = WriteOptions::builder()
+            .version(Version::V2)
+            .data_pagesize_limit(Some(1024))
+            .strict(true)
+            .build()
+            .unwrap();
+        assert_eq!(options.version, Version::V2);
+        assert_eq!(options.data_pagesize_limit, Some(1024));
+        assert!(options.strict && options.write_statistics);
+
+        let error = WriteOptions::builder()
+            .compression(CompressionOptions::Lzo)
+            .build()
+            .unwrap_err();
+        assert!(error.to_string().contains("deprecated"), "{error}");
+
+        for limit in [0, i32::MAX as usize + 1] {
+            let error = WriteOptions::builder()
+                .data_pagesize_limit(Some(limit))
+                .build()
+                .unwrap_err();
+            assert!(error.to_string().contains("page size limit"), "{error}");
+        }
+    }
+
+    #[test]
+    fn decimal_physical_type() {
+        use crate::parquet::schema::types::PrimitiveLogicalType;
+
+        let values = [1i128, -2];
+        // the scale of a decimal can't exceed its precision
+        let cases = [
+            (1, 0, ParquetPhysicalType::Int32),
+            (9, 2, ParquetPhysicalType::Int32),
+            (10, 2, ParquetPhysicalType::Int64),
+            (18, 2, ParquetPhysicalType::Int64),
+            (19, 2, ParquetPhysicalType::FixedLenByteArray(9)),
+            (38, 2, ParquetPhysicalType::FixedLenByteArray(16)),
+        ];
+        for (precision, scale, physical_type) in cases {
+            let data_type = ArrowDataType::Decimal(precision, scale);
+            let type_ = to_parquet_type(&Field::new("d", data_type.clone(), false)).unwrap();
+            let type_ = to_parquet_leaves(type_).pop().unwrap();
+            assert_eq!(type_.physical_type, physical_type);
+            assert_eq!(
+                type_.logical_type,
+                Some(PrimitiveLogicalType::Decimal(precision, scale))
+            );
+
+            let array = PrimitiveArray::<i128>::new(data_type, values.to_vec().into(), None);
+            let nested = [Nested::Primitive(None, false, array.len())];
+            let options = WriteOptions::builder().build().unwrap();
+            let page = array_to_page(&array, type_, &nested, options, Encoding::Plain);
+            let Page::Data(page) = page.unwrap() else {
+                unreachable!()
+            };
+            let expected = values
+                .iter()
+                .flat_map(|x| match physical_type {
+                    ParquetPhysicalType::Int32 => (*x as i32).to_le_bytes().to_vec(),
+                    ParquetPhysicalType::Int64 => (*x as i64).to_le_bytes().to_vec(),
+                    ParquetPhysicalType::FixedLenByteArray(size) => {
+                        x.to_be_bytes()[16 - size..].to_vec()
+                    },
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(page.buffer(), expected);
+        }
+    }
+
+    #[test]
+    fn page_versions() {
+        use crate::parquet::page::DataPageHeader;
+
+        let array = Int32Array::from([Some(1), None, Some(3), None, Some(5)]);
+        let type_ = to_parquet_type(&Field::new("a", ArrowDataType::Int32, true)).unwrap();
+        let type_ = to_parquet_leaves(type_).pop().unwrap();
+        let nested = [Nested::Primitive(
+            array.validity().cloned(),
+            true,
+            array.len(),
+        )];
+        let values = [1i32, 3, 5]
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .collect::<Vec<_>>();
+        let page = |version, compression| {
+            let options = WriteOptions::builder()
+                .version(version)
+                .compression(compression)
+                .build()
+                .unwrap();
+            let page = array_to_page(&array, type_.clone(), &nested, options, Encoding::Plain);
+            let Page::Data(page) = page.unwrap() else {
+                unreachable!()
+            };
+            page
+        };
+
+        // v1 prefixes the levels with their length, which is in the header of v2
+        let v1 = page(Version::V1, CompressionOptions::Uncompressed);
+        let DataPageHeader::V1(header) = v1.header() else {
+            unreachable!()
+        };
+        assert_eq!(header.num_values, 5);
+        assert_eq!(
+            Encoding::try_from(header.definition_level_encoding).unwrap(),
+            Encoding::Rle
+        );
+        let def_length = i32::from_le_bytes(v1.buffer()[..4].try_into().unwrap()) as usize;
+        let def = &v1.buffer()[4..4 + def_length];
+        assert_eq!(&v1.buffer()[4 + def_length..], values);
+
+        let v2 = page(Version::V2, CompressionOptions::Uncompressed);
+        let DataPageHeader::V2(header) = v2.header() else {
+            unreachable!()
+        };
+        assert_eq!(
+            (header.num_values, header.num_rows, header.num_nulls),
+            (5, 5, 2)
+        );
+        assert_eq!(header.repetition_levels_byte_length, 0);
+        assert_eq!(header.definition_levels_byte_length, def_length as i32);
+        assert_eq!(header.is_compressed, Some(false));
+        assert_eq!(v2.buffer(), [def, values.as_slice()].concat());
+
+        // v2 levels are not compressed
+        #[cfg(feature = "zstd")]
+        {
+            use crate::parquet::page::CompressedPage;
+
+            let v2 = page(Version::V2, CompressionOptions::Zstd(None));
+            let DataPageHeader::V2(header) = v2.header() else {
+                unreachable!()
+            };
+            assert_eq!(header.is_compressed, Some(true));
+            let CompressedPage::Data(v2) =
+                compress(Page::Data(v2), vec![], CompressionOptions::Zstd(None)).unwrap()
+            else {
+                unreachable!()
+            };
+            assert_eq!(&v2.buffer[..def_length], def);
+        }
+    }
+
+    #[test]
+    fn compressed_pages() {
+        use crate::parquet::read::decompress;
+
+        let array = Int32Array::from_iter((0..4096).map(|i| (i % 3 != 0).then_some(i % 100)));
+        let type_ = to_parquet_type(&Field::new("a", ArrowDataType::Int32, true)).unwrap();
+        let type_ = to_parquet_leaves(type_).pop().unwrap();
+        let nested = [Nested::Primitive(
+            array.validity().cloned(),
+            true,
+            array.len(),
+        )];
+
+        let codecs = [CompressionOptions::Uncompressed]
+            .into_iter()
+            .chain(cfg!(feature = "snappy").then_some(CompressionOptions::Snappy))
+            .chain(
+                cfg!(feature = "gzip")
+                    .then(|| CompressionOptions::Gzip(Some(GzipLevel::try_new(9).unwrap()))),
+            )
+            .chain(cfg!(feature = "lz4").then_some(CompressionOptions::Lz4Raw))
+            .chain(
+                cfg!(feature = "zstd")
+                    .then(|| {
+                        [
+                            CompressionOptions::Zstd(None),
+                            CompressionOptions::Zstd(Some(ZstdLevel::try_new(19).unwrap())),
+                        ]
+                    })
+                    .into_iter()
+                    .flatten(),
+            )
+            .collect::<Vec<_>>();
+
+        for version in [Version::V1, Version::V2] {
+            for codec in codecs.iter().copied() {
+                let options = WriteOptions::builder()
+                    .version(version)
+                    .compression(codec)
+                    .build()
+                    .unwrap();
+                let page = array_to_page(&array, type_.clone(), &nested, options, Encoding::Plain)
+                    .unwrap();
+                let expected = match &page {
+                    Page::Data(page) => page.buffer().to_vec(),
+                    Page::Dict(_) => unreachable!(),
+                };
+
+                let compressed = compress(page, vec![], codec).unwrap();
+                assert_eq!(compressed.compression(), codec.into());
+                let CompressedPage::Data(data) = &compressed else {
+                    unreachable!()
+                };
+                assert_eq!(data.uncompressed_size(), expected.len());
+                if codec != CompressionOptions::Uncompressed {
+                    assert!(data.buffer.len() < expected.len(), "{codec:?}");
+                }
+
+                let Page::Data(page) = decompress(compressed, &mut vec![]).unwrap() else {
+                    unreachable!()
+                };
+                assert_eq!(page.buffer(), expected, "{codec:?} {version:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn null_array() {
+        use arrow::bitmap::Bitmap;
+
+        use crate::parquet::encoding::hybrid_rle::{decode_u32, encode_constant_u32};
+        use crate::parquet::page::DataPageHeader;
+
+        let len = 1000;
+        let array = NullArray::new(ArrowDataType::Null, len);
+        let type_ = to_parquet_type(&Field::new("n", ArrowDataType::Null, true)).unwrap();
+        let type_ = to_parquet_leaves(type_).pop().unwrap();
+        let mut nested = to_nested(&array, &ParquetType::PrimitiveType(type_.clone())).unwrap();
+        assert_eq!(
+            nested,
+            vec![vec![Nested::Primitive(
+                Some(Bitmap::new_zeroed(len)),
+                true,
+                len
+            )]]
+        );
+        let nested = nested.pop().unwrap();
+
+        // a single run of the null level: its header and a byte for the level
+        let mut def = vec![];
+        encode_constant_u32(&mut def, 0, len, 1).unwrap();
+        assert_eq!(def.len(), 3);
+        assert_eq!(
+            decode_u32(&mut def.as_slice(), 1, len).unwrap(),
+            vec![0; len]
+        );
+
+        for version in [Version::V1, Version::V2] {
+            let options = WriteOptions::builder().version(version).build().unwrap();
+            let page = array_to_page(&array, type_.clone(), &nested, options, Encoding::Plain);
+            let Page::Data(page) = page.unwrap() else {
+                unreachable!()
+            };
+            assert_eq!(page.num_values(), len);
+
+            // no values are written
+            let expected = match page.header() {
+                DataPageHeader::V1(_) => {
+                    [(def.len() as i32).to_le_bytes().as_slice(), def.as_slice()].concat()
+                },
+                DataPageHeader::V2(header) => {
+                    assert_eq!(header.num_nulls, len as i32);
+                    assert_eq!(header.definition_levels_byte_length, def.len() as i32);
+                    def.clone()
+                },
+            };
+            assert_eq!(page.buffer(), expected);
+        }
+
+        // [[None, None], [], None]
+        let list = ArrowDataType::List(Box::new(Field::new("item", ArrowDataType::Null, true)));
+        let array = ListArray::<i32>::new(
+            list.clone(),
+            vec![0, 2, 2, 2].try_into().unwrap(),
+            NullArray::new(ArrowDataType::Null, 2).boxed(),
+            Some(Bitmap::from([true, true, false])),
+        );
+        let type_ = to_parquet_type(&Field::new("l", list, true)).unwrap();
+        let nested = to_nested(&array, &type_).unwrap();
+        assert_eq!(
+            compute_levels(&nested[0]).unwrap(),
+            (vec![0, 1, 0, 0], vec![2, 2, 1, 0])
+        );
+    }
+
+    #[test]
+    fn chunk_statistics() {
+        use crate::parquet::statistics::PrimitiveStatistics;
+        use crate::parquet::write::statistics::reduce;
+
+        let array = Int32Array::from_iter((0..4096).map(|i| (i % 7 != 0).then_some(i - 3000)));
+        let type_ = to_parquet_type(&Field::new("a", ArrowDataType::Int32, true)).unwrap();
+        let type_ = to_parquet_leaves(type_).pop().unwrap();
+        let nested = [Nested::Primitive(
+            array.validity().cloned(),
+            true,
+            array.len(),
+        )];
+        let options = WriteOptions::builder()
+            .data_pagesize_limit(Some(1024))
+            .build()
+            .unwrap();
+
+        let pages = array_to_pages(&array, type_, &nested, options, Encoding::Plain)
+            .unwrap()
+            .collect::<PolarsResult<Vec<_>>>()
+            .unwrap();
+        assert!(pages.len() > 1);
+        let stats = pages
+            .iter()
+            .map(|page| match page {
+                Page::Data(page) => page.statistics().transpose().unwrap(),
+                Page::Dict(_) => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+
+        let chunk = reduce(&stats.iter().collect::<Vec<_>>()).unwrap().unwrap();
+        let chunk = chunk
+            .as_any()
+            .downcast_ref::<PrimitiveStatistics<i32>>()
+            .unwrap();
+        assert_eq!(chunk.min_value, Some(-2999));
+        assert_eq!(chunk.max_value, Some(1094));
+        assert_eq!(chunk.null_count, Some(array.null_count() as i64));
+    }
+
+    #[test]
+    fn float16() {
+        let values = [
+            Some(f16::from_f32(1.0)),
+            Some(f16::from_bits(0xfc00)), // -inf
+            None,
+            Some(f16::from_bits(0x7e00)), // NaN
+        ];
+        let array = PrimitiveArray::<f16>::from(values);
+        let field = Field::new("a", ArrowDataType::Float16, true);
+        let ParquetType::PrimitiveType(type_) = to_parquet_type(&field).unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(
+            type_.physical_type,
+            ParquetPhysicalType::FixedLenByteArray(2)
+        );
+        assert_eq!(type_.logical_type, None);
+
+        let nested = [Nested::Primitive(
+            array.validity().cloned(),
+            true,
+            array.len(),
+        )];
+        let options = WriteOptions::builder()
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let page = array_to_page(&array, type_, &nested, options, Encoding::Plain).unwrap();
+        let Page::Data(page) = page else {
+            unreachable!()
+        };
+        let (_, def, values) = crate::parquet::page::split_buffer(&page).unwrap();
+        assert_eq!(def, [3, 0b1011]);
+        assert_eq!(values, [0x00, 0x3c, 0x00, 0xfc, 0x00, 0x7e]);
+    }
+}
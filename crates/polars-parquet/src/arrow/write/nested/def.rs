@@ -4,7 +4,7 @@ use polars_utils::slice::GetSaferUnchecked;
 
 use super::super::pages::{ListNested, Nested};
 use super::rep::num_values;
-use super::to_length;
+use super::{levels_capacity, to_length};
 
 // (is_valid, length)
 trait DebugIter: Iterator<Item = (u32, usize)> + std::fmt::Debug {}
@@ -16,6 +16,10 @@ fn single_iter<'a>(
     is_optional: bool,
     length: usize,
 ) -> Box<dyn DebugIter + 'a> {
+    // an all-valid bitmap defines the same levels as no bitmap, without iterating its bits
+    let validity = validity
+        .as_ref()
+        .filter(|validity| validity.unset_bits() > 0);
     match (is_optional, validity) {
         (false, _) => {
             Box::new(std::iter::repeat((0u32, 1usize)).take(length)) as Box<dyn DebugIter>
@@ -30,7 +34,11 @@ fn single_iter<'a>(
 }
 
 fn single_list_iter<'a, O: Offset>(nested: &'a ListNested<O>) -> Box<dyn DebugIter + 'a> {
-    match (nested.is_optional, &nested.validity) {
+    let validity = nested
+        .validity
+        .as_ref()
+        .filter(|validity| validity.unset_bits() > 0);
+    match (nested.is_optional, validity) {
         (false, _) => Box::new(
             std::iter::repeat(0u32)
                 .zip(to_length(&nested.offsets))
@@ -58,6 +66,7 @@ fn single_fixed_list_iter<'a>(
     len: usize,
 ) -> Box<dyn DebugIter + 'a> {
     let lengths = std::iter::repeat(width).take(len);
+    let validity = validity.filter(|validity| validity.unset_bits() > 0);
     match (is_optional, validity) {
         (false, _) => Box::new(
             std::iter::repeat(0u32)
@@ -86,7 +95,7 @@ fn iter<'a>(nested: &'a [Nested]) -> Vec<Box<dyn DebugIter + 'a>> {
             Nested::Primitive(validity, is_optional, length) => {
                 single_iter(validity, *is_optional, *length)
             },
-            Nested::List(nested) => single_list_iter(nested),
+            Nested::List(nested) | Nested::Map(nested, _) => single_list_iter(nested),
             Nested::LargeList(nested) => single_list_iter(nested),
             Nested::Struct(validity, is_optional, length) => {
                 single_iter(validity, *is_optional, *length)
@@ -102,6 +111,11 @@ fn iter<'a>(nested: &'a [Nested]) -> Vec<Box<dyn DebugIter + 'a>> {
         .collect()
 }
 
+/// Returns the definition levels of `nested`.
+pub fn def_levels(nested: &[Nested]) -> Vec<u32> {
+    DefLevelsIter::new(nested).collect()
+}
+
 /// Iterator adapter of parquet / dremel definition levels
 #[derive(Debug)]
 pub struct DefLevelsIter<'a> {
@@ -126,7 +140,13 @@ pub struct DefLevelsIter<'a> {
 
 impl<'a> DefLevelsIter<'a> {
     pub fn new(nested: &'a [Nested]) -> Self {
-        let remaining_values = num_values(nested);
+        Self::with_value_count(nested, num_values(nested))
+    }
+
+    /// Returns the iterator of the `value_count` levels of `nested`, as returned by
+    /// [`num_values`].
+    pub(super) fn with_value_count(nested: &'a [Nested], value_count: usize) -> Self {
+        let remaining_values = value_count;
 
         let iter = iter(nested);
         let remaining = vec![0; iter.len()];
@@ -204,7 +224,7 @@ impl<'a> Iterator for DefLevelsIter<'a> {
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         let length = self.remaining_values;
-        (length, Some(length))
+        (levels_capacity(length), Some(length))
     }
 }
 
@@ -218,6 +238,7 @@ mod tests {
         let result = iter.by_ref().collect::<Vec<_>>();
         assert_eq!(result, expected);
         assert_eq!(iter.size_hint().0, 0);
+        assert_eq!(super::super::compute_levels(&nested).unwrap().1, expected);
     }
 
     #[test]
@@ -311,6 +332,8 @@ mod tests {
             Nested::Primitive(Some(v1.into()), true, 12),
         ];
         let expected = vec![3u32, 3, 0, 3, 2, 3, 3, 3, 3, 1, 3, 3, 3, 0, 3];
+        // the non-null primitives
+        assert_eq!(super::super::written_value_count(&nested), 11);
 
         test(nested, expected)
     }
@@ -621,4 +644,171 @@ mod tests {
 
         test(nested, expected)
     }
+
+    #[test]
+    fn nested_list_struct_list_nullable_max_level() {
+        let nested = vec![
+            Nested::List(ListNested {
+                is_optional: true,
+                offsets: vec![0, 2].try_into().unwrap(),
+                validity: Some([true].into()),
+            }),
+            Nested::Struct(Some([true, false].into()), true, 2),
+            Nested::List(ListNested {
+                is_optional: true,
+                offsets: vec![0, 1, 1].try_into().unwrap(),
+                validity: Some([true, false].into()),
+            }),
+            Nested::Primitive(Some([true].into()), true, 1),
+        ];
+
+        assert_eq!(super::super::max_level(&nested), (6, 2));
+        assert_eq!(super::super::bit_width(6), 3);
+        assert_eq!(super::super::bit_width(2), 2);
+    }
+
+    #[test]
+    fn nested_list_struct_list_nullable_serialized() {
+        /*
+        [
+            [{"a": ["b"]}, None],
+        ]
+        */
+        let nested = vec![
+            Nested::List(ListNested {
+                is_optional: true,
+                offsets: vec![0, 2].try_into().unwrap(),
+                validity: Some([true].into()),
+            }),
+            Nested::Struct(Some([true, false].into()), true, 2),
+            Nested::List(ListNested {
+                is_optional: true,
+                offsets: vec![0, 1, 1].try_into().unwrap(),
+                validity: Some([true, false].into()),
+            }),
+            Nested::Primitive(Some([true].into()), true, 1),
+        ];
+        let expected = vec![6, 2];
+
+        let serialized = super::super::serialize_nested(&nested);
+        let deserialized = super::super::deserialize_nested(&serialized).unwrap();
+        assert_eq!(deserialized, nested);
+        assert!(super::super::deserialize_nested(&serialized[..serialized.len() - 1]).is_err());
+
+        test(deserialized, expected)
+    }
+
+    #[test]
+    fn struct_optional_list_required() {
+        /*
+        [
+            {"l": [1, 2]},
+            None,
+            {"l": [3, 4, 5]},
+            {"l": []},
+        ]
+        */
+        let a = [true, false, true, true];
+        let nested = vec![
+            Nested::Struct(Some(a.into()), true, 4),
+            Nested::List(ListNested {
+                is_optional: false,
+                offsets: vec![0, 2, 2, 5, 5].try_into().unwrap(),
+                validity: None,
+            }),
+            Nested::Primitive(None, false, 5),
+        ];
+        let expected = vec![2, 2, 0, 2, 2, 2, 1];
+
+        test(nested, expected)
+    }
+
+    #[test]
+    fn list_of_empty_inner_lists() {
+        /*
+        [
+            [[], []],
+            [[], [1]],
+            [],
+            [None, []],
+        ]
+        */
+        let nested = vec![
+            Nested::List(ListNested {
+                is_optional: true,
+                offsets: vec![0, 2, 4, 4, 6].try_into().unwrap(),
+                validity: None,
+            }),
+            Nested::List(ListNested {
+                is_optional: true,
+                offsets: vec![0, 0, 0, 0, 1, 1, 1].try_into().unwrap(),
+                validity: Some([true, true, true, true, false, true].into()),
+            }),
+            Nested::Primitive(None, true, 1),
+        ];
+        // every empty inner list emits exactly one level, 3
+        let expected = vec![3, 3, 3, 5, 1, 2, 3];
+
+        test(nested, expected)
+    }
+
+    #[test]
+    fn all_valid_bitmaps() {
+        // [[{"a": 1}, {"a": 2}], None, [], [{"a": 3}]], with bitmaps without nulls below the
+        // list
+        let nested = |all_valid: Option<Bitmap>| {
+            vec![
+                Nested::List(ListNested {
+                    is_optional: true,
+                    offsets: vec![0, 2, 2, 2, 3].try_into().unwrap(),
+                    validity: Some([true, false, true, true].into()),
+                }),
+                Nested::Struct(all_valid.clone(), true, 3),
+                Nested::Primitive(all_valid, true, 3),
+            ]
+        };
+        let expected = vec![4, 4, 0, 1, 4];
+
+        test(nested(None), expected.clone());
+        test(nested(Some(Bitmap::new_with_value(true, 3))), expected);
+    }
+
+    #[test]
+    fn sliced_lists() {
+        use arrow::array::{Int32Array, ListArray};
+        use arrow::datatypes::{ArrowDataType, Field};
+        use arrow::offset::Offset;
+
+        use crate::arrow::write::{slice_parquet_array, to_nested, to_parquet_type};
+
+        // [[1, None], None, [], [2, None], None, [4]][2..5] = [[], [2, None], None]
+        fn sliced<O: Offset>() -> Vec<Nested> {
+            let item = Box::new(Field::new("item", ArrowDataType::Int32, true));
+            let data_type = if O::IS_LARGE {
+                ArrowDataType::LargeList(item)
+            } else {
+                ArrowDataType::List(item)
+            };
+            let offsets = [0, 2, 2, 2, 4, 4, 5].map(O::from_as_usize).to_vec();
+            let array = ListArray::<O>::new(
+                data_type.clone(),
+                offsets.try_into().unwrap(),
+                Int32Array::from([Some(1), None, Some(2), None, Some(4)]).boxed(),
+                Some([true, false, true, true, false, true].into()),
+            )
+            .sliced(2, 3);
+            let type_ = to_parquet_type(&Field::new("l", data_type, true)).unwrap();
+            let mut nested = to_nested(&array, &type_).unwrap().pop().unwrap();
+            // the leaf of `to_nested` is unsliced: slice it to the values of the sliced list, as
+            // `array_to_pages` does before computing the levels.
+            let mut values = array.values().clone();
+            slice_parquet_array(values.as_mut(), &mut nested, 0, array.len());
+            nested
+        }
+
+        // the offsets of both are relative to the first offset of the slice, 2
+        let expected = vec![1, 3, 2, 0];
+        test(sliced::<i32>(), expected.clone());
+        test(sliced::<i64>(), expected);
+    }
 }
@@ -1,26 +1,44 @@
 mod def;
 mod rep;
+mod serialize;
 
-use arrow::offset::Offset;
-use polars_error::PolarsResult;
-pub use rep::num_values;
+use std::ops::Range;
 
-use super::Nested;
+use arrow::array::Array;
+use arrow::bitmap::Bitmap;
+use arrow::compute::utils::combine_validities_and;
+use arrow::datatypes::ArrowDataType;
+use arrow::offset::{Offset, OffsetsBuffer};
+pub use def::def_levels;
+use polars_error::{polars_bail, polars_ensure, PolarsResult};
+pub use rep::{num_values, rep_levels};
+pub use serialize::{deserialize_nested, serialize_nested};
+
+use super::{to_nested, Nested};
+use crate::arrow::read::schema::is_nullable;
 use crate::parquet::encoding::hybrid_rle::encode;
-use crate::parquet::read::levels::get_bit_width;
+use crate::parquet::schema::types::{ParquetType, PrimitiveType};
 use crate::parquet::write::Version;
 
-fn write_levels_v1<F: FnOnce(&mut Vec<u8>) -> PolarsResult<()>>(
+/// The maximum number of levels reserved up-front for a column.
+///
+/// The number of values of a column is derived from the lengths in its [`Nested`], which may be
+/// inconsistent with its offsets. Beyond this hint the levels grow as they are computed.
+const MAX_LEVELS_CAPACITY: usize = 1 << 20;
+
+/// Returns the capacity to reserve for `value_count` levels.
+fn levels_capacity(value_count: usize) -> usize {
+    value_count.min(MAX_LEVELS_CAPACITY)
+}
+
+fn write_levels_v1<F: FnOnce(&mut Vec<u8>) -> PolarsResult<usize>>(
     buffer: &mut Vec<u8>,
     encode: F,
 ) -> PolarsResult<()> {
     buffer.extend_from_slice(&[0; 4]);
     let start = buffer.len();
 
-    encode(buffer)?;
-
-    let end = buffer.len();
-    let length = end - start;
+    let length = encode(buffer)?;
 
     // write the first 4 bytes as length
     let length = (length as i32).to_le_bytes();
@@ -28,48 +46,219 @@ fn write_levels_v1<F: FnOnce(&mut Vec<u8>) -> PolarsResult<()>>(
     Ok(())
 }
 
-/// writes the rep levels to a `Vec<u8>`.
-fn write_rep_levels(buffer: &mut Vec<u8>, nested: &[Nested], version: Version) -> PolarsResult<()> {
-    let max_level = max_rep_level(nested) as i16;
+/// writes the levels to a `Vec<u8>` and returns how many there were.
+fn write_levels<I: Iterator<Item = u32>>(
+    buffer: &mut Vec<u8>,
+    levels: I,
+    max_level: usize,
+    version: Version,
+) -> PolarsResult<usize> {
     if max_level == 0 {
-        return Ok(());
+        return Ok(levels.count());
     }
-    let num_bits = get_bit_width(max_level);
+    let num_bits = bit_width(max_level as u32);
 
-    let levels = rep::RepLevelsIter::new(nested);
+    let mut count = 0;
+    let levels = levels.inspect(|_| count += 1);
 
     match version {
-        Version::V1 => {
-            write_levels_v1(buffer, |buffer: &mut Vec<u8>| {
-                encode::<u32, _, _>(buffer, levels, num_bits)?;
-                Ok(())
-            })?;
-        },
+        Version::V1 => write_levels_v1(buffer, move |buffer: &mut Vec<u8>| {
+            Ok(encode::<u32, _, _>(buffer, levels, num_bits)?)
+        })?,
         Version::V2 => {
             encode::<u32, _, _>(buffer, levels, num_bits)?;
         },
     }
+    Ok(count)
+}
+
+/// The number of levels and the maximum levels of a nested column.
+///
+/// Each of these walks `nested`, so writing a page computes them once and passes them to both
+/// the levels and the values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct NestedInfo {
+    /// The number of levels, as returned by [`num_values`].
+    pub(crate) value_count: usize,
+    /// The maximum definition level.
+    pub(crate) max_def: u32,
+    /// The maximum repetition level.
+    pub(crate) max_rep: u32,
+}
+
+impl NestedInfo {
+    pub(crate) fn new(nested: &[Nested]) -> Self {
+        let (max_def, max_rep) = max_level(nested);
+        Self {
+            value_count: num_values(nested),
+            max_def,
+            max_rep,
+        }
+    }
+}
+
+/// Returns the `(repetition, definition)` levels of `nested`.
+///
+/// The number of levels is computed once and shared by both, and an error is returned if they
+/// disagree on it, which means that `nested` is inconsistent.
+pub fn compute_levels(nested: &[Nested]) -> PolarsResult<(Vec<u32>, Vec<u32>)> {
+    compute_levels_with_info(nested, &NestedInfo::new(nested))
+}
+
+/// [`compute_levels`] of `nested`, whose [`NestedInfo`] is `info`.
+fn compute_levels_with_info(
+    nested: &[Nested],
+    info: &NestedInfo,
+) -> PolarsResult<(Vec<u32>, Vec<u32>)> {
+    let rep_levels = rep::rep_levels_with_value_count(nested, info.value_count);
+    if is_fully_defined(nested) {
+        // every value is at the maximum definition level
+        let def_levels = vec![info.max_def; rep_levels.len()];
+        return Ok((rep_levels, def_levels));
+    }
+
+    let mut def_levels = Vec::with_capacity(rep_levels.len());
+    def_levels.extend(def::DefLevelsIter::with_value_count(
+        nested,
+        info.value_count,
+    ));
+    ensure_levels_match(rep_levels.len(), def_levels.len())?;
+    Ok((rep_levels, def_levels))
+}
 
+fn ensure_levels_match(num_rep_levels: usize, num_def_levels: usize) -> PolarsResult<()> {
+    polars_ensure!(
+        num_rep_levels == num_def_levels,
+        ComputeError: "repetition and definition levels of a nested column have different lengths"
+    );
     Ok(())
 }
 
-/// writes the rep levels to a `Vec<u8>`.
-fn write_def_levels(buffer: &mut Vec<u8>, nested: &[Nested], version: Version) -> PolarsResult<()> {
-    let max_level = max_def_level(nested) as i16;
-    if max_level == 0 {
-        return Ok(());
+/// Returns an iterator of the definition levels of [`compute_levels_with_info`].
+fn def_levels_iter<'a>(
+    nested: &'a [Nested],
+    info: &NestedInfo,
+) -> Box<dyn Iterator<Item = u32> + 'a> {
+    if is_fully_defined(nested) {
+        // every value is at the maximum definition level
+        Box::new(std::iter::repeat(info.max_def).take(info.value_count))
+    } else {
+        Box::new(def::DefLevelsIter::with_value_count(
+            nested,
+            info.value_count,
+        ))
     }
-    let num_bits = get_bit_width(max_level);
+}
+
+/// The levels of a nested column together with what is needed to write its values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WritePlan {
+    /// The definition levels.
+    pub def: Vec<u32>,
+    /// The repetition levels.
+    pub rep: Vec<u32>,
+    /// Whether each value of the leaf array is written, i.e. it is at the maximum definition
+    /// level. The leaf values below a null or empty list are not part of the mask.
+    pub value_mask: Vec<bool>,
+    /// The number of definition levels below the maximum.
+    pub null_count: usize,
+    /// The maximum definition level.
+    pub max_def: u32,
+    /// The maximum repetition level.
+    pub max_rep: u32,
+}
 
-    let levels = def::DefLevelsIter::new(nested);
+/// Returns the [`WritePlan`] of `nested`, computed in a single pass over its levels.
+pub fn compute_write_plan(nested: &[Nested]) -> PolarsResult<WritePlan> {
+    let NestedInfo {
+        value_count,
+        max_def,
+        max_rep,
+    } = NestedInfo::new(nested);
+    // a definition level of at least `min_leaf_def` reaches a value of the leaf array
+    let min_leaf_def = nested
+        .iter()
+        .rposition(|nested| !matches!(nested, Nested::Primitive(..) | Nested::Struct(..)))
+        .map_or(0, |i| max_def_level(&nested[..=i])) as u32;
 
-    match version {
-        Version::V1 => write_levels_v1(buffer, move |buffer: &mut Vec<u8>| {
-            encode::<u32, _, _>(buffer, levels, num_bits)?;
-            Ok(())
-        }),
-        Version::V2 => Ok(encode::<u32, _, _>(buffer, levels, num_bits)?),
+    let capacity = levels_capacity(value_count);
+    let mut plan = WritePlan {
+        def: Vec::with_capacity(capacity),
+        rep: Vec::with_capacity(capacity),
+        value_mask: Vec::with_capacity(levels_capacity(nested.last().map_or(0, |n| n.len()))),
+        null_count: 0,
+        max_def,
+        max_rep,
+    };
+
+    let mut rep = rep::RepLevelsIter::with_value_count(nested, value_count);
+    let mut def = def::DefLevelsIter::with_value_count(nested, value_count);
+    loop {
+        match (rep.next(), def.next()) {
+            (Some(rep), Some(def)) => {
+                plan.rep.push(rep);
+                plan.def.push(def);
+                plan.null_count += (def < max_def) as usize;
+                if def >= min_leaf_def {
+                    plan.value_mask.push(def == max_def);
+                }
+            },
+            (None, None) => break,
+            _ => polars_bail!(ComputeError:
+                "repetition and definition levels of a nested column have different lengths"
+            ),
+        }
     }
+    Ok(plan)
+}
+
+/// Returns the `(repetition, definition)` levels of `nested` with the offsets of the list at
+/// `list_level` replaced by `offsets`.
+///
+/// This computes the levels of the same values grouped into different lists, e.g. when the
+/// list structure is known separately from the values.
+/// # Errors
+/// Errors if `nested[list_level]` is not a [`Nested::List`] or [`Nested::Map`], or if
+/// `offsets` does not have the same number of lists and span the same values as the
+/// offsets it replaces.
+pub fn to_levels_with_offsets(
+    nested: &[Nested],
+    list_level: usize,
+    offsets: OffsetsBuffer<i32>,
+) -> PolarsResult<(Vec<u32>, Vec<u32>)> {
+    let mut nested = nested.to_vec();
+    let list = match nested.get_mut(list_level) {
+        Some(Nested::List(list) | Nested::Map(list, _)) => list,
+        other => polars_bail!(InvalidOperation:
+            "Expected a list at nesting level {}, found {:?}", list_level, other
+        ),
+    };
+    polars_ensure!(
+        offsets.len_proxy() == list.offsets.len_proxy()
+            && offsets.first() == list.offsets.first()
+            && offsets.last() == list.offsets.last(),
+        InvalidOperation:
+        "The offsets replacing nesting level {} must have {} lists spanning values {}..{}",
+        list_level, list.offsets.len_proxy(), list.offsets.first(), list.offsets.last()
+    );
+    list.offsets = offsets;
+
+    compute_levels(&nested)
+}
+
+/// Returns whether every definition level of `nested` is the maximum one, i.e. `nested` has
+/// neither nulls nor empty lists at any nesting level.
+pub fn is_fully_defined(nested: &[Nested]) -> bool {
+    nested.iter().all(|nested| {
+        let has_empty = match nested {
+            Nested::List(nested) | Nested::Map(nested, _) => rep::num_empty(&nested.offsets) > 0,
+            Nested::LargeList(nested) => rep::num_empty(&nested.offsets) > 0,
+            Nested::FixedSizeList { width, len, .. } => *width == 0 && *len > 0,
+            Nested::Primitive(_, _, _) | Nested::Struct(_, _, _) => false,
+        };
+        let (_, validity) = validity_of(nested);
+        !has_empty && validity.map_or(true, |v| v.unset_bits() == 0)
+    })
 }
 
 fn max_def_level(nested: &[Nested]) -> usize {
@@ -77,24 +266,197 @@ fn max_def_level(nested: &[Nested]) -> usize {
         .iter()
         .map(|nested| match nested {
             Nested::Primitive(_, is_optional, _) => *is_optional as usize,
-            Nested::List(nested) => 1 + (nested.is_optional as usize),
+            Nested::List(nested) | Nested::Map(nested, _) => 1 + (nested.is_optional as usize),
             Nested::LargeList(nested) => 1 + (nested.is_optional as usize),
             Nested::Struct(_, is_optional, _) => *is_optional as usize,
-            Nested::FixedSizeList { is_optional, .. } => *is_optional as usize,
+            Nested::FixedSizeList { is_optional, .. } => 1 + (*is_optional as usize),
         })
         .sum()
 }
 
+/// Returns the maximum `(definition, repetition)` levels of a leaf with nesting `nested`.
+pub fn max_level(nested: &[Nested]) -> (u32, u32) {
+    (max_def_level(nested) as u32, max_rep_level(nested) as u32)
+}
+
+/// Returns the number of bits needed to encode levels of at most `max`, `ceil(log2(max + 1))`.
+pub fn bit_width(max: u32) -> u32 {
+    32 - max.leading_zeros()
+}
+
 fn max_rep_level(nested: &[Nested]) -> usize {
     nested
         .iter()
         .map(|nested| match nested {
-            Nested::FixedSizeList { .. } | Nested::LargeList(_) | Nested::List(_) => 1,
+            Nested::FixedSizeList { .. }
+            | Nested::LargeList(_)
+            | Nested::List(_)
+            | Nested::Map(_, _) => 1,
             Nested::Primitive(_, _, _) | Nested::Struct(_, _, _) => 0,
         })
         .sum()
 }
 
+/// Returns whether the nulls of `array`, a leaf written as `type_`, are skipped when writing
+/// its values.
+///
+/// Besides optional leaves, this holds for required leaves under a null parent: their
+/// definition level is below the maximum, and their validity carries the parent's nulls.
+pub(super) fn is_optional_leaf(type_: &PrimitiveType, array: &dyn Array) -> bool {
+    is_nullable(&type_.field_info) || array.null_count() > 0
+}
+
+/// Returns `leaf` with the nulls of its struct ancestors, or `None` if there are none to add.
+///
+/// Only the validity of nullable children includes the nulls of their parent, so a required
+/// `leaf` under a null struct still has values in these slots. They are not written, as their
+/// definition level is below the maximum.
+pub(super) fn with_parent_nulls(
+    leaf: &dyn Array,
+    type_: &PrimitiveType,
+    nested: &[Nested],
+) -> Option<Box<dyn Array>> {
+    if is_nullable(&type_.field_info) || matches!(leaf.data_type(), ArrowDataType::Null) {
+        return None;
+    }
+    let parent_validity = nested
+        .iter()
+        .rev()
+        .skip(1)
+        .map_while(|nested| match nested {
+            Nested::Struct(validity, _, _) => Some(validity.as_ref()),
+            _ => None,
+        })
+        .fold(None, |acc: Option<Bitmap>, validity| {
+            combine_validities_and(acc.as_ref(), validity)
+        })
+        .filter(|validity| validity.unset_bits() > 0)?;
+    let validity = combine_validities_and(Some(&parent_validity), leaf.validity());
+    Some(leaf.with_validity(validity))
+}
+
+/// Returns whether `nested` is optional, and its validity.
+pub(super) fn validity_of(nested: &Nested) -> (bool, Option<&Bitmap>) {
+    match nested {
+        Nested::Primitive(validity, is_optional, _) | Nested::Struct(validity, is_optional, _) => {
+            (*is_optional, validity.as_ref())
+        },
+        Nested::List(nested) | Nested::Map(nested, _) => {
+            (nested.is_optional, nested.validity.as_ref())
+        },
+        Nested::LargeList(nested) => (nested.is_optional, nested.validity.as_ref()),
+        Nested::FixedSizeList {
+            validity,
+            is_optional,
+            ..
+        } => (*is_optional, validity.as_ref()),
+    }
+}
+
+/// Errors if a required level of `nested` has a null that is not inherited from a null parent.
+///
+/// Nulls of a struct and of a fixed-size list are carried by the validity of their children, so
+/// those are allowed. Children of a null list have no elements, so they never inherit nulls.
+pub(super) fn validate_required(nested: &[Nested]) -> PolarsResult<()> {
+    for (i, level) in nested.iter().enumerate() {
+        let (is_optional, validity) = validity_of(level);
+        let Some(validity) = validity.filter(|v| !is_optional && v.unset_bits() > 0) else {
+            continue;
+        };
+
+        let (width, parent) = match i.checked_sub(1).map(|i| &nested[i]) {
+            Some(Nested::Struct(validity, _, _)) => (1, validity.as_ref()),
+            Some(Nested::FixedSizeList {
+                validity, width, ..
+            }) => (*width, validity.as_ref()),
+            _ => (1, None),
+        };
+
+        let inherited = |j: usize| parent.is_some_and(|p| !p.get_bit(j / width));
+        if let Some(j) = (0..validity.len()).find(|&j| !validity.get_bit(j) && !inherited(j)) {
+            polars_bail!(InvalidOperation:
+                "a required field has a null at position {} that is not inherited from a null parent",
+                j
+            )
+        }
+    }
+    Ok(())
+}
+
+/// Errors if the leaf paths of `nested`, as returned by [`to_nested`], disagree on the number
+/// of rows or if any of their levels does not have as many rows as its parent has children.
+///
+/// Sibling leaves share their ancestors, so this ensures that their level streams describe
+/// the same records.
+pub fn validate_nested(nested: &[Vec<Nested>]) -> PolarsResult<()> {
+    if let Some(first) = nested.first().and_then(|path| path.first()) {
+        for (leaf, path) in nested.iter().enumerate() {
+            polars_ensure!(
+                path.first().map_or(0, |n| n.len()) == first.len(),
+                ComputeError: "leaf {} of a nested column has {} rows, expected {}",
+                leaf, path.first().map_or(0, |n| n.len()), first.len()
+            );
+        }
+    }
+
+    for (leaf, path) in nested.iter().enumerate() {
+        for (depth, window) in path.windows(2).enumerate() {
+            let (parent, child) = (&window[0], &window[1]);
+            let expected = match parent {
+                Nested::Primitive(..) => polars_bail!(ComputeError:
+                    "leaf {} of a nested column has a primitive at depth {}", leaf, depth
+                ),
+                Nested::Struct(_, _, len) => *len,
+                Nested::List(nested) | Nested::Map(nested, _) => nested.offsets.range() as usize,
+                Nested::LargeList(nested) => nested.offsets.range() as usize,
+                Nested::FixedSizeList { width, len, .. } => width * len,
+            };
+            polars_ensure!(
+                child.len() == expected,
+                ComputeError: "leaf {} of a nested column has {} rows at depth {}, expected {}",
+                leaf, child.len(), depth + 1, expected
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Errors if `def` and `rep` are not consistent levels of `nested`.
+///
+/// Both must have the same length, be at most the maximum levels of `nested` and the
+/// repetition levels must start a record at their first level and at every one of the rows of
+/// `nested`. This does not check that they are the levels of the values of `nested`, which is
+/// what [`compute_levels`] returns.
+pub fn validate_levels(nested: &[Nested], def: &[u32], rep: &[u32]) -> PolarsResult<()> {
+    let (max_def, max_rep) = max_level(nested);
+    polars_ensure!(
+        def.len() == rep.len(),
+        ComputeError: "a nested column has {} definition levels but {} repetition levels",
+        def.len(), rep.len()
+    );
+    if let Some(i) = def.iter().position(|def| *def > max_def) {
+        polars_bail!(ComputeError:
+            "definition level {} at position {} exceeds the maximum of {}", def[i], i, max_def
+        )
+    }
+    if let Some(i) = rep.iter().position(|rep| *rep > max_rep) {
+        polars_bail!(ComputeError:
+            "repetition level {} at position {} exceeds the maximum of {}", rep[i], i, max_rep
+        )
+    }
+    polars_ensure!(
+        rep.first().map_or(true, |rep| *rep == 0),
+        ComputeError: "the first repetition level must be 0, found {}", rep[0]
+    );
+    let rows = rep.iter().filter(|rep| **rep == 0).count();
+    let expected = nested.first().map_or(0, |n| n.len());
+    polars_ensure!(
+        rows == expected,
+        ComputeError: "the levels of a nested column have {} rows, expected {}", rows, expected
+    );
+    Ok(())
+}
+
 fn to_length<O: Offset>(
     offsets: &[O],
 ) -> impl Iterator<Item = usize> + std::fmt::Debug + Clone + '_ {
@@ -103,17 +465,1841 @@ fn to_length<O: Offset>(
         .map(|w| w[1].to_usize() - w[0].to_usize())
 }
 
-/// Write `repetition_levels` and `definition_levels` to buffer.
+/// Write `repetition_levels` and `definition_levels` to buffer and returns their byte lengths.
 pub fn write_rep_and_def(
     page_version: Version,
     nested: &[Nested],
     buffer: &mut Vec<u8>,
 ) -> PolarsResult<(usize, usize)> {
-    write_rep_levels(buffer, nested, page_version)?;
-    let repetition_levels_byte_length = buffer.len();
+    write_rep_and_def_with_info(page_version, nested, &NestedInfo::new(nested), buffer)
+}
+
+/// [`write_rep_and_def`] of `nested`, whose [`NestedInfo`] is `info`.
+pub(crate) fn write_rep_and_def_with_info(
+    page_version: Version,
+    nested: &[Nested],
+    info: &NestedInfo,
+    buffer: &mut Vec<u8>,
+) -> PolarsResult<(usize, usize)> {
+    // the levels are encoded as they are computed, without collecting them
+    let rep_levels = rep::rep_levels_iter(nested, info.value_count);
+    let start = buffer.len();
+    let num_rep_levels = write_levels(buffer, rep_levels, info.max_rep as usize, page_version)?;
+    let repetition_levels_byte_length = buffer.len() - start;
+
+    let def_levels = def_levels_iter(nested, info);
+    let start = buffer.len();
+    let num_def_levels = write_levels(buffer, def_levels, info.max_def as usize, page_version)?;
+    let definition_levels_byte_length = buffer.len() - start;
 
-    write_def_levels(buffer, nested, page_version)?;
-    let definition_levels_byte_length = buffer.len() - repetition_levels_byte_length;
+    ensure_levels_match(num_rep_levels, num_def_levels)?;
 
     Ok((repetition_levels_byte_length, definition_levels_byte_length))
 }
+
+/// Returns the number of leaf values of `nested` that are written to the values section,
+/// i.e. the number of definition levels equal to the maximum definition level.
+///
+/// Unlike [`num_values`], this excludes nulls and empty lists at every nesting level.
+pub fn written_value_count(nested: &[Nested]) -> usize {
+    let max_def_level = max_def_level(nested) as u32;
+    def::DefLevelsIter::new(nested)
+        .filter(|def| *def == max_def_level)
+        .count()
+}
+
+/// Returns the repetition level that marks the start of a new record in the levels of
+/// `nested`.
+///
+/// Records always start at repetition level 0; a level `r > 0` continues the current
+/// record, starting a new entry of the list at depth `r`.
+pub fn leaf_record_boundary_rep_level(_nested: &[Nested]) -> u32 {
+    0
+}
+
+/// Returns the number of repeated ancestors of the leaf of `nested`, which is also its
+/// maximum repetition level.
+pub fn leaf_repetition_depth(nested: &[Nested]) -> u32 {
+    max_rep_level(nested) as u32
+}
+
+/// Returns an upper bound of the number of bytes of the bitpacked levels of `nested`.
+pub(super) fn estimated_levels_size(nested: &[Nested]) -> usize {
+    let (max_def, max_rep) = max_level(nested);
+    let bits = bit_width(max_def) + bit_width(max_rep);
+    (num_values(nested) * bits as usize).div_ceil(8)
+}
+
+/// Returns the `(def_bits, rep_bits)` used to encode the levels of each leaf of `array`,
+/// in the order of [`to_nested`].
+///
+/// The widths only depend on the nesting of `type_`, so they are known before any level
+/// is computed.
+pub fn plan_level_widths(array: &dyn Array, type_: &ParquetType) -> PolarsResult<Vec<(u32, u32)>> {
+    Ok(to_nested(array, type_)?
+        .iter()
+        .map(|nested| {
+            let (max_def, max_rep) = max_level(nested);
+            (bit_width(max_def), bit_width(max_rep))
+        })
+        .collect())
+}
+
+/// Returns the null count of each page in `page_ranges`, where each range is a range of
+/// rows of `nested`.
+///
+/// The null count of a page is the number of definition levels smaller than
+/// `max_def_level` over the rows of that page, so it is computed without slicing `nested`
+/// per page.
+pub fn page_null_counts(
+    nested: &[Nested],
+    page_ranges: &[Range<usize>],
+    max_def_level: u32,
+) -> PolarsResult<Vec<usize>> {
+    let num_rows = nested.first().map_or(0, |n| n.len());
+    polars_ensure!(
+        page_ranges.iter().all(|range| range.start <= range.end && range.end <= num_rows),
+        InvalidOperation: "page ranges must be within the {} rows of the column", num_rows
+    );
+    if num_rows == 0 {
+        return Ok(vec![0; page_ranges.len()]);
+    }
+
+    // null count of every row
+    let mut row_null_counts = Vec::with_capacity(num_rows);
+    let reps = rep::RepLevelsIter::new(nested);
+    let defs = def::DefLevelsIter::new(nested);
+    if max_rep_level(nested) == 0 {
+        row_null_counts.extend(defs.map(|def| (def < max_def_level) as usize));
+    } else {
+        for (rep, def) in reps.zip(defs) {
+            if rep == 0 {
+                row_null_counts.push(0);
+            }
+            *row_null_counts.last_mut().unwrap() += (def < max_def_level) as usize;
+        }
+    }
+
+    Ok(page_ranges
+        .iter()
+        .map(|range| row_null_counts[range.clone()].iter().sum())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::AssertUnwindSafe;
+
+    use arrow::array::*;
+    use arrow::bitmap::Bitmap;
+    use arrow::compute::concatenate::concatenate;
+    use arrow::datatypes::{ArrowDataType, Field, IntervalUnit};
+    use arrow::offset::{Offsets, OffsetsBuffer};
+    use arrow::types::{i256, months_days_ns};
+
+    use super::super::pages::{normalize_struct_nulls, ListNested};
+    use super::super::{
+        array_to_columns, slice_parquet_array, to_parquet_leaves, to_parquet_type, Encoding,
+        WriteOptions,
+    };
+    use super::*;
+    use crate::parquet::encoding::hybrid_rle::decode_u32;
+    use crate::parquet::page::{split_buffer, Page};
+    use crate::parquet::read::levels::get_bit_width;
+
+    /// Returns the `(rep, def)` levels of every leaf of `array`, written as `field`.
+    fn levels(array: &dyn Array, field: &Field) -> Vec<(Vec<u32>, Vec<u32>)> {
+        let type_ = to_parquet_type(field).unwrap();
+        let array = normalize_struct_nulls(array, None).unwrap();
+        to_nested(array.as_ref(), &type_)
+            .unwrap()
+            .iter()
+            .map(|nested| {
+                let (rep, def) = compute_levels(nested).unwrap();
+                assert_eq!(rep.len(), num_values(nested));
+                (rep, def)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pages_do_not_split_lists() {
+        let lengths = (0..1000).map(|i| i % 5).collect::<Vec<_>>();
+        let offsets = std::iter::once(0)
+            .chain(lengths.iter().scan(0, |acc, len| {
+                *acc += len;
+                Some(*acc)
+            }))
+            .collect::<Vec<i32>>();
+        let num_values = *offsets.last().unwrap() as usize;
+        let values = Int64Array::from_vec((0..num_values as i64).collect());
+        let list_type =
+            ArrowDataType::List(Box::new(Field::new("item", ArrowDataType::Int64, true)));
+        let array = ListArray::<i32>::new(
+            list_type.clone(),
+            offsets.try_into().unwrap(),
+            values.boxed(),
+            None,
+        );
+
+        let max_page_size = 2048;
+        let options = WriteOptions::builder()
+            .write_statistics(false)
+            .version(Version::V2)
+            .data_pagesize_limit(Some(max_page_size))
+            .build()
+            .unwrap();
+        let type_ = to_parquet_type(&Field::new("l", list_type, true)).unwrap();
+        let pages = array_to_columns(array.boxed(), type_, options, &[Encoding::Plain])
+            .unwrap()
+            .pop()
+            .unwrap()
+            .collect::<PolarsResult<Vec<_>>>()
+            .unwrap();
+        assert!(pages.len() > 1);
+
+        let mut rows = 0;
+        for page in pages {
+            let Page::Data(page) = page else {
+                unreachable!()
+            };
+            assert!(page.buffer().len() <= max_page_size);
+
+            let (rep, _, _) = split_buffer(&page).unwrap();
+            let rep = decode_u32(&mut &rep[..], 1, page.num_values()).unwrap();
+            // every page starts a record
+            assert_eq!(rep[0], 0);
+            rows += rep.iter().filter(|r| **r == 0).count();
+        }
+        assert_eq!(rows, 1000);
+    }
+
+    #[test]
+    fn pages_are_computed_lazily() {
+        // 1M lists of ~2.5M values: the levels of each page are computed from the slice of
+        // `nested` of that page when the page is requested, so only one page is in memory.
+        let num_rows = 1_000_000;
+        let offsets = (0..=num_rows)
+            .map(|i| (i / 2) * 5 + (i % 2) * 2)
+            .collect::<Vec<i32>>();
+        let num_values = *offsets.last().unwrap() as usize;
+        let values = Int32Array::from_vec((0..num_values as i32).collect());
+        let list_type =
+            ArrowDataType::List(Box::new(Field::new("item", ArrowDataType::Int32, false)));
+        let array = ListArray::<i32>::new(
+            list_type.clone(),
+            offsets.try_into().unwrap(),
+            values.boxed(),
+            None,
+        );
+
+        let max_page_size = 64 * 1024;
+        let options = WriteOptions::builder()
+            .write_statistics(false)
+            .version(Version::V2)
+            .data_pagesize_limit(Some(max_page_size))
+            .build()
+            .unwrap();
+        let type_ = to_parquet_type(&Field::new("l", list_type, true)).unwrap();
+        let mut pages = array_to_columns(array.boxed(), type_, options, &[Encoding::Plain])
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        let (mut num_pages, mut written) = (0, 0);
+        for page in pages.by_ref() {
+            let Page::Data(page) = page.unwrap() else {
+                unreachable!()
+            };
+            assert!(page.buffer().len() <= max_page_size);
+            assert!(page.num_values() < num_values / 100);
+            num_pages += 1;
+            written += page.num_values();
+        }
+        assert!(num_pages > 100);
+        assert_eq!(written, num_values);
+    }
+
+    #[test]
+    fn write_plan() {
+        /*
+            [{"a": "a"}, {"a": "b"}],
+            None,
+            [{"a": "b"}, None, {"a": "b"}],
+            [{"a": None}, {"a": None}, {"a": None}],
+            [],
+            [{"a": "d"}, {"a": "d"}, {"a": "d"}],
+            None,
+            [{"a": "e"}],
+        */
+        let a = [
+            true, true, true, false, true, false, false, false, true, true, true, true,
+        ];
+        let b = [
+            true, true, true, false, true, true, true, true, true, true, true, true,
+        ];
+        let c = [true, false, true, true, true, true, false, true];
+        let nested = vec![
+            Nested::List(ListNested {
+                is_optional: true,
+                offsets: vec![0, 2, 2, 5, 8, 8, 11, 11, 12].try_into().unwrap(),
+                validity: Some(c.into()),
+            }),
+            Nested::Struct(Some(b.into()), true, 12),
+            Nested::Primitive(Some(a.into()), true, 12),
+        ];
+
+        let plan = compute_write_plan(&nested).unwrap();
+        assert_eq!(plan.def, vec![4, 4, 0, 4, 2, 4, 3, 3, 3, 1, 4, 4, 4, 0, 4]);
+        assert_eq!(plan.rep, vec![0, 1, 0, 0, 1, 1, 0, 1, 1, 0, 0, 1, 1, 0, 0]);
+        assert_eq!((plan.max_def, plan.max_rep), (4, 1));
+
+        // the fields agree with each other and with the other level functions
+        assert_eq!(
+            compute_levels(&nested).unwrap(),
+            (plan.rep.clone(), plan.def.clone())
+        );
+        assert_eq!(plan.def.len(), num_values(&nested));
+        assert_eq!(
+            plan.null_count,
+            plan.def.iter().filter(|d| **d < plan.max_def).count()
+        );
+        assert_eq!(plan.value_mask, a.to_vec());
+        assert_eq!(
+            plan.value_mask.iter().filter(|v| **v).count(),
+            written_value_count(&nested)
+        );
+        assert_eq!(
+            plan.rep.iter().filter(|r| **r == 0).count(),
+            nested[0].len()
+        );
+    }
+
+    #[test]
+    fn inflated_value_count() {
+        // the offsets define 3 values, but the leaf claims far more than can be allocated
+        let nested = vec![
+            Nested::List(ListNested {
+                is_optional: false,
+                offsets: vec![0, 1, 3].try_into().unwrap(),
+                validity: None,
+            }),
+            Nested::Primitive(None, false, 1 << 60),
+        ];
+        assert_eq!(num_values(&nested), 1 << 60);
+
+        let (rep, def) = compute_levels(&nested).unwrap();
+        assert_eq!(rep, vec![0, 0, 1]);
+        assert_eq!(def, vec![1, 1, 1]);
+
+        let plan = compute_write_plan(&nested).unwrap();
+        assert_eq!((plan.rep, plan.def), (rep, def));
+        assert!(plan.value_mask.capacity() <= MAX_LEVELS_CAPACITY);
+    }
+
+    #[test]
+    fn nested_info() {
+        // [[1, None], None, [], [4]]
+        let nested = vec![
+            Nested::List(ListNested {
+                is_optional: true,
+                offsets: vec![0, 2, 2, 2, 3].try_into().unwrap(),
+                validity: Some(Bitmap::from([true, false, true, true])),
+            }),
+            Nested::Primitive(Some(Bitmap::from([true, false, true])), true, 3),
+        ];
+        let info = NestedInfo::new(&nested);
+        assert_eq!(
+            info,
+            NestedInfo {
+                value_count: 5,
+                max_def: 3,
+                max_rep: 1,
+            }
+        );
+
+        let levels = compute_levels_with_info(&nested, &info).unwrap();
+        assert_eq!(levels, compute_levels(&nested).unwrap());
+        assert_eq!(levels, (vec![0, 1, 0, 0, 0], vec![3, 2, 0, 1, 3]));
+
+        for version in [Version::V1, Version::V2] {
+            let mut buffer = vec![];
+            let lengths = write_rep_and_def(version, &nested, &mut buffer).unwrap();
+            let mut with_info = vec![];
+            let lengths_with_info =
+                write_rep_and_def_with_info(version, &nested, &info, &mut with_info).unwrap();
+            assert_eq!((lengths_with_info, with_info), (lengths, buffer));
+        }
+    }
+
+    #[test]
+    fn streamed_levels() {
+        // the levels streamed into the encoder are the ones `compute_levels` collects
+        let dense_fixed_size_list = vec![
+            Nested::FixedSizeList {
+                validity: None,
+                is_optional: false,
+                width: 2,
+                len: 3,
+            },
+            Nested::Primitive(None, false, 6),
+        ];
+        let list = vec![
+            Nested::List(ListNested {
+                is_optional: true,
+                offsets: vec![0, 2, 2, 2, 3].try_into().unwrap(),
+                validity: Some(Bitmap::from([true, false, true, true])),
+            }),
+            Nested::Primitive(Some(Bitmap::from([true, false, true])), true, 3),
+        ];
+        for nested in [dense_fixed_size_list, list] {
+            let info = NestedInfo::new(&nested);
+            let (rep, def) = compute_levels(&nested).unwrap();
+            for version in [Version::V1, Version::V2] {
+                let mut expected = vec![];
+                write_levels(
+                    &mut expected,
+                    rep.iter().copied(),
+                    info.max_rep as usize,
+                    version,
+                )
+                .unwrap();
+                let rep_length = expected.len();
+                write_levels(
+                    &mut expected,
+                    def.iter().copied(),
+                    info.max_def as usize,
+                    version,
+                )
+                .unwrap();
+                let def_length = expected.len() - rep_length;
+
+                let mut buffer = vec![];
+                let lengths = write_rep_and_def(version, &nested, &mut buffer).unwrap();
+                assert_eq!((lengths, buffer), ((rep_length, def_length), expected));
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_nested() {
+        let list = || {
+            Nested::List(ListNested {
+                is_optional: true,
+                offsets: vec![0, 2, 3].try_into().unwrap(),
+                validity: None,
+            })
+        };
+        let valid = vec![
+            vec![
+                list(),
+                Nested::Struct(None, true, 3),
+                Nested::Primitive(None, true, 3),
+            ],
+            vec![
+                list(),
+                Nested::Struct(None, true, 3),
+                Nested::Primitive(None, true, 3),
+            ],
+        ];
+        assert!(validate_nested(&valid).is_ok());
+
+        // the struct under the list has more rows than the list has children
+        let mut invalid = valid.clone();
+        invalid[1][1] = Nested::Struct(None, true, 4);
+        invalid[1][2] = Nested::Primitive(None, true, 4);
+        assert!(validate_nested(&invalid).is_err());
+
+        // the leaves disagree on the number of records
+        let invalid = vec![
+            vec![
+                Nested::Struct(None, true, 3),
+                Nested::Primitive(None, true, 3),
+            ],
+            vec![
+                Nested::Struct(None, true, 2),
+                Nested::Primitive(None, true, 2),
+            ],
+        ];
+        assert!(validate_nested(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_validate_levels() {
+        // [[0, 1], None, [2], []]
+        let nested = vec![
+            Nested::List(ListNested {
+                is_optional: true,
+                offsets: vec![0, 2, 2, 3, 3].try_into().unwrap(),
+                validity: Some(Bitmap::from([true, false, true, true])),
+            }),
+            Nested::Primitive(None, false, 3),
+        ];
+        let (rep, def) = compute_levels(&nested).unwrap();
+        assert!(validate_levels(&nested, &def, &rep).is_ok());
+
+        // a level above the maximum
+        let mut invalid = def.clone();
+        invalid[0] = 3;
+        assert!(validate_levels(&nested, &invalid, &rep).is_err());
+        let mut invalid = rep.clone();
+        invalid[1] = 2;
+        assert!(validate_levels(&nested, &def, &invalid).is_err());
+
+        // the first level continues a record
+        let mut invalid = rep.clone();
+        invalid[0] = 1;
+        assert!(validate_levels(&nested, &def, &invalid).is_err());
+
+        // one record too many
+        let mut invalid = rep.clone();
+        invalid[1] = 0;
+        assert!(validate_levels(&nested, &def, &invalid).is_err());
+
+        // levels of different lengths
+        assert!(validate_levels(&nested, &def[1..], &rep).is_err());
+    }
+
+    #[test]
+    fn test_page_null_counts() {
+        // [[0, 1], None, [2, None, 3], [4, 5, 6], [], [7, 8, 9], None, [10]]
+        let v0 = [true, false, true, true, true, true, false, true];
+        let v1 = [
+            true, true, true, false, true, true, true, true, true, true, true, true,
+        ];
+        let nested = vec![
+            Nested::List(ListNested {
+                is_optional: true,
+                offsets: vec![0, 2, 2, 5, 8, 8, 11, 11, 12].try_into().unwrap(),
+                validity: Some(v0.into()),
+            }),
+            Nested::Primitive(Some(v1.into()), true, 12),
+        ];
+        let max_def_level = max_def_level(&nested) as u32;
+
+        let total = page_null_counts(&nested, &[0..8, 8..8], max_def_level).unwrap();
+        assert_eq!(total, vec![4, 0]);
+
+        let pages = page_null_counts(&nested, &[0..4, 4..8], max_def_level).unwrap();
+        assert_eq!(pages, vec![2, 2]);
+        assert_eq!(pages.iter().sum::<usize>(), total[0]);
+
+        assert!(page_null_counts(&nested, &[0..4, 4..9], max_def_level).is_err());
+
+        let empty = page_null_counts(&[], &[0..0, 0..0], max_def_level).unwrap();
+        assert_eq!(empty, vec![0, 0]);
+        assert!(page_null_counts(&[], &[0..0, 0..1], max_def_level).is_err());
+    }
+
+    #[test]
+    fn test_to_levels_with_offsets() {
+        let nested = |offsets: Vec<i32>| {
+            vec![
+                Nested::List(ListNested::new(
+                    offsets.try_into().unwrap(),
+                    Some(Bitmap::from([true, true, false, true])),
+                    true,
+                )),
+                Nested::Primitive(None, false, 5),
+            ]
+        };
+
+        // [[0, 1], [], None, [2, 3, 4]] regrouped as [[0], [1, 2], None, [3, 4]]
+        let levels = to_levels_with_offsets(
+            &nested(vec![0, 2, 2, 2, 5]),
+            0,
+            vec![0, 1, 3, 3, 5].try_into().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            levels,
+            compute_levels(&nested(vec![0, 1, 3, 3, 5])).unwrap()
+        );
+
+        // different number of lists or values
+        let original = nested(vec![0, 2, 2, 2, 5]);
+        assert!(to_levels_with_offsets(&original, 0, vec![0, 5].try_into().unwrap()).is_err());
+        assert!(
+            to_levels_with_offsets(&original, 0, vec![0, 1, 1, 1, 4].try_into().unwrap()).is_err()
+        );
+        // not a list
+        assert!(to_levels_with_offsets(&original, 1, vec![0, 5].try_into().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_plan_level_widths() {
+        let struct_type = ArrowDataType::Struct(vec![
+            Field::new("b", ArrowDataType::Boolean, false),
+            Field::new("c", ArrowDataType::Int32, false),
+        ]);
+        let array = StructArray::new(
+            struct_type.clone(),
+            vec![
+                BooleanArray::from_slice([false, false, true, true]).boxed(),
+                Int32Array::from_slice([42, 28, 19, 31]).boxed(),
+            ],
+            Some(Bitmap::from([true, true, false, true])),
+        );
+        let list_type = ArrowDataType::List(Box::new(Field::new("a", struct_type, true)));
+        let array = ListArray::<i32>::new(
+            list_type.clone(),
+            vec![0, 2, 4].try_into().unwrap(),
+            array.boxed(),
+            None,
+        );
+        let type_ = to_parquet_type(&Field::new("l", list_type, false)).unwrap();
+
+        let widths = plan_level_widths(&array, &type_).unwrap();
+
+        let expected = to_nested(&array, &type_)
+            .unwrap()
+            .iter()
+            .map(|nested| {
+                (
+                    get_bit_width(max_def_level(nested) as i16),
+                    get_bit_width(max_rep_level(nested) as i16),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(widths, expected);
+        assert_eq!(widths, vec![(2, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn map_of_list_of_struct() {
+        /*
+        [
+            {"k1": [{"a": 1}, {"a": 2}], "k2": []},
+            None,
+            {},
+            {"k3": None, "k4": [None, {"a": None}]},
+        ]
+        */
+        let a = Int32Array::from([Some(1), Some(2), None, None]).boxed();
+        let struct_type = ArrowDataType::Struct(vec![Field::new("a", ArrowDataType::Int32, true)]);
+        let structs = StructArray::new(
+            struct_type.clone(),
+            vec![a],
+            Some(Bitmap::from([true, true, false, true])),
+        );
+        let list_type = ArrowDataType::List(Box::new(Field::new("item", struct_type, true)));
+        let lists = ListArray::<i32>::new(
+            list_type.clone(),
+            vec![0, 2, 2, 2, 4].try_into().unwrap(),
+            structs.boxed(),
+            Some(Bitmap::from([true, true, false, true])),
+        );
+        let keys = Utf8Array::<i32>::from_slice(["k1", "k2", "k3", "k4"]).boxed();
+        let kv_type = ArrowDataType::Struct(vec![
+            Field::new("key", ArrowDataType::Utf8, false),
+            Field::new("value", list_type, true),
+        ]);
+        let kv = StructArray::new(kv_type.clone(), vec![keys, lists.boxed()], None);
+        let map_type = ArrowDataType::Map(Box::new(Field::new("entries", kv_type, false)), false);
+        let array = MapArray::new(
+            map_type.clone(),
+            OffsetsBuffer::try_from(vec![0, 2, 2, 2, 4]).unwrap(),
+            kv.boxed(),
+            Some(Bitmap::from([true, false, true, true])),
+        );
+
+        let levels = levels(&array, &Field::new("m", map_type, true));
+        assert_eq!(
+            levels,
+            vec![
+                // key
+                (vec![0, 1, 0, 0, 0, 1], vec![2, 2, 0, 1, 2, 2]),
+                // value.a
+                (vec![0, 2, 1, 0, 0, 0, 1, 2], vec![6, 6, 3, 0, 1, 2, 4, 5]),
+            ]
+        );
+    }
+
+    #[test]
+    fn required_list_of_all_null_elements() {
+        // [[None, None], [None], [], [None]]
+        let list_type =
+            ArrowDataType::List(Box::new(Field::new("item", ArrowDataType::Int32, true)));
+        let array = ListArray::<i32>::new(
+            list_type.clone(),
+            vec![0, 2, 3, 3, 4].try_into().unwrap(),
+            Int32Array::new_null(ArrowDataType::Int32, 4).boxed(),
+            None,
+        );
+
+        // a defined list with a null element is at def level 1, an empty list at 0
+        let field = Field::new("l", list_type, false);
+        assert_eq!(
+            levels(&array, &field),
+            vec![(vec![0, 1, 0, 0, 0], vec![1, 1, 1, 0, 1])]
+        );
+
+        // no value is written
+        let options = WriteOptions::builder()
+            .write_statistics(false)
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let type_ = to_parquet_type(&field).unwrap();
+        let mut columns =
+            array_to_columns(array.boxed(), type_, options, &[Encoding::Plain]).unwrap();
+        let Page::Data(page) = columns[0].next().unwrap().unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(page.num_values(), 5);
+        let (_, _, values) = split_buffer(&page).unwrap();
+        assert!(values.is_empty());
+    }
+
+    #[test]
+    fn list_of_decimal256() {
+        // [[1, None], None, [-2, 2^128]]
+        let values = PrimitiveArray::<i256>::from([
+            Some(i256::from_words(0, 1)),
+            None,
+            Some(i256::from_words(-1, -2)),
+            Some(i256::from_words(1, 0)),
+        ])
+        .to(ArrowDataType::Decimal256(50, 2));
+        let list_type = ArrowDataType::List(Box::new(Field::new(
+            "item",
+            ArrowDataType::Decimal256(50, 2),
+            true,
+        )));
+        let array = ListArray::<i32>::new(
+            list_type.clone(),
+            vec![0, 2, 2, 4].try_into().unwrap(),
+            values.boxed(),
+            Some(Bitmap::from([true, false, true])),
+        );
+        let field = Field::new("l", list_type, true);
+
+        let type_ = to_parquet_type(&field).unwrap();
+        let leaves = to_parquet_leaves(type_.clone());
+        assert_eq!(
+            leaves[0].physical_type,
+            crate::parquet::schema::types::PhysicalType::FixedLenByteArray(32)
+        );
+        assert_eq!(
+            leaves[0].logical_type,
+            Some(crate::parquet::schema::types::PrimitiveLogicalType::Decimal(50, 2))
+        );
+
+        assert_eq!(
+            levels(&array, &field),
+            vec![(vec![0, 1, 0, 0, 1], vec![3, 2, 0, 3, 3])]
+        );
+
+        // the valid values are written as 32-byte big-endian integers
+        let options = WriteOptions::builder()
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let mut columns =
+            array_to_columns(array.boxed(), type_, options, &[Encoding::Plain]).unwrap();
+        let Page::Data(page) = columns[0].next().unwrap().unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(page.num_values(), 5);
+        let (_, _, values) = split_buffer(&page).unwrap();
+        let expected = [1i128, -2]
+            .iter()
+            .flat_map(|x| {
+                let hi = if *x < 0 { -1i128 } else { 0 };
+                hi.to_be_bytes().into_iter().chain(x.to_be_bytes())
+            })
+            .chain(1i128.to_be_bytes().into_iter().chain(0i128.to_be_bytes()))
+            .collect::<Vec<_>>();
+        assert_eq!(values, expected);
+
+        // 77 digits do not fit in 32 bytes
+        let field = Field::new("d", ArrowDataType::Decimal256(77, 2), true);
+        assert!(to_parquet_type(&field).is_err());
+    }
+
+    #[test]
+    fn list_of_interval() {
+        // [[(1 month, 2 days, 3ms), None], None, [(4 months, 0 days, 1.5ms)]]
+        let interval = ArrowDataType::Interval(IntervalUnit::MonthDayNano);
+        let values = PrimitiveArray::<months_days_ns>::from([
+            Some(months_days_ns::new(1, 2, 3_000_000)),
+            None,
+            Some(months_days_ns::new(4, 0, 1_500_000)),
+        ])
+        .to(interval.clone());
+        let list_type = ArrowDataType::List(Box::new(Field::new("item", interval, true)));
+        let array = ListArray::<i32>::new(
+            list_type.clone(),
+            vec![0, 2, 2, 3].try_into().unwrap(),
+            values.boxed(),
+            Some(Bitmap::from([true, false, true])),
+        );
+        let field = Field::new("l", list_type, true);
+
+        let type_ = to_parquet_type(&field).unwrap();
+        let leaves = to_parquet_leaves(type_.clone());
+        assert_eq!(
+            leaves[0].physical_type,
+            crate::parquet::schema::types::PhysicalType::FixedLenByteArray(12)
+        );
+        assert_eq!(
+            leaves[0].converted_type,
+            Some(crate::parquet::schema::types::PrimitiveConvertedType::Interval)
+        );
+
+        assert_eq!(
+            levels(&array, &field),
+            vec![(vec![0, 1, 0, 0], vec![3, 2, 0, 3])]
+        );
+
+        // months, days and milliseconds of the valid values
+        let options = WriteOptions::builder()
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let mut columns =
+            array_to_columns(array.boxed(), type_, options.clone(), &[Encoding::Plain]).unwrap();
+        let Page::Data(page) = columns[0].next().unwrap().unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(page.num_values(), 4);
+        let (_, _, values) = split_buffer(&page).unwrap();
+        let expected = [[1u32, 2, 3], [4, 0, 1]]
+            .iter()
+            .flatten()
+            .flat_map(|x| x.to_le_bytes())
+            .collect::<Vec<_>>();
+        assert_eq!(values, expected);
+
+        // a flat column with milliseconds that do not fit 4 bytes
+        let array =
+            PrimitiveArray::<months_days_ns>::from([Some(months_days_ns::new(0, 0, i64::MAX))])
+                .to(ArrowDataType::Interval(IntervalUnit::MonthDayNano));
+        let field = Field::new("i", array.data_type().clone(), true);
+        let type_ = to_parquet_type(&field).unwrap();
+        let mut columns =
+            array_to_columns(array.boxed(), type_, options.clone(), &[Encoding::Plain]).unwrap();
+        assert!(columns[0].next().unwrap().is_err());
+
+        // parquet's INTERVAL is unsigned: negative components are an error
+        for interval in [
+            months_days_ns::new(-1, 0, 0),
+            months_days_ns::new(0, -1, 0),
+            months_days_ns::new(0, 0, -1_000_000),
+        ] {
+            let array = PrimitiveArray::<months_days_ns>::from([None, Some(interval)])
+                .to(ArrowDataType::Interval(IntervalUnit::MonthDayNano));
+            let field = Field::new("i", array.data_type().clone(), true);
+            let type_ = to_parquet_type(&field).unwrap();
+            let mut columns =
+                array_to_columns(array.boxed(), type_, options.clone(), &[Encoding::Plain])
+                    .unwrap();
+            assert!(columns[0].next().unwrap().is_err());
+        }
+    }
+
+    #[test]
+    fn top_level_map() {
+        // [{"a": 1, "b": None}, None, {}, {"c": 3}]
+        let keys = Utf8Array::<i32>::from_slice(["a", "b", "c"]).boxed();
+        let values = Int32Array::from([Some(1), None, Some(3)]).boxed();
+        let kv_type = ArrowDataType::Struct(vec![
+            Field::new("key", ArrowDataType::Utf8, false),
+            Field::new("value", ArrowDataType::Int32, true),
+        ]);
+        let kv = StructArray::new(kv_type.clone(), vec![keys, values], None);
+        let map_type = ArrowDataType::Map(Box::new(Field::new("entries", kv_type, false)), false);
+        let offsets = OffsetsBuffer::try_from(vec![0, 2, 2, 2, 3]).unwrap();
+        let validity = Bitmap::from([true, false, true, true]);
+        let array = MapArray::new(
+            map_type.clone(),
+            offsets.clone(),
+            kv.boxed(),
+            Some(validity.clone()),
+        );
+        let field = Field::new("m", map_type, true);
+
+        let type_ = to_parquet_type(&field).unwrap();
+        let nested = to_nested(&array, &type_).unwrap();
+        let map = Nested::Map(
+            ListNested::new(offsets, Some(validity), true),
+            Box::new((
+                Nested::Primitive(None, false, 3),
+                Nested::Primitive(Some(Bitmap::from([true, false, true])), true, 3),
+            )),
+        );
+        let entries = Nested::Struct(None, false, 3);
+        assert_eq!(
+            nested,
+            vec![
+                vec![
+                    map.clone(),
+                    entries.clone(),
+                    Nested::Primitive(None, false, 3)
+                ],
+                vec![
+                    map,
+                    entries,
+                    Nested::Primitive(Some(Bitmap::from([true, false, true])), true, 3)
+                ],
+            ]
+        );
+        for nested in &nested {
+            assert_eq!(max_rep_level(nested), 1);
+        }
+
+        assert_eq!(
+            levels(&array, &field),
+            vec![
+                // key
+                (vec![0, 1, 0, 0, 0], vec![2, 2, 0, 1, 2]),
+                // value
+                (vec![0, 1, 0, 0, 0], vec![3, 2, 0, 1, 3]),
+            ]
+        );
+    }
+
+    #[test]
+    fn map_key_and_value() {
+        // [{"a": 1, "b": None}, None, {"c": 3}]
+        let map = Nested::Map(
+            ListNested::new(
+                vec![0, 2, 2, 3].try_into().unwrap(),
+                Some(Bitmap::from([true, false, true])),
+                true,
+            ),
+            Box::new((
+                Nested::Primitive(None, false, 3),
+                Nested::Primitive(Some(Bitmap::from([true, false, true])), true, 3),
+            )),
+        );
+        let nested = vec![
+            map,
+            Nested::Struct(None, false, 3),
+            Nested::Primitive(None, false, 3),
+        ];
+
+        let serialized = serialize_nested(&nested);
+        assert_eq!(deserialize_nested(&serialized).unwrap(), nested);
+
+        // the key and value are sliced with the entries
+        let mut sliced = nested.clone();
+        let mut keys = Utf8Array::<i32>::from_slice(["a", "b", "c"]);
+        slice_parquet_array(&mut keys, &mut sliced, 1, 2);
+        let Nested::Map(_, entries) = &sliced[0] else {
+            panic!("expected a map, found {:?}", sliced[0])
+        };
+        assert_eq!(entries.0, Nested::Primitive(None, false, 1));
+        assert_eq!(
+            entries.1,
+            Nested::Primitive(Some(Bitmap::from([true])), true, 1)
+        );
+        assert_eq!(keys, Utf8Array::<i32>::from_slice(["c"]));
+    }
+
+    #[test]
+    fn list_of_nullable_maps() {
+        /*
+        [
+            None,
+            [],
+            [None, {}, {"a": 1, "b": None}],
+        ]
+        The null list owns the map {"y": 9} and the null map owns {"z": 7}.
+        */
+        let keys = Utf8Array::<i32>::from_slice(["y", "z", "a", "b"]).boxed();
+        let values = Int32Array::from([Some(9), Some(7), Some(1), None]).boxed();
+        let kv_type = ArrowDataType::Struct(vec![
+            Field::new("key", ArrowDataType::Utf8, false),
+            Field::new("value", ArrowDataType::Int32, true),
+        ]);
+        let kv = StructArray::new(kv_type.clone(), vec![keys, values], None);
+        let map_type = ArrowDataType::Map(Box::new(Field::new("entries", kv_type, false)), false);
+        let maps = MapArray::new(
+            map_type.clone(),
+            OffsetsBuffer::try_from(vec![0, 1, 2, 2, 4]).unwrap(),
+            kv.boxed(),
+            Some(Bitmap::from([true, false, true, true])),
+        );
+        let list_type = ArrowDataType::List(Box::new(Field::new("item", map_type, true)));
+        let array = ListArray::<i32>::new(
+            list_type.clone(),
+            vec![0, 1, 1, 4].try_into().unwrap(),
+            maps.boxed(),
+            Some(Bitmap::from([false, true, true])),
+        );
+
+        let levels = levels(&array, &Field::new("l", list_type, true));
+        assert_eq!(
+            levels,
+            vec![
+                // key
+                (vec![0, 0, 0, 1, 1, 2], vec![0, 1, 2, 3, 4, 4]),
+                // value
+                (vec![0, 0, 0, 1, 1, 2], vec![0, 1, 2, 3, 5, 4]),
+            ]
+        );
+    }
+
+    #[test]
+    fn struct_of_mixed_nullability() {
+        /*
+        [
+            {"a": 1, "b": "x", "c": [1, None]},
+            None,
+            {"a": 3, "b": None, "c": None},
+            {"a": 4, "b": "z", "c": []},
+        ]
+        The null struct owns {"a": 2, "b": "y", "c": [3]}.
+        */
+        let a = Int32Array::from_slice([1, 2, 3, 4]).boxed();
+        let b = Utf8Array::<i64>::from([Some("x"), Some("y"), None, Some("z")]).boxed();
+        let list_type =
+            ArrowDataType::List(Box::new(Field::new("item", ArrowDataType::Int32, true)));
+        let c = ListArray::<i32>::new(
+            list_type.clone(),
+            vec![0, 2, 3, 3, 3].try_into().unwrap(),
+            Int32Array::from([Some(1), None, Some(3)]).boxed(),
+            Some(Bitmap::from([true, true, false, true])),
+        )
+        .boxed();
+        let struct_type = ArrowDataType::Struct(vec![
+            Field::new("a", ArrowDataType::Int32, false),
+            Field::new("b", ArrowDataType::LargeUtf8, true),
+            Field::new("c", list_type, true),
+        ]);
+        let array = StructArray::new(
+            struct_type.clone(),
+            vec![a, b, c],
+            Some(Bitmap::from([true, false, true, true])),
+        );
+        let field = Field::new("s", struct_type, true);
+
+        assert_eq!(
+            levels(&array, &field),
+            vec![
+                // a
+                (vec![0, 0, 0, 0], vec![1, 0, 1, 1]),
+                // b
+                (vec![0, 0, 0, 0], vec![2, 0, 1, 2]),
+                // c
+                (vec![0, 1, 0, 0, 0], vec![4, 3, 0, 1, 2]),
+            ]
+        );
+
+        // the values of the null struct are not written
+        let options = WriteOptions::builder()
+            .write_statistics(false)
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let type_ = to_parquet_type(&field).unwrap();
+        let columns =
+            array_to_columns(array.boxed(), type_, options, &[Encoding::Plain; 3]).unwrap();
+        let values = columns
+            .into_iter()
+            .map(|mut pages| {
+                let Page::Data(page) = pages.next().unwrap().unwrap() else {
+                    unreachable!()
+                };
+                let (_, _, values) = split_buffer(&page).unwrap();
+                values.to_vec()
+            })
+            .collect::<Vec<_>>();
+        let int32s =
+            |values: &[i32]| -> Vec<u8> { values.iter().flat_map(|x| x.to_le_bytes()).collect() };
+        assert_eq!(values[0], int32s(&[1, 3, 4]));
+        assert_eq!(values[1], b"\x01\0\0\0x\x01\0\0\0z".to_vec());
+        assert_eq!(values[2], int32s(&[1]));
+    }
+
+    #[test]
+    fn sliced_top_level() {
+        /*
+        [
+            {"a": 1, "l": [1, 2]},
+            None,
+            {"a": None, "l": None},
+            {"a": 4, "l": [4, None, 6]},
+            {"a": 5, "l": []},
+            {"a": 6, "l": [7]},
+        ]
+        */
+        let a = Int32Array::from([Some(1), Some(2), None, Some(4), Some(5), Some(6)]).boxed();
+        let list_type =
+            ArrowDataType::List(Box::new(Field::new("item", ArrowDataType::Int32, true)));
+        let l = ListArray::<i32>::new(
+            list_type.clone(),
+            vec![0, 2, 3, 3, 6, 6, 7].try_into().unwrap(),
+            Int32Array::from([Some(1), Some(2), Some(3), Some(4), None, Some(6), Some(7)]).boxed(),
+            Some(Bitmap::from([true, true, false, true, true, true])),
+        )
+        .boxed();
+        let struct_type = ArrowDataType::Struct(vec![
+            Field::new("a", ArrowDataType::Int32, true),
+            Field::new("l", list_type.clone(), true),
+        ]);
+        let structs = StructArray::new(
+            struct_type.clone(),
+            vec![a, l.clone()],
+            Some(Bitmap::from([true, false, true, true, true, true])),
+        )
+        .boxed();
+
+        let options = WriteOptions::builder()
+            .write_statistics(false)
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let pages = |array: Box<dyn Array>, field: &Field| {
+            let type_ = to_parquet_type(field).unwrap();
+            let encoding = vec![Encoding::Plain; to_parquet_leaves(type_.clone()).len()];
+            array_to_columns(array, type_, options.clone(), &encoding)
+                .unwrap()
+                .into_iter()
+                .flat_map(|pages| {
+                    pages.map(|page| {
+                        let Page::Data(page) = page.unwrap() else {
+                            unreachable!()
+                        };
+                        page.buffer().to_vec()
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+
+        for (array, field) in [
+            (structs, Field::new("s", struct_type, true)),
+            (l, Field::new("l", list_type, true)),
+        ] {
+            for (offset, length) in [(0, 6), (1, 3), (2, 4), (3, 1), (6, 0)] {
+                let sliced = array.sliced(offset, length);
+                let expected = concatenate(&[sliced.as_ref()]).unwrap();
+
+                assert_eq!(
+                    levels(sliced.as_ref(), &field),
+                    levels(expected.as_ref(), &field)
+                );
+                assert_eq!(pages(sliced, &field), pages(expected, &field));
+            }
+        }
+    }
+
+    #[test]
+    fn strict_required_nulls() {
+        let options = |strict| {
+            WriteOptions::builder()
+                .write_statistics(false)
+                .version(Version::V2)
+                .strict(strict)
+                .build()
+                .unwrap()
+        };
+        let write = |array: Box<dyn Array>, field: &Field, strict| {
+            let type_ = to_parquet_type(field).unwrap();
+            array_to_columns(array, type_, options(strict), &[Encoding::Plain]).map(|_| ())
+        };
+
+        // a required Int32 column with a null
+        let array = Int32Array::from([Some(1), None, Some(3)]).boxed();
+        let field = Field::new("a", ArrowDataType::Int32, false);
+        assert!(write(array.clone(), &field, true).is_err());
+        assert!(write(array, &field, false).is_ok());
+
+        // the null of a required field under a null struct is inherited
+        let struct_type = ArrowDataType::Struct(vec![Field::new("a", ArrowDataType::Int32, false)]);
+        let array = StructArray::new(
+            struct_type.clone(),
+            vec![Int32Array::from([Some(1), None, Some(3)]).boxed()],
+            Some(Bitmap::from([true, false, true])),
+        );
+        let field = Field::new("s", struct_type.clone(), true);
+        assert!(write(array.boxed(), &field, true).is_ok());
+
+        // ...but not under a valid one
+        let array = StructArray::new(
+            struct_type,
+            vec![Int32Array::from([Some(1), None, Some(3)]).boxed()],
+            Some(Bitmap::from([true, true, false])),
+        );
+        assert!(write(array.boxed(), &field, true).is_err());
+    }
+
+    #[test]
+    fn all_required_list_of_struct_of_list() {
+        // [[{"l": [1, 2]}, {"l": [3]}], [{"l": [4, 5, 6]}]]
+        let inner_type =
+            ArrowDataType::List(Box::new(Field::new("item", ArrowDataType::Int32, false)));
+        let struct_type = ArrowDataType::Struct(vec![Field::new("l", inner_type.clone(), false)]);
+        let outer_type =
+            ArrowDataType::List(Box::new(Field::new("item", struct_type.clone(), false)));
+        let build = |inner_offsets: Vec<i32>| {
+            let num_values = *inner_offsets.last().unwrap() as usize;
+            let inner = ListArray::<i32>::new(
+                inner_type.clone(),
+                inner_offsets.try_into().unwrap(),
+                Int32Array::from_vec((1..=num_values as i32).collect()).boxed(),
+                None,
+            );
+            let structs = StructArray::new(struct_type.clone(), vec![inner.boxed()], None);
+            ListArray::<i32>::new(
+                outer_type.clone(),
+                vec![0, 2, 3].try_into().unwrap(),
+                structs.boxed(),
+                None,
+            )
+        };
+        let field = Field::new("l", outer_type.clone(), false);
+        let nested = |array: &ListArray<i32>| {
+            let type_ = to_parquet_type(&field).unwrap();
+            to_nested(array, &type_).unwrap().pop().unwrap()
+        };
+
+        let array = build(vec![0, 2, 3, 6]);
+        assert_eq!(
+            levels(&array, &field),
+            vec![(vec![0, 2, 1, 0, 2, 2], vec![2; 6])]
+        );
+
+        // the folded definition levels are the ones of the recursive computation
+        let nested_ = nested(&array);
+        assert!(is_fully_defined(&nested_));
+        let (_, def_levels) = compute_levels(&nested_).unwrap();
+        assert_eq!(
+            def_levels,
+            def::DefLevelsIter::new(&nested_).collect::<Vec<_>>()
+        );
+
+        // an empty inner list is below the maximum definition level
+        let array = build(vec![0, 2, 2, 5]);
+        assert!(!is_fully_defined(&nested(&array)));
+        assert_eq!(
+            levels(&array, &field),
+            vec![(vec![0, 2, 1, 0, 2, 2], vec![2, 2, 1, 2, 2, 2])]
+        );
+    }
+
+    #[test]
+    fn list_of_list_of_struct() {
+        /*
+        [
+            [[{"a": 1, "b": "x"}, None], [{"a": None, "b": "z"}]],
+            None,
+            [None],
+        ]
+        */
+        let a = Int32Array::from([Some(1), None, None]).boxed();
+        let b = Utf8Array::<i32>::from_slice(["x", "y", "z"]).boxed();
+        let struct_type = ArrowDataType::Struct(vec![
+            Field::new("a", ArrowDataType::Int32, true),
+            Field::new("b", ArrowDataType::Utf8, false),
+        ]);
+        let structs = StructArray::new(
+            struct_type.clone(),
+            vec![a, b],
+            Some(Bitmap::from([true, false, true])),
+        );
+        let inner_type = ArrowDataType::List(Box::new(Field::new("item", struct_type, true)));
+        let inner = ListArray::<i32>::new(
+            inner_type.clone(),
+            vec![0, 2, 3, 3].try_into().unwrap(),
+            structs.boxed(),
+            Some(Bitmap::from([true, true, false])),
+        );
+        let outer_type = ArrowDataType::List(Box::new(Field::new("item", inner_type, true)));
+        let array = ListArray::<i32>::new(
+            outer_type.clone(),
+            vec![0, 2, 2, 3].try_into().unwrap(),
+            inner.boxed(),
+            Some(Bitmap::from([true, false, true])),
+        );
+
+        let levels = levels(&array, &Field::new("l", outer_type, true));
+        assert_eq!(
+            levels,
+            vec![
+                // a
+                (vec![0, 2, 1, 0, 0], vec![6, 4, 5, 0, 2]),
+                // b
+                (vec![0, 2, 1, 0, 0], vec![5, 4, 5, 0, 2]),
+            ]
+        );
+    }
+
+    #[test]
+    fn fixed_size_list_of_fixed_size_list() {
+        /*
+        [
+            [[1, 2, None], None],
+            None,
+        ]
+        */
+        let values = Int32Array::from([
+            Some(1),
+            Some(2),
+            None,
+            Some(4),
+            Some(5),
+            Some(6),
+            Some(7),
+            Some(8),
+            Some(9),
+            Some(10),
+            Some(11),
+            Some(12),
+        ]);
+        let inner_type = ArrowDataType::FixedSizeList(
+            Box::new(Field::new("item", ArrowDataType::Int32, true)),
+            3,
+        );
+        let inner = FixedSizeListArray::new(
+            inner_type.clone(),
+            values.boxed(),
+            Some(Bitmap::from([true, false, true, true])),
+        );
+        let outer_type =
+            ArrowDataType::FixedSizeList(Box::new(Field::new("item", inner_type, true)), 2);
+        let array = FixedSizeListArray::new(
+            outer_type.clone(),
+            inner.boxed(),
+            Some(Bitmap::from([true, false])),
+        );
+
+        let levels = levels(&array, &Field::new("l", outer_type, true));
+        assert_eq!(levels, vec![(vec![0, 2, 2, 1, 0], vec![5, 5, 4, 2, 0])]);
+    }
+
+    #[test]
+    fn large_list_offsets_beyond_i32() {
+        // [[a, b], None, [c]], sliced from a list whose values start past `i32::MAX`
+        let start = 1i64 << 32;
+        let list = ListNested::<i64> {
+            is_optional: true,
+            offsets: vec![start, start + 2, start + 2, start + 3]
+                .try_into()
+                .unwrap(),
+            validity: Some(Bitmap::from([true, false, true])),
+        };
+        let nested = vec![Nested::LargeList(list), Nested::Primitive(None, true, 3)];
+        validate_nested(std::slice::from_ref(&nested)).unwrap();
+        assert_eq!(
+            compute_levels(&nested).unwrap(),
+            (vec![0, 1, 0, 0], vec![3, 3, 0, 3])
+        );
+        assert_eq!(num_values(&nested), 4);
+    }
+
+    #[test]
+    fn level_bit_widths() {
+        assert_eq!(
+            (0..=8).map(bit_width).collect::<Vec<_>>(),
+            vec![0, 1, 2, 2, 3, 3, 3, 3, 4]
+        );
+        assert_eq!(bit_width(u32::MAX), 32);
+        for max in 0..100 {
+            assert_eq!(bit_width(max), get_bit_width(max as i16));
+        }
+    }
+
+    #[test]
+    fn fixed_size_list_of_width_zero() {
+        // `FixedSizeListArray` cannot hold a size of 0, so the nesting is built directly.
+        let fsl = |validity: Option<Bitmap>, is_optional| Nested::FixedSizeList {
+            validity,
+            is_optional,
+            width: 0,
+            len: 4,
+        };
+        let leaf = Nested::Primitive(None, true, 0);
+        let levels = |nested: &[Nested]| {
+            let (rep, def) = compute_levels(nested).unwrap();
+            assert_eq!(rep.len(), num_values(nested));
+            (rep, def)
+        };
+
+        // every element is empty and contributes a single level
+        let validity = Bitmap::from([true, false, true, true]);
+        assert_eq!(
+            levels(&[fsl(Some(validity.clone()), true), leaf.clone()]),
+            (vec![0, 0, 0, 0], vec![1, 0, 1, 1])
+        );
+        assert_eq!(
+            levels(&[fsl(None, true), leaf.clone()]),
+            (vec![0, 0, 0, 0], vec![1, 1, 1, 1])
+        );
+        assert_eq!(
+            levels(&[fsl(None, false), leaf.clone()]),
+            (vec![0, 0, 0, 0], vec![0, 0, 0, 0])
+        );
+
+        // [[[], None], None, [[], []]]
+        let list = Nested::List(ListNested::<i32> {
+            is_optional: true,
+            offsets: vec![0, 2, 2, 4].try_into().unwrap(),
+            validity: Some(Bitmap::from([true, false, true])),
+        });
+        let nested = [list, fsl(Some(validity), true), leaf];
+        assert_eq!(levels(&nested), (vec![0, 1, 0, 0, 1], vec![3, 2, 0, 3, 3]));
+        assert!(!is_fully_defined(&nested));
+    }
+
+    #[test]
+    fn struct_of_map() {
+        /*
+        [
+            {"m": {"a": 1, "b": None}},
+            None,
+            {"m": None},
+            {"m": {}},
+        ]
+        */
+        let keys = Utf8Array::<i32>::from_slice(["a", "b", "c"]).boxed();
+        let values = Int32Array::from([Some(1), None, Some(3)]).boxed();
+        let kv_type = ArrowDataType::Struct(vec![
+            Field::new("key", ArrowDataType::Utf8, false),
+            Field::new("value", ArrowDataType::Int32, true),
+        ]);
+        let kv = StructArray::new(kv_type.clone(), vec![keys, values], None);
+        let map_type = ArrowDataType::Map(Box::new(Field::new("entries", kv_type, false)), false);
+        // the entry of the null struct is not empty
+        let map = MapArray::new(
+            map_type.clone(),
+            OffsetsBuffer::try_from(vec![0, 2, 3, 3, 3]).unwrap(),
+            kv.boxed(),
+            Some(Bitmap::from([true, true, false, true])),
+        );
+        let struct_type = ArrowDataType::Struct(vec![Field::new("m", map_type, true)]);
+        let array = StructArray::new(
+            struct_type.clone(),
+            vec![map.boxed()],
+            Some(Bitmap::from([true, false, true, true])),
+        );
+
+        let levels = levels(&array, &Field::new("s", struct_type, true));
+        assert_eq!(
+            levels,
+            vec![
+                // m.key
+                (vec![0, 1, 0, 0, 0], vec![3, 3, 0, 1, 2]),
+                // m.value
+                (vec![0, 1, 0, 0, 0], vec![4, 3, 0, 1, 2]),
+            ]
+        );
+    }
+
+    #[test]
+    fn struct_of_list_of_struct() {
+        /*
+        [
+            {"l": [{"a": 1, "b": 10}, {"a": None, "b": 20}, None]},
+            None,
+            {"l": None},
+            {"l": []},
+            {"l": [{"a": 4, "b": 40}, {"a": 5, "b": 50}]},
+        ]
+        The null struct owns {"l": [{"a": 7, "b": 70}]}.
+        */
+        let inner_type = ArrowDataType::Struct(vec![
+            Field::new("a", ArrowDataType::Int32, true),
+            Field::new("b", ArrowDataType::Int32, false),
+        ]);
+        let inner = StructArray::new(
+            inner_type.clone(),
+            vec![
+                Int32Array::from([Some(1), None, None, Some(7), Some(4), Some(5)]).boxed(),
+                Int32Array::from_slice([10, 20, 0, 70, 40, 50]).boxed(),
+            ],
+            Some(Bitmap::from([true, true, false, true, true, true])),
+        );
+        let list_type = ArrowDataType::List(Box::new(Field::new("item", inner_type, true)));
+        let list = ListArray::<i32>::new(
+            list_type.clone(),
+            vec![0, 3, 4, 4, 4, 6].try_into().unwrap(),
+            inner.boxed(),
+            Some(Bitmap::from([true, true, false, true, true])),
+        );
+        let struct_type = ArrowDataType::Struct(vec![Field::new("l", list_type, true)]);
+        let array = StructArray::new(
+            struct_type.clone(),
+            vec![list.boxed()],
+            Some(Bitmap::from([true, false, true, true, true])),
+        );
+        let field = Field::new("s", struct_type, true);
+
+        let expected = vec![
+            // a
+            (vec![0, 1, 1, 0, 0, 0, 0, 1], vec![5, 4, 3, 0, 1, 2, 5, 5]),
+            // b
+            (vec![0, 1, 1, 0, 0, 0, 0, 1], vec![4, 4, 3, 0, 1, 2, 4, 4]),
+        ];
+        assert_eq!(levels(&array, &field), expected);
+
+        // the single pass of the write plan agrees with the levels
+        let type_ = to_parquet_type(&field).unwrap();
+        let normalized = normalize_struct_nulls(&array, None).unwrap();
+        let nested = to_nested(normalized.as_ref(), &type_).unwrap();
+        let plans = nested
+            .iter()
+            .map(|nested| compute_write_plan(nested).unwrap())
+            .collect::<Vec<_>>();
+        for (plan, (rep, def)) in plans.iter().zip(&expected) {
+            assert_eq!((&plan.rep, &plan.def), (rep, def));
+        }
+        assert_eq!(plans[0].value_mask, vec![true, false, false, true, true]);
+        assert_eq!(plans[1].value_mask, vec![true, true, false, true, true]);
+
+        // and so do the levels written to the pages, whose maximum definition levels (5 and 4)
+        // take 3 bits
+        let options = WriteOptions::builder()
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let columns =
+            array_to_columns(array.boxed(), type_, options, &[Encoding::Plain; 2]).unwrap();
+        assert_eq!(columns.len(), expected.len());
+        for (mut pages, (rep, def)) in columns.into_iter().zip(&expected) {
+            let Page::Data(page) = pages.next().unwrap().unwrap() else {
+                unreachable!()
+            };
+            assert!(pages.next().is_none());
+            let (rep_levels, def_levels, _) = split_buffer(&page).unwrap();
+            assert_eq!(
+                &decode_u32(&mut &rep_levels[..], 1, rep.len()).unwrap(),
+                rep
+            );
+            assert_eq!(
+                &decode_u32(&mut &def_levels[..], 3, def.len()).unwrap(),
+                def
+            );
+        }
+    }
+
+    /// A nested type of the randomized level tests. Its leaves are `Int32`.
+    #[derive(Debug, Clone)]
+    struct Shape {
+        is_nullable: bool,
+        kind: ShapeKind,
+    }
+
+    #[derive(Debug, Clone)]
+    enum ShapeKind {
+        Leaf,
+        List(Box<Shape>),
+        FixedSizeList(usize, Box<Shape>),
+        Struct(Vec<Shape>),
+    }
+
+    /// xorshift, so that the cases are reproducible
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, n: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % n
+        }
+    }
+
+    impl Shape {
+        fn random(rng: &mut Rng, depth: usize) -> Self {
+            let is_nullable = rng.below(2) == 0;
+            let kind = if depth == 0 { 0 } else { rng.below(4) };
+            let kind = match kind {
+                0 => ShapeKind::Leaf,
+                1 => ShapeKind::List(Box::new(Self::random(rng, depth - 1))),
+                2 => ShapeKind::FixedSizeList(
+                    rng.below(3) as usize + 1,
+                    Box::new(Self::random(rng, depth - 1)),
+                ),
+                _ => ShapeKind::Struct(
+                    (0..rng.below(3) + 1)
+                        .map(|_| Self::random(rng, depth - 1))
+                        .collect(),
+                ),
+            };
+            Self { is_nullable, kind }
+        }
+
+        fn field(&self, name: &str) -> Field {
+            let data_type = match &self.kind {
+                ShapeKind::Leaf => ArrowDataType::Int32,
+                ShapeKind::List(child) => ArrowDataType::List(Box::new(child.field("item"))),
+                ShapeKind::FixedSizeList(width, child) => {
+                    ArrowDataType::FixedSizeList(Box::new(child.field("item")), *width)
+                },
+                ShapeKind::Struct(children) => ArrowDataType::Struct(
+                    children
+                        .iter()
+                        .enumerate()
+                        .map(|(i, child)| child.field(&format!("f{i}")))
+                        .collect(),
+                ),
+            };
+            Field::new(name, data_type, self.is_nullable)
+        }
+
+        fn num_leaves(&self) -> usize {
+            match &self.kind {
+                ShapeKind::Leaf => 1,
+                ShapeKind::List(child) | ShapeKind::FixedSizeList(_, child) => child.num_leaves(),
+                ShapeKind::Struct(children) => children.iter().map(Self::num_leaves).sum(),
+            }
+        }
+
+        /// Returns a random array of this shape. Null entries may have children, including
+        /// non-empty lists.
+        fn array(&self, rng: &mut Rng, len: usize) -> Box<dyn Array> {
+            let data_type = self.field("").data_type;
+            let validity = self
+                .is_nullable
+                .then(|| (0..len).map(|_| rng.below(4) != 0).collect::<Bitmap>());
+            match &self.kind {
+                ShapeKind::Leaf => {
+                    let values = (0..len as i32).collect::<Vec<_>>();
+                    PrimitiveArray::<i32>::new(data_type, values.into(), validity).boxed()
+                },
+                ShapeKind::List(child) => {
+                    let lengths = (0..len).map(|_| rng.below(4) as usize).collect::<Vec<_>>();
+                    let values = child.array(rng, lengths.iter().sum());
+                    let offsets = Offsets::<i32>::try_from_lengths(lengths.into_iter()).unwrap();
+                    ListArray::new(data_type, offsets.into(), values, validity).boxed()
+                },
+                ShapeKind::FixedSizeList(width, child) => {
+                    let values = child.array(rng, len * width);
+                    FixedSizeListArray::new(data_type, values, validity).boxed()
+                },
+                ShapeKind::Struct(children) => {
+                    let values = children.iter().map(|child| child.array(rng, len)).collect();
+                    StructArray::new(data_type, values, validity).boxed()
+                },
+            }
+        }
+
+        /// Returns smaller shapes: without nullability, with a node replaced by one of its
+        /// children, a narrower fixed-size list or a struct with fewer fields.
+        fn shrink(&self) -> Vec<Self> {
+            let with_kind = |kind| Self {
+                is_nullable: self.is_nullable,
+                kind,
+            };
+            let mut shapes = vec![];
+            if self.is_nullable {
+                shapes.push(with_kind(self.kind.clone()).non_nullable());
+            }
+            match &self.kind {
+                ShapeKind::Leaf => {},
+                ShapeKind::List(child) => {
+                    shapes.push(child.as_ref().clone());
+                    shapes.extend(
+                        child
+                            .shrink()
+                            .into_iter()
+                            .map(|child| with_kind(ShapeKind::List(Box::new(child)))),
+                    );
+                },
+                ShapeKind::FixedSizeList(width, child) => {
+                    shapes.push(child.as_ref().clone());
+                    if *width > 1 {
+                        shapes.push(with_kind(ShapeKind::FixedSizeList(
+                            width - 1,
+                            child.clone(),
+                        )));
+                    }
+                    shapes.extend(
+                        child.shrink().into_iter().map(|child| {
+                            with_kind(ShapeKind::FixedSizeList(*width, Box::new(child)))
+                        }),
+                    );
+                },
+                ShapeKind::Struct(children) => {
+                    shapes.extend(children.iter().cloned());
+                    for i in 0..children.len() {
+                        if children.len() > 1 {
+                            let mut fewer = children.clone();
+                            fewer.remove(i);
+                            shapes.push(with_kind(ShapeKind::Struct(fewer)));
+                        }
+                        for child in children[i].shrink() {
+                            let mut children = children.clone();
+                            children[i] = child;
+                            shapes.push(with_kind(ShapeKind::Struct(children)));
+                        }
+                    }
+                },
+            }
+            shapes
+        }
+
+        fn non_nullable(mut self) -> Self {
+            self.is_nullable = false;
+            self
+        }
+
+        /// Appends the `(rep, def)` levels of entry `index` of `array` to `leaves`, one per
+        /// leaf. This follows the Dremel definition entry by entry, independently of
+        /// [`Nested`].
+        fn oracle(
+            &self,
+            array: &dyn Array,
+            index: usize,
+            rep: u32,
+            depth: u32,
+            def: u32,
+            leaves: &mut [(Vec<u32>, Vec<u32>)],
+        ) {
+            let push = |leaves: &mut [(Vec<u32>, Vec<u32>)], def| {
+                for (reps, defs) in leaves.iter_mut() {
+                    reps.push(rep);
+                    defs.push(def);
+                }
+            };
+            if self.is_nullable && array.is_null(index) {
+                return push(leaves, def);
+            }
+            let def = def + self.is_nullable as u32;
+            let (child, values, range) = match &self.kind {
+                ShapeKind::Leaf => return push(leaves, def),
+                ShapeKind::List(child) => {
+                    let array = array.as_any().downcast_ref::<ListArray<i32>>().unwrap();
+                    let (start, end) = array.offsets().start_end(index);
+                    (child, array.values(), start..end)
+                },
+                ShapeKind::FixedSizeList(width, child) => {
+                    let array = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+                    (child, array.values(), index * width..(index + 1) * width)
+                },
+                ShapeKind::Struct(children) => {
+                    let array = array.as_any().downcast_ref::<StructArray>().unwrap();
+                    let mut leaves = leaves;
+                    for (child, values) in children.iter().zip(array.values()) {
+                        let (head, tail) = leaves.split_at_mut(child.num_leaves());
+                        child.oracle(values.as_ref(), index, rep, depth, def, head);
+                        leaves = tail;
+                    }
+                    return;
+                },
+            };
+            if range.is_empty() {
+                return push(leaves, def);
+            }
+            for (i, index) in range.enumerate() {
+                let rep = if i == 0 { rep } else { depth + 1 };
+                child.oracle(values.as_ref(), index, rep, depth + 1, def + 1, leaves);
+            }
+        }
+    }
+
+    /// A randomized level test: `len` rows of a random array of `shape`, generated with `seed`
+    /// and sliced at `offset`.
+    #[derive(Debug, Clone)]
+    struct Case {
+        shape: Shape,
+        seed: u64,
+        len: usize,
+        offset: usize,
+    }
+
+    impl Case {
+        /// Compares the levels of the writer with the ones of [`Shape::oracle`].
+        fn check(&self) -> Result<(), String> {
+            let array = self
+                .shape
+                .array(&mut Rng(self.seed), self.offset + self.len)
+                .sliced(self.offset, self.len);
+            let field = self.shape.field("c");
+
+            let mut expected = vec![(vec![], vec![]); self.shape.num_leaves()];
+            for index in 0..array.len() {
+                self.shape
+                    .oracle(array.as_ref(), index, 0, 0, 0, &mut expected);
+            }
+
+            let actual =
+                std::panic::catch_unwind(AssertUnwindSafe(|| levels(array.as_ref(), &field)))
+                    .map_err(|_| "computing the levels panicked".to_string())?;
+            if actual != expected {
+                return Err(format!("expected {expected:?}, got {actual:?}"));
+            }
+            Ok(())
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+            let mut cases = vec![];
+            let with = |shape, len, offset| Self {
+                shape,
+                seed: self.seed,
+                len,
+                offset,
+            };
+            if self.len > 0 {
+                cases.push(with(self.shape.clone(), self.len / 2, self.offset));
+                cases.push(with(self.shape.clone(), self.len - 1, self.offset));
+            }
+            if self.offset > 0 {
+                cases.push(with(self.shape.clone(), self.len, 0));
+            }
+            cases.extend(
+                self.shape
+                    .shrink()
+                    .into_iter()
+                    .map(|shape| with(shape, self.len, self.offset)),
+            );
+            cases
+        }
+
+        /// Returns a minimal failing case, shrinking greedily until no smaller case fails.
+        fn minimize(mut self) -> Self {
+            'outer: loop {
+                for case in self.shrink() {
+                    if case.check().is_err() {
+                        self = case;
+                        continue 'outer;
+                    }
+                }
+                return self;
+            }
+        }
+    }
+
+    #[test]
+    fn random_levels_match_oracle() {
+        let mut rng = Rng(0x2545F4914F6CDD1D);
+        for _ in 0..500 {
+            let case = Case {
+                shape: Shape::random(&mut rng, 4),
+                seed: rng.below(u64::MAX) | 1,
+                len: rng.below(20) as usize,
+                offset: rng.below(5) as usize,
+            };
+            if case.check().is_err() {
+                let case = case.minimize();
+                panic!("{:#?}: {}", case, case.check().unwrap_err());
+            }
+        }
+    }
+
+    #[test]
+    fn oracle() {
+        // [[1, None], None, [], [3]]
+        let shape = Shape {
+            is_nullable: true,
+            kind: ShapeKind::List(Box::new(Shape {
+                is_nullable: true,
+                kind: ShapeKind::Leaf,
+            })),
+        };
+        let array = ListArray::<i32>::new(
+            shape.field("").data_type,
+            vec![0, 2, 2, 2, 3].try_into().unwrap(),
+            Int32Array::from([Some(1), None, Some(3)]).boxed(),
+            Some(Bitmap::from([true, false, true, true])),
+        );
+        let mut leaves = vec![(vec![], vec![])];
+        for index in 0..array.len() {
+            shape.oracle(&array, index, 0, 0, 0, &mut leaves);
+        }
+        assert_eq!(leaves, vec![(vec![0, 1, 0, 0, 0], vec![3, 2, 0, 1, 3])]);
+        assert_eq!(levels(&array, &shape.field("l")), leaves);
+    }
+}
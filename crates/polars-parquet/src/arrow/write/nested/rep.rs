@@ -1,7 +1,8 @@
+use arrow::offset::{Offset, OffsetsBuffer};
 use polars_utils::slice::GetSaferUnchecked;
 
 use super::super::pages::Nested;
-use super::to_length;
+use super::{levels_capacity, to_length};
 
 trait DebugIter: Iterator<Item = usize> + std::fmt::Debug {}
 
@@ -12,7 +13,7 @@ fn iter<'a>(nested: &'a [Nested]) -> Vec<Box<dyn DebugIter + 'a>> {
         .iter()
         .filter_map(|nested| match nested {
             Nested::Primitive(_, _, _) => None,
-            Nested::List(nested) => {
+            Nested::List(nested) | Nested::Map(nested, _) => {
                 Some(Box::new(to_length(&nested.offsets)) as Box<dyn DebugIter>)
             },
             Nested::LargeList(nested) => {
@@ -27,21 +28,87 @@ fn iter<'a>(nested: &'a [Nested]) -> Vec<Box<dyn DebugIter + 'a>> {
 }
 
 /// return number values of the nested
+///
+/// The count saturates at `usize::MAX`, which no column can actually hold.
 pub fn num_values(nested: &[Nested]) -> usize {
     let pr = match nested.last().unwrap() {
         Nested::Primitive(_, _, len) => *len,
         _ => unreachable!(),
     };
 
-    iter(nested)
-        .into_iter()
-        .map(|lengths| {
-            lengths
-                .map(|length| if length == 0 { 1 } else { 0 })
-                .sum::<usize>()
+    // every empty list contributes one value
+    nested
+        .iter()
+        .map(|nested| match nested {
+            Nested::Primitive(_, _, _) | Nested::Struct(_, _, _) => 0,
+            Nested::List(nested) | Nested::Map(nested, _) => num_empty(&nested.offsets),
+            Nested::LargeList(nested) => num_empty(&nested.offsets),
+            Nested::FixedSizeList { width, len, .. } => {
+                if *width == 0 {
+                    *len
+                } else {
+                    0
+                }
+            },
         })
-        .sum::<usize>()
-        + pr
+        .fold(pr, usize::saturating_add)
+}
+
+pub(super) fn num_empty<O: Offset>(offsets: &OffsetsBuffer<O>) -> usize {
+    to_length(offsets).filter(|length| *length == 0).count()
+}
+
+/// Returns the repetition levels of `nested`.
+///
+/// A dense `FixedSizeList` of primitives (e.g. an embedding column) has the trivial levels
+/// `[0, 1, 1, ...]` per entry, which are emitted directly instead of through [`RepLevelsIter`].
+pub fn rep_levels(nested: &[Nested]) -> Vec<u32> {
+    rep_levels_with_value_count(nested, num_values(nested))
+}
+
+/// [`rep_levels`] of `nested` with `value_count` levels, as returned by [`num_values`].
+pub(super) fn rep_levels_with_value_count(nested: &[Nested], value_count: usize) -> Vec<u32> {
+    if let Some((width, len)) = fixed_size_list_of_primitive(nested) {
+        let mut levels = Vec::with_capacity(levels_capacity(width.saturating_mul(len)));
+        for _ in 0..len {
+            levels.push(0);
+            levels.extend(std::iter::repeat(1).take(width - 1));
+        }
+        return levels;
+    }
+    RepLevelsIter::with_value_count(nested, value_count).collect()
+}
+
+/// Returns an iterator of the levels of [`rep_levels_with_value_count`], without collecting
+/// them.
+pub(super) fn rep_levels_iter<'a>(
+    nested: &'a [Nested],
+    value_count: usize,
+) -> Box<dyn Iterator<Item = u32> + 'a> {
+    if let Some((width, len)) = fixed_size_list_of_primitive(nested) {
+        return Box::new(
+            (0..len)
+                .flat_map(move |_| std::iter::once(0).chain(std::iter::repeat(1).take(width - 1))),
+        );
+    }
+    Box::new(RepLevelsIter::with_value_count(nested, value_count))
+}
+
+/// Returns the `(width, len)` of the `FixedSizeList` of `nested` if it is its only repeated
+/// level, it is not empty and its child is the primitive leaf.
+///
+/// The nulls of the list and of the leaf are irrelevant here: a null entry still has `width`
+/// values, and repetition levels do not depend on definition.
+fn fixed_size_list_of_primitive(nested: &[Nested]) -> Option<(usize, usize)> {
+    let [parents @ .., Nested::FixedSizeList { width, len, .. }, Nested::Primitive(_, _, _)] =
+        nested
+    else {
+        return None;
+    };
+    let only_structs = parents
+        .iter()
+        .all(|nested| matches!(nested, Nested::Struct(_, _, _)));
+    (only_structs && *width > 0).then_some((*width, *len))
 }
 
 /// Iterator adapter of parquet / dremel repetition levels
@@ -68,7 +135,13 @@ pub struct RepLevelsIter<'a> {
 
 impl<'a> RepLevelsIter<'a> {
     pub fn new(nested: &'a [Nested]) -> Self {
-        let remaining_values = num_values(nested);
+        Self::with_value_count(nested, num_values(nested))
+    }
+
+    /// Returns the iterator of the `value_count` levels of `nested`, as returned by
+    /// [`num_values`].
+    pub(super) fn with_value_count(nested: &'a [Nested], value_count: usize) -> Self {
+        let remaining_values = value_count;
 
         let iter = iter(nested);
         let remaining = vec![0; iter.len()];
@@ -136,7 +209,7 @@ impl<'a> Iterator for RepLevelsIter<'a> {
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         let length = self.remaining_values;
-        (length, Some(length))
+        (levels_capacity(length), Some(length))
     }
 }
 
@@ -150,6 +223,7 @@ mod tests {
         assert_eq!(iter.size_hint().0, expected.len());
         assert_eq!(iter.by_ref().collect::<Vec<_>>(), expected);
         assert_eq!(iter.size_hint().0, 0);
+        assert_eq!(super::super::compute_levels(&nested).unwrap().0, expected);
     }
 
     #[test]
@@ -209,6 +283,30 @@ mod tests {
         test(nested, expected)
     }
 
+    #[test]
+    fn l2_record_boundaries() {
+        let nested = vec![
+            Nested::List(ListNested {
+                is_optional: false,
+                offsets: vec![0, 2, 2, 4].try_into().unwrap(),
+                validity: None,
+            }),
+            Nested::List(ListNested {
+                is_optional: false,
+                offsets: vec![0, 3, 7, 8, 10].try_into().unwrap(),
+                validity: None,
+            }),
+            Nested::Primitive(None, false, 10),
+        ];
+
+        assert_eq!(super::super::leaf_repetition_depth(&nested), 2);
+        let boundary = super::super::leaf_record_boundary_rep_level(&nested);
+        assert_eq!(boundary, 0);
+        // one boundary per record, including the empty one
+        let reps = rep_levels(&nested);
+        assert_eq!(reps.iter().filter(|rep| **rep == boundary).count(), 3);
+    }
+
     #[test]
     fn list_of_struct() {
         /*
@@ -373,4 +471,146 @@ mod tests {
 
         test(nested, expected)
     }
+
+    #[test]
+    fn struct_optional_list_required() {
+        /*
+        [
+            {"l": [1, 2]},
+            None,
+            {"l": [3, 4, 5]},
+            {"l": []},
+        ]
+        */
+        let nested = vec![
+            Nested::Struct(Some([true, false, true, true].into()), true, 4),
+            Nested::List(ListNested {
+                is_optional: false,
+                offsets: vec![0, 2, 2, 5, 5].try_into().unwrap(),
+                validity: None,
+            }),
+            Nested::Primitive(None, false, 5),
+        ];
+        let expected = vec![0, 1, 0, 0, 1, 1, 0];
+
+        test(nested, expected)
+    }
+
+    #[test]
+    fn l1_sliced() {
+        // the lengths are read from the borrowed offsets, so a sliced buffer whose first
+        // offset is not 0 is equivalent to its rebased counterpart.
+        let mut offsets: OffsetsBuffer<i32> = vec![0, 2, 2, 5, 6].try_into().unwrap();
+        offsets.slice(1, 3);
+        let sliced = vec![
+            Nested::List(ListNested {
+                is_optional: false,
+                offsets,
+                validity: None,
+            }),
+            Nested::Primitive(None, false, 3),
+        ];
+        let rebased = vec![
+            Nested::List(ListNested {
+                is_optional: false,
+                offsets: vec![0, 0, 3].try_into().unwrap(),
+                validity: None,
+            }),
+            Nested::Primitive(None, false, 3),
+        ];
+        let expected = vec![0, 0, 1, 1];
+
+        test(sliced, expected.clone());
+        test(rebased, expected)
+    }
+
+    #[test]
+    fn l1_empty() {
+        // [[], []]
+        let nested = vec![
+            Nested::List(ListNested {
+                is_optional: false,
+                offsets: vec![0, 0, 0].try_into().unwrap(),
+                validity: None,
+            }),
+            Nested::Primitive(None, false, 0),
+        ];
+        assert_eq!(num_values(&nested), 2);
+
+        test(nested, vec![0, 0])
+    }
+
+    #[test]
+    fn l1_leading_null() {
+        // [None, [1, 2], [3]]
+        let nested = vec![
+            Nested::List(ListNested {
+                is_optional: true,
+                offsets: vec![0, 0, 2, 3].try_into().unwrap(),
+                validity: Some([false, true, true].into()),
+            }),
+            Nested::Primitive(None, false, 3),
+        ];
+        // the null only contributes a level, without desyncing the entries that follow it
+        let (_, def) = super::super::compute_levels(&nested).unwrap();
+        assert_eq!(def, vec![0, 2, 2, 2]);
+
+        test(nested, vec![0, 0, 1, 0])
+    }
+
+    #[test]
+    fn dense_fixed_size_list() {
+        // an embedding column, i.e. `FixedSizeList<Float32, 768>`
+        let fsl = |width, len| Nested::FixedSizeList {
+            validity: None,
+            is_optional: true,
+            width,
+            len,
+        };
+        let cases = [
+            vec![fsl(768, 100), Nested::Primitive(None, false, 76800)],
+            vec![
+                Nested::Struct(None, true, 3),
+                fsl(2, 3),
+                Nested::Primitive(None, true, 6),
+            ],
+            vec![fsl(1, 4), Nested::Primitive(None, true, 4)],
+        ];
+        for nested in cases {
+            assert!(fixed_size_list_of_primitive(&nested).is_some());
+            let expected = RepLevelsIter::new(&nested).collect::<Vec<_>>();
+            assert_eq!(rep_levels(&nested), expected);
+        }
+        assert_eq!(
+            rep_levels(&[fsl(3, 2), Nested::Primitive(None, false, 6)]),
+            vec![0, 1, 1, 0, 1, 1]
+        );
+
+        // not the only repeated level
+        let nested = vec![
+            Nested::List(ListNested {
+                is_optional: false,
+                offsets: vec![0, 1, 1, 2].try_into().unwrap(),
+                validity: None,
+            }),
+            fsl(2, 2),
+            Nested::Primitive(None, false, 4),
+        ];
+        assert!(fixed_size_list_of_primitive(&nested).is_none());
+        assert_eq!(rep_levels(&nested), vec![0, 2, 0, 0, 2]);
+    }
+
+    #[test]
+    fn num_values_saturates() {
+        let nested = vec![
+            Nested::FixedSizeList {
+                validity: None,
+                is_optional: false,
+                width: 0,
+                len: usize::MAX,
+            },
+            Nested::Primitive(None, false, 1),
+        ];
+        assert_eq!(num_values(&nested), usize::MAX);
+    }
 }
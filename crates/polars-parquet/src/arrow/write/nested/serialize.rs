@@ -0,0 +1,198 @@
+use arrow::bitmap::{Bitmap, MutableBitmap};
+use arrow::offset::{Offset, OffsetsBuffer};
+use polars_error::{polars_bail, polars_ensure, polars_err, PolarsResult};
+
+use super::super::pages::{ListNested, Nested};
+
+const PRIMITIVE: u8 = 0;
+const LIST: u8 = 1;
+const LARGE_LIST: u8 = 2;
+const FIXED_SIZE_LIST: u8 = 3;
+const STRUCT: u8 = 4;
+const MAP: u8 = 5;
+
+/// Serializes `nested` into a compact binary format, so that its levels can be computed
+/// by another process.
+///
+/// Every [`Nested`] is written as a tag and its `is_optional` flag followed by its lengths,
+/// offsets and validity, and for a map by its key and value. Integers are written as
+/// little-endian `u64` and validities as a presence byte followed by their packed bits.
+/// Use [`deserialize_nested`] to read it back.
+pub fn serialize_nested(nested: &[Nested]) -> Vec<u8> {
+    let mut buffer = vec![];
+    write_usize(&mut buffer, nested.len());
+    for nested in nested {
+        write_nested(&mut buffer, nested);
+    }
+    buffer
+}
+
+/// Deserializes [`Nested`] written by [`serialize_nested`].
+/// # Errors
+/// Errors if `bytes` is truncated or is not a valid serialization.
+pub fn deserialize_nested(bytes: &[u8]) -> PolarsResult<Vec<Nested>> {
+    let mut reader = Reader { bytes };
+    let length = reader.usize()?;
+    let nested = (0..length)
+        .map(|_| reader.nested())
+        .collect::<PolarsResult<Vec<_>>>()?;
+    polars_ensure!(
+        reader.bytes.is_empty(),
+        ComputeError: "serialized nested has {} trailing bytes", reader.bytes.len()
+    );
+    Ok(nested)
+}
+
+/// Writes `nested`. The key and value of a map are written after its list of entries.
+fn write_nested(buffer: &mut Vec<u8>, nested: &Nested) {
+    match nested {
+        Nested::Primitive(validity, is_optional, len) => {
+            buffer.extend([PRIMITIVE, *is_optional as u8]);
+            write_usize(buffer, *len);
+            write_validity(buffer, validity.as_ref());
+        },
+        Nested::List(nested) => {
+            buffer.extend([LIST, nested.is_optional as u8]);
+            write_list(buffer, nested);
+        },
+        Nested::LargeList(nested) => {
+            buffer.extend([LARGE_LIST, nested.is_optional as u8]);
+            write_list(buffer, nested);
+        },
+        Nested::Map(nested, entries) => {
+            buffer.extend([MAP, nested.is_optional as u8]);
+            write_list(buffer, nested);
+            write_nested(buffer, &entries.0);
+            write_nested(buffer, &entries.1);
+        },
+        Nested::FixedSizeList {
+            validity,
+            is_optional,
+            width,
+            len,
+        } => {
+            buffer.extend([FIXED_SIZE_LIST, *is_optional as u8]);
+            write_usize(buffer, *width);
+            write_usize(buffer, *len);
+            write_validity(buffer, validity.as_ref());
+        },
+        Nested::Struct(validity, is_optional, len) => {
+            buffer.extend([STRUCT, *is_optional as u8]);
+            write_usize(buffer, *len);
+            write_validity(buffer, validity.as_ref());
+        },
+    }
+}
+
+fn write_usize(buffer: &mut Vec<u8>, value: usize) {
+    buffer.extend_from_slice(&(value as u64).to_le_bytes())
+}
+
+fn write_validity(buffer: &mut Vec<u8>, validity: Option<&Bitmap>) {
+    match validity {
+        None => buffer.push(0),
+        Some(validity) => {
+            buffer.push(1);
+            let packed = validity.iter().collect::<MutableBitmap>();
+            buffer.extend_from_slice(packed.as_slice());
+        },
+    }
+}
+
+fn write_list<O: Offset>(buffer: &mut Vec<u8>, nested: &ListNested<O>) {
+    write_usize(buffer, nested.offsets.len_proxy());
+    nested
+        .offsets
+        .iter()
+        .for_each(|offset| write_usize(buffer, offset.to_usize()));
+    write_validity(buffer, nested.validity.as_ref());
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, length: usize) -> PolarsResult<&'a [u8]> {
+        polars_ensure!(
+            length <= self.bytes.len(),
+            ComputeError: "serialized nested is truncated"
+        );
+        let (taken, remaining) = self.bytes.split_at(length);
+        self.bytes = remaining;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> PolarsResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn usize(&mut self) -> PolarsResult<usize> {
+        let value = u64::from_le_bytes(self.take(8)?.try_into().unwrap());
+        usize::try_from(value).map_err(|_| polars_err!(ComputeError: "invalid serialized length"))
+    }
+
+    fn validity(&mut self, length: usize) -> PolarsResult<Option<Bitmap>> {
+        if self.u8()? == 0 {
+            return Ok(None);
+        }
+        let bytes = self.take(length.div_ceil(8))?;
+        Bitmap::try_new(bytes.to_vec(), length).map(Some)
+    }
+
+    fn nested(&mut self) -> PolarsResult<Nested> {
+        let tag = self.u8()?;
+        let is_optional = self.u8()? != 0;
+        Ok(match tag {
+            PRIMITIVE => {
+                let len = self.usize()?;
+                Nested::Primitive(self.validity(len)?, is_optional, len)
+            },
+            LIST => Nested::List(self.list(is_optional)?),
+            LARGE_LIST => Nested::LargeList(self.list(is_optional)?),
+            MAP => {
+                let list = self.list(is_optional)?;
+                let key = self.nested()?;
+                let value = self.nested()?;
+                Nested::Map(list, Box::new((key, value)))
+            },
+            FIXED_SIZE_LIST => {
+                let width = self.usize()?;
+                let len = self.usize()?;
+                Nested::FixedSizeList {
+                    validity: self.validity(len)?,
+                    is_optional,
+                    width,
+                    len,
+                }
+            },
+            STRUCT => {
+                let len = self.usize()?;
+                Nested::Struct(self.validity(len)?, is_optional, len)
+            },
+            other => polars_bail!(ComputeError: "invalid serialized nested tag {}", other),
+        })
+    }
+
+    fn list<O: Offset>(&mut self, is_optional: bool) -> PolarsResult<ListNested<O>> {
+        let length = self.usize()?;
+        let num_offsets = length
+            .checked_add(1)
+            .ok_or_else(|| polars_err!(ComputeError: "invalid serialized length"))?;
+        polars_ensure!(
+            num_offsets.checked_mul(8).map_or(false, |n| n <= self.bytes.len()),
+            ComputeError: "serialized nested is truncated"
+        );
+        let offsets = (0..num_offsets)
+            .map(|_| {
+                let offset = self.usize()?;
+                O::from_usize(offset).ok_or_else(
+                    || polars_err!(ComputeError: "invalid serialized offset {}", offset),
+                )
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let offsets: OffsetsBuffer<O> = offsets.try_into()?;
+        let validity = self.validity(length)?;
+        Ok(ListNested::new(offsets, validity, is_optional))
+    }
+}
@@ -1,13 +1,23 @@
+use std::cmp::Ordering;
 use std::fmt::Debug;
 
 use arrow::array::{Array, FixedSizeListArray, ListArray, MapArray, StructArray};
 use arrow::bitmap::Bitmap;
-use arrow::datatypes::PhysicalType;
+use arrow::compute::concatenate::concatenate;
+use arrow::compute::utils::combine_validities_and;
+use arrow::datatypes::{ArrowDataType, ArrowSchema, Field, PhysicalType};
 use arrow::offset::{Offset, OffsetsBuffer};
-use polars_error::{polars_bail, PolarsResult};
+use polars_error::{polars_bail, polars_ensure, PolarsResult};
 
-use super::{array_to_pages, Encoding, WriteOptions};
+use super::dictionary::value_comparator;
+use super::nested::{
+    compute_levels, validate_levels, validate_nested, validate_required, validity_of,
+};
+use super::{
+    array_to_pages, slice_nested_leaf, to_parquet_type, with_bloom_filter, Encoding, WriteOptions,
+};
 use crate::arrow::read::schema::is_nullable;
+use crate::parquet::metadata::SortingColumn;
 use crate::parquet::page::Page;
 use crate::parquet::schema::types::{ParquetType, PrimitiveType as ParquetPrimitiveType};
 use crate::write::DynIter;
@@ -41,6 +51,10 @@ pub enum Nested {
     List(ListNested<i32>),
     /// a list
     LargeList(ListNested<i64>),
+    /// a map, whose levels are those of a list of key-value entries
+    /// - the list of entries
+    /// - the key and value of the entries
+    Map(ListNested<i32>, Box<(Nested, Nested)>),
     /// Width
     FixedSizeList {
         validity: Option<Bitmap>,
@@ -60,165 +74,565 @@ impl Nested {
     pub fn len(&self) -> usize {
         match self {
             Nested::Primitive(_, _, length) => *length,
-            Nested::List(nested) => nested.offsets.len_proxy(),
+            Nested::List(nested) | Nested::Map(nested, _) => nested.offsets.len_proxy(),
             Nested::LargeList(nested) => nested.offsets.len_proxy(),
             Nested::Struct(_, _, len) => *len,
             Nested::FixedSizeList { len, .. } => *len,
         }
     }
+
+    /// Returns whether the element has no rows
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Returns the fields of the group `type_`, erroring with the parquet field name if it is a
+/// primitive. `context` names what `type_` is written for.
+fn expect_group<'a>(type_: &'a ParquetType, context: &str) -> PolarsResult<&'a [ParquetType]> {
+    match type_ {
+        ParquetType::GroupType { fields, .. } => Ok(fields),
+        ParquetType::PrimitiveType(primitive) => polars_bail!(InvalidOperation:
+            "the parquet field \"{}\" of {} must be a group, found a primitive of type {:?}",
+            primitive.field_info.name, context, primitive.physical_type
+        ),
+    }
+}
+
+/// Returns the element of the list-like group `type_`, the single field of its repeated group.
+fn repeated_element<'a>(type_: &'a ParquetType, context: &str) -> PolarsResult<&'a ParquetType> {
+    let fields = expect_group(type_, context)?;
+    let Some(repeated) = fields.first() else {
+        polars_bail!(InvalidOperation:
+            "the parquet group \"{}\" of {} has no fields", type_.name(), context
+        )
+    };
+    let Some(element) = expect_group(repeated, context)?.first() else {
+        polars_bail!(InvalidOperation:
+            "the parquet group \"{}\" of {} has no fields", repeated.name(), context
+        )
+    };
+    Ok(element)
+}
+
+/// Where the optionality of the fields walked by [`to_nested_recursive`] comes from.
+#[derive(Debug, Clone, Copy)]
+enum Optionality<'a> {
+    /// The repetition of the matching field of a parquet type
+    Parquet(&'a ParquetType),
+    /// Whether the array has a validity
+    Validity,
+}
+
+impl<'a> Optionality<'a> {
+    /// Returns whether `array` is optional.
+    fn is_optional(self, array: &dyn Array) -> bool {
+        match self {
+            Self::Parquet(type_) => is_nullable(type_.get_field_info()),
+            // a `NullArray` has no validity, but all of its values are null
+            Self::Validity => {
+                array.validity().is_some() || matches!(array.data_type(), ArrowDataType::Null)
+            },
+        }
+    }
+
+    /// Returns the optionality of each field of the struct `array`.
+    fn fields(self, array: &StructArray) -> PolarsResult<Vec<Self>> {
+        let type_ = match self {
+            Self::Parquet(type_) => type_,
+            Self::Validity => return Ok(vec![Self::Validity; array.values().len()]),
+        };
+        let fields = expect_group(type_, "a struct array")?;
+        polars_ensure!(
+            fields.len() == array.values().len(),
+            InvalidOperation:
+            "the parquet group \"{}\" has {} fields, but the struct array has {} fields ({})",
+            type_.name(), fields.len(), array.values().len(),
+            StructArray::get_fields(array.data_type())
+                .iter()
+                .map(|f| f.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        Ok(fields.iter().map(Self::Parquet).collect())
+    }
+
+    /// Returns the optionality of the values of a list-like array. `context` names the array.
+    fn element(self, context: &str) -> PolarsResult<Self> {
+        match self {
+            Self::Parquet(type_) => repeated_element(type_, context).map(Self::Parquet),
+            Self::Validity => Ok(Self::Validity),
+        }
+    }
 }
 
 /// Constructs the necessary `Vec<Vec<Nested>>` to write the rep and def levels of `array` to parquet
+///
+/// The leaf of each `Vec<Nested>` spans all values of its parent lists, including those outside
+/// of a sliced list's offsets. Slice it with [`slice_parquet_array`] before computing its levels.
+///
+/// [`slice_parquet_array`]: super::slice_parquet_array
 pub fn to_nested(array: &dyn Array, type_: &ParquetType) -> PolarsResult<Vec<Vec<Nested>>> {
     let mut nested = vec![];
 
-    to_nested_recursive(array, type_, &mut nested, vec![])?;
+    to_nested_recursive(array, Optionality::Parquet(type_), &mut nested, vec![])?;
+    Ok(nested)
+}
+
+/// Returns the `Vec<Vec<Nested>>` of `array` like [`to_nested`], inferring the nesting from
+/// `array` alone instead of from a parquet type.
+///
+/// An array is optional iff it has a validity bitmap, and a [`NullArray`](arrow::array::NullArray)
+/// is always optional. The levels therefore describe the arrays and not a schema: an
+/// optional field without nulls in `array` is required here. The nulls of structs and lists
+/// are first normalized as when `array` is written.
+///
+/// # Errors
+/// Errors if the array contains a physical type that cannot be written to parquet, or a map
+/// with null keys.
+pub fn nested_from_arrow(array: &dyn Array) -> PolarsResult<Vec<Vec<Nested>>> {
+    let array = normalize_struct_nulls(array, None)?;
+    let mut nested = vec![];
+
+    to_nested_recursive(array.as_ref(), Optionality::Validity, &mut nested, vec![])?;
     Ok(nested)
 }
 
+/// Returns the [`Nested`] of `array` itself, without those of its children.
+fn to_nested_node(array: &dyn Array, optionality: Optionality) -> PolarsResult<Nested> {
+    let is_optional = optionality.is_optional(array);
+
+    use PhysicalType::*;
+    Ok(match array.data_type().to_physical_type() {
+        Struct => Nested::Struct(array.validity().cloned(), is_optional, array.len()),
+        FixedSizeList => {
+            let array = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+            Nested::FixedSizeList {
+                validity: array.validity().cloned(),
+                len: array.len(),
+                width: array.size(),
+                is_optional,
+            }
+        },
+        List => {
+            let array = array.as_any().downcast_ref::<ListArray<i32>>().unwrap();
+            Nested::List(ListNested::new(
+                array.offsets().clone(),
+                array.validity().cloned(),
+                is_optional,
+            ))
+        },
+        LargeList => {
+            let array = array.as_any().downcast_ref::<ListArray<i64>>().unwrap();
+            Nested::LargeList(ListNested::new(
+                array.offsets().clone(),
+                array.validity().cloned(),
+                is_optional,
+            ))
+        },
+        Map => {
+            let array = array.as_any().downcast_ref::<MapArray>().unwrap();
+            let entries = map_entries(array)?;
+            let optionality = optionality.element("a map array")?;
+            let fields = optionality.fields(entries)?;
+            let key = to_nested_node(entries.values()[0].as_ref(), fields[0])?;
+            let value = to_nested_node(entries.values()[1].as_ref(), fields[1])?;
+            check_map_keys(&key, optionality)?;
+
+            Nested::Map(
+                ListNested::new(
+                    array.offsets().clone(),
+                    array.validity().cloned(),
+                    is_optional,
+                ),
+                Box::new((key, value)),
+            )
+        },
+        // a `NullArray` has no validity, but all of its values are null
+        Null => Nested::Primitive(
+            Some(Bitmap::new_zeroed(array.len())),
+            is_optional,
+            array.len(),
+        ),
+        Boolean | Primitive(_) | Binary | FixedSizeBinary | LargeBinary | Utf8 | LargeUtf8
+        | Dictionary(_) | BinaryView | Utf8View => {
+            Nested::Primitive(array.validity().cloned(), is_optional, array.len())
+        },
+        other => polars_bail!(InvalidOperation:
+            "Writing {:?} to parquet not yet implemented", other
+        ),
+    })
+}
+
 fn to_nested_recursive(
     array: &dyn Array,
-    type_: &ParquetType,
+    optionality: Optionality,
     nested: &mut Vec<Vec<Nested>>,
     mut parents: Vec<Nested>,
 ) -> PolarsResult<()> {
-    let is_optional = is_nullable(type_.get_field_info());
+    parents.push(to_nested_node(array, optionality)?);
 
     use PhysicalType::*;
     match array.data_type().to_physical_type() {
         Struct => {
             let array = array.as_any().downcast_ref::<StructArray>().unwrap();
-            let fields = if let ParquetType::GroupType { fields, .. } = type_ {
-                fields
-            } else {
-                polars_bail!(InvalidOperation:
-                    "Parquet type must be a group for a struct array".to_string(),
-                )
-            };
-
-            parents.push(Nested::Struct(
-                array.validity().cloned(),
-                is_optional,
-                array.len(),
-            ));
-
-            for (type_, array) in fields.iter().zip(array.values()) {
-                to_nested_recursive(array.as_ref(), type_, nested, parents.clone())?;
+            let fields = optionality.fields(array)?;
+            for (optionality, array) in fields.into_iter().zip(array.values()) {
+                to_nested_recursive(array.as_ref(), optionality, nested, parents.clone())?;
             }
         },
         FixedSizeList => {
             let array = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
-            let type_ = if let ParquetType::GroupType { fields, .. } = type_ {
-                if let ParquetType::GroupType { fields, .. } = &fields[0] {
-                    &fields[0]
-                } else {
-                    polars_bail!(InvalidOperation:
-                        "Parquet type must be a group for a list array".to_string(),
-                    )
-                }
-            } else {
-                polars_bail!(InvalidOperation:
-                    "Parquet type must be a group for a list array".to_string(),
-                )
-            };
-
-            parents.push(Nested::FixedSizeList {
-                validity: array.validity().cloned(),
-                len: array.len(),
-                width: array.size(),
-                is_optional,
-            });
-            to_nested_recursive(array.values().as_ref(), type_, nested, parents)?;
+            let optionality = optionality.element("a list array")?;
+            to_nested_recursive(array.values().as_ref(), optionality, nested, parents)?;
         },
         List => {
             let array = array.as_any().downcast_ref::<ListArray<i32>>().unwrap();
-            let type_ = if let ParquetType::GroupType { fields, .. } = type_ {
-                if let ParquetType::GroupType { fields, .. } = &fields[0] {
-                    &fields[0]
-                } else {
-                    polars_bail!(InvalidOperation:
-                        "Parquet type must be a group for a list array".to_string(),
-                    )
-                }
-            } else {
-                polars_bail!(InvalidOperation:
-                    "Parquet type must be a group for a list array".to_string(),
-                )
-            };
+            let optionality = optionality.element("a list array")?;
+            to_nested_recursive(array.values().as_ref(), optionality, nested, parents)?;
+        },
+        LargeList => {
+            let array = array.as_any().downcast_ref::<ListArray<i64>>().unwrap();
+            let optionality = optionality.element("a list array")?;
+            to_nested_recursive(array.values().as_ref(), optionality, nested, parents)?;
+        },
+        Map => {
+            let array = array.as_any().downcast_ref::<MapArray>().unwrap();
+            let optionality = optionality.element("a map array")?;
+            to_nested_recursive(array.field().as_ref(), optionality, nested, parents)?;
+        },
+        _ => nested.push(parents),
+    }
+    Ok(())
+}
 
-            parents.push(Nested::List(ListNested::new(
-                array.offsets().clone(),
-                array.validity().cloned(),
-                is_optional,
-            )));
-            to_nested_recursive(array.values().as_ref(), type_, nested, parents)?;
+/// Propagates the nulls of structs to their children and empties null list (and map) entries.
+///
+/// A null struct or list contributes a single definition level and its children are never
+/// visited, so any values behind it would otherwise be walked (and written) as if it was
+/// valid.
+///
+/// For the same reason, a fixed-size list with null entries is converted to a large list
+/// whose null entries are empty.
+///
+/// The nulls of a parent are only added to the validity of children whose field is nullable;
+/// required children keep their validity, but their list entries are still emptied.
+pub(super) fn normalize_struct_nulls(
+    array: &dyn Array,
+    parent_validity: Option<&Bitmap>,
+) -> PolarsResult<Box<dyn Array>> {
+    normalize_nulls(array, parent_validity, true)
+}
+
+fn normalize_nulls(
+    array: &dyn Array,
+    parent_validity: Option<&Bitmap>,
+    is_nullable: bool,
+) -> PolarsResult<Box<dyn Array>> {
+    if parent_validity.map_or(true, |v| v.unset_bits() == 0) && is_normalized(array) {
+        return Ok(array.to_boxed());
+    }
+
+    use PhysicalType::*;
+    // the validity of the entries that are written, which nulls the children of this array
+    let entries_validity = combine_validities_and(parent_validity, array.validity());
+    let validity = if is_nullable {
+        entries_validity.clone()
+    } else {
+        array.validity().cloned()
+    };
+    Ok(match array.data_type().to_physical_type() {
+        Struct => {
+            let array = array.as_any().downcast_ref::<StructArray>().unwrap();
+            let values = array
+                .values()
+                .iter()
+                .zip(StructArray::get_fields(array.data_type()))
+                .map(|(a, field)| {
+                    normalize_nulls(a.as_ref(), entries_validity.as_ref(), field.is_nullable)
+                })
+                .collect::<PolarsResult<Vec<_>>>()?;
+            let data_type = normalized_type(array.data_type(), &values);
+            StructArray::new(data_type, values, validity).boxed()
+        },
+        List => {
+            let array = array.as_any().downcast_ref::<ListArray<i32>>().unwrap();
+            let (offsets, values) = empty_null_entries(
+                array.offsets(),
+                array.values().as_ref(),
+                entries_validity.as_ref(),
+            )?;
+            ListArray::new(
+                normalized_type(array.data_type(), std::slice::from_ref(&values)),
+                offsets,
+                values,
+                validity,
+            )
+            .boxed()
         },
         LargeList => {
             let array = array.as_any().downcast_ref::<ListArray<i64>>().unwrap();
-            let type_ = if let ParquetType::GroupType { fields, .. } = type_ {
-                if let ParquetType::GroupType { fields, .. } = &fields[0] {
-                    &fields[0]
-                } else {
-                    polars_bail!(InvalidOperation:
-                        "Parquet type must be a group for a list array".to_string(),
-                    )
-                }
-            } else {
-                polars_bail!(InvalidOperation:
-                    "Parquet type must be a group for a list array".to_string(),
+            let (offsets, values) = empty_null_entries(
+                array.offsets(),
+                array.values().as_ref(),
+                entries_validity.as_ref(),
+            )?;
+            ListArray::new(
+                normalized_type(array.data_type(), std::slice::from_ref(&values)),
+                offsets,
+                values,
+                validity,
+            )
+            .boxed()
+        },
+        Map => {
+            let array = array.as_any().downcast_ref::<MapArray>().unwrap();
+            let (offsets, field) = empty_null_entries(
+                array.offsets(),
+                array.field().as_ref(),
+                entries_validity.as_ref(),
+            )?;
+            MapArray::try_new(
+                normalized_type(array.data_type(), std::slice::from_ref(&field)),
+                offsets,
+                field,
+                validity,
+            )?
+            .boxed()
+        },
+        FixedSizeList => {
+            let array = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+            if entries_validity
+                .as_ref()
+                .map_or(true, |v| v.unset_bits() == 0)
+            {
+                let values = normalize_struct_nulls(array.values().as_ref(), None)?;
+                return Ok(FixedSizeListArray::new(
+                    normalized_type(array.data_type(), std::slice::from_ref(&values)),
+                    values,
+                    validity,
                 )
+                .boxed());
+            }
+
+            // null entries of a fixed-size list still hold `width` values, which can only be
+            // dropped by writing it as a list.
+            let field = match array.data_type().to_logical_type() {
+                ArrowDataType::FixedSizeList(field, _) => field.clone(),
+                _ => unreachable!(),
             };
+            let width = array.size() as i64;
+            let offsets = (0..=array.len() as i64)
+                .map(|i| i * width)
+                .collect::<Vec<_>>();
+            let (offsets, values) = empty_null_entries(
+                &offsets.try_into()?,
+                array.values().as_ref(),
+                entries_validity.as_ref(),
+            )?;
+            ListArray::<i64>::new(
+                ArrowDataType::LargeList(Box::new(Field {
+                    data_type: values.data_type().clone(),
+                    ..*field
+                })),
+                offsets,
+                values,
+                validity,
+            )
+            .boxed()
+        },
+        Null => array.to_boxed(),
+        _ if parent_validity.is_some() && is_nullable => array.with_validity(validity),
+        _ => array.to_boxed(),
+    })
+}
 
-            parents.push(Nested::LargeList(ListNested::new(
-                array.offsets().clone(),
-                array.validity().cloned(),
-                is_optional,
-            )));
-            to_nested_recursive(array.values().as_ref(), type_, nested, parents)?;
+/// Returns whether `array` has no nulls to propagate nor list entries to empty or rebase, so
+/// that normalizing it returns it unchanged.
+fn is_normalized(array: &dyn Array) -> bool {
+    fn is_list_normalized<O: Offset>(offsets: &OffsetsBuffer<O>, values: &dyn Array) -> bool {
+        offsets.first().is_zero()
+            && offsets.last().to_usize() == values.len()
+            && is_normalized(values)
+    }
+
+    use PhysicalType::*;
+    let physical_type = array.data_type().to_physical_type();
+    let is_nested = matches!(
+        physical_type,
+        Struct | List | LargeList | Map | FixedSizeList
+    );
+    if is_nested && array.null_count() > 0 {
+        return false;
+    }
+    match physical_type {
+        Struct => {
+            let array = array.as_any().downcast_ref::<StructArray>().unwrap();
+            array.values().iter().all(|a| is_normalized(a.as_ref()))
+        },
+        List => {
+            let array = array.as_any().downcast_ref::<ListArray<i32>>().unwrap();
+            is_list_normalized(array.offsets(), array.values().as_ref())
+        },
+        LargeList => {
+            let array = array.as_any().downcast_ref::<ListArray<i64>>().unwrap();
+            is_list_normalized(array.offsets(), array.values().as_ref())
         },
         Map => {
             let array = array.as_any().downcast_ref::<MapArray>().unwrap();
-            let type_ = if let ParquetType::GroupType { fields, .. } = type_ {
-                if let ParquetType::GroupType { fields, .. } = &fields[0] {
-                    &fields[0]
-                } else {
-                    polars_bail!(InvalidOperation:
-                        "Parquet type must be a group for a map array".to_string(),
-                    )
-                }
-            } else {
-                polars_bail!(InvalidOperation:
-                    "Parquet type must be a group for a map array".to_string(),
-                )
-            };
+            is_list_normalized(array.offsets(), array.field().as_ref())
+        },
+        FixedSizeList => {
+            let array = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+            is_normalized(array.values().as_ref())
+        },
+        _ => true,
+    }
+}
 
-            parents.push(Nested::List(ListNested::new(
-                array.offsets().clone(),
-                array.validity().cloned(),
-                is_optional,
-            )));
-            to_nested_recursive(array.field().as_ref(), type_, nested, parents)?;
+/// Returns `data_type` with the data types of its normalized `children`, which differ from the
+/// original ones when a fixed-size list was converted to a large list.
+fn normalized_type(data_type: &ArrowDataType, children: &[Box<dyn Array>]) -> ArrowDataType {
+    let with_type = |field: &Field, child: &dyn Array| Field {
+        data_type: child.data_type().clone(),
+        ..field.clone()
+    };
+    let logical_type = data_type.to_logical_type();
+    let normalized = match logical_type {
+        ArrowDataType::Struct(fields) => ArrowDataType::Struct(
+            fields
+                .iter()
+                .zip(children)
+                .map(|(field, child)| with_type(field, child.as_ref()))
+                .collect(),
+        ),
+        ArrowDataType::List(field) => {
+            ArrowDataType::List(Box::new(with_type(field, children[0].as_ref())))
         },
-        _ => {
-            parents.push(Nested::Primitive(
-                array.validity().cloned(),
-                is_optional,
-                array.len(),
-            ));
-            nested.push(parents)
+        ArrowDataType::LargeList(field) => {
+            ArrowDataType::LargeList(Box::new(with_type(field, children[0].as_ref())))
+        },
+        ArrowDataType::FixedSizeList(field, size) => {
+            ArrowDataType::FixedSizeList(Box::new(with_type(field, children[0].as_ref())), *size)
         },
+        ArrowDataType::Map(field, sorted) => {
+            ArrowDataType::Map(Box::new(with_type(field, children[0].as_ref())), *sorted)
+        },
+        _ => unreachable!(),
+    };
+    if &normalized == logical_type {
+        data_type.clone()
+    } else {
+        normalized
+    }
+}
+
+/// Returns `offsets` and `values` where every entry that is null in `validity` is empty.
+///
+/// The returned offsets start at zero and end at the length of the returned values, so that
+/// the values of a sliced list are sliced alongside it.
+fn empty_null_entries<O: Offset>(
+    offsets: &OffsetsBuffer<O>,
+    values: &dyn Array,
+    validity: Option<&Bitmap>,
+) -> PolarsResult<(OffsetsBuffer<O>, Box<dyn Array>)> {
+    let needs_trimming = validity.map_or(false, |validity| {
+        validity
+            .iter()
+            .zip(offsets.lengths())
+            .any(|(is_valid, length)| !is_valid && length > 0)
+    });
+    if !needs_trimming {
+        let first = *offsets.first();
+        let (start, end) = (first.to_usize(), offsets.last().to_usize());
+        if start == 0 && end == values.len() {
+            return Ok((offsets.clone(), normalize_struct_nulls(values, None)?));
+        }
+        let new_offsets = offsets
+            .buffer()
+            .iter()
+            .map(|o| *o - first)
+            .collect::<Vec<_>>();
+        return Ok((
+            // SAFETY: subtracting the first offset keeps the offsets monotonic and non-negative.
+            unsafe { OffsetsBuffer::new_unchecked(new_offsets.into()) },
+            normalize_struct_nulls(values.sliced(start, end - start).as_ref(), None)?,
+        ));
+    }
+    let validity = validity.unwrap();
+
+    let mut new_offsets = Vec::with_capacity(offsets.len());
+    new_offsets.push(O::zero());
+    let mut slices = vec![];
+    let mut length = O::zero();
+    for (i, is_valid) in validity.iter().enumerate() {
+        let (start, end) = offsets.start_end(i);
+        if is_valid && end > start {
+            slices.push(values.sliced(start, end - start));
+            length += O::from_as_usize(end - start);
+        }
+        new_offsets.push(length);
+    }
+    let values = if slices.is_empty() {
+        values.sliced(0, 0)
+    } else {
+        concatenate(&slices.iter().map(|a| a.as_ref()).collect::<Vec<_>>())?
+    };
+
+    Ok((
+        new_offsets.try_into()?,
+        normalize_struct_nulls(values.as_ref(), None)?,
+    ))
+}
+
+/// Returns the entries of `array`, erroring if they are not a struct of a key and a value.
+fn map_entries(array: &MapArray) -> PolarsResult<&StructArray> {
+    let entries = array.field().as_any().downcast_ref::<StructArray>();
+    match entries {
+        Some(entries) if entries.values().len() == 2 => Ok(entries),
+        _ => polars_bail!(InvalidOperation:
+            "The entries of a map must be a struct of a key and a value, found {:?}",
+            array.field().data_type()
+        ),
+    }
+}
+
+/// Checks that `key`, the nesting of the keys of a map whose entries have `optionality`, is
+/// non-null and, when written as a parquet type, required, as the parquet `MAP` logical type
+/// requires.
+fn check_map_keys(key: &Nested, optionality: Optionality) -> PolarsResult<()> {
+    let (is_optional, validity) = validity_of(key);
+    if let Optionality::Parquet(type_) = optionality {
+        polars_ensure!(
+            !is_optional,
+            InvalidOperation: "The keys of a map must be required, found optional field {:?}",
+            expect_group(type_, "a map array")?[0].name()
+        );
     }
+
+    let null_count = validity.map_or(0, |validity| validity.unset_bits());
+    polars_ensure!(
+        null_count == 0,
+        InvalidOperation: "The keys of a map must be non-null, found {} nulls",
+        null_count
+    );
     Ok(())
 }
 
 /// Convert [`Array`] to `Vec<&dyn Array>` leaves in DFS order.
-pub fn to_leaves(array: &dyn Array) -> Vec<&dyn Array> {
+///
+/// # Errors
+/// Errors if the array contains a physical type that cannot be written to parquet.
+pub fn to_leaves(array: &dyn Array) -> PolarsResult<Vec<&dyn Array>> {
     let mut leaves = vec![];
-    to_leaves_recursive(array, &mut leaves);
-    leaves
+    to_leaves_recursive(array, &mut leaves)?;
+    Ok(leaves)
 }
 
-fn to_leaves_recursive<'a>(array: &'a dyn Array, leaves: &mut Vec<&'a dyn Array>) {
+fn to_leaves_recursive<'a>(
+    array: &'a dyn Array,
+    leaves: &mut Vec<&'a dyn Array>,
+) -> PolarsResult<()> {
     use PhysicalType::*;
     match array.data_type().to_physical_type() {
         Struct => {
@@ -226,27 +640,32 @@ fn to_leaves_recursive<'a>(array: &'a dyn Array, leaves: &mut Vec<&'a dyn Array>
             array
                 .values()
                 .iter()
-                .for_each(|a| to_leaves_recursive(a.as_ref(), leaves));
+                .try_for_each(|a| to_leaves_recursive(a.as_ref(), leaves))
         },
         List => {
             let array = array.as_any().downcast_ref::<ListArray<i32>>().unwrap();
-            to_leaves_recursive(array.values().as_ref(), leaves);
+            to_leaves_recursive(array.values().as_ref(), leaves)
         },
         LargeList => {
             let array = array.as_any().downcast_ref::<ListArray<i64>>().unwrap();
-            to_leaves_recursive(array.values().as_ref(), leaves);
+            to_leaves_recursive(array.values().as_ref(), leaves)
         },
         FixedSizeList => {
             let array = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
-            to_leaves_recursive(array.values().as_ref(), leaves);
+            to_leaves_recursive(array.values().as_ref(), leaves)
         },
         Map => {
             let array = array.as_any().downcast_ref::<MapArray>().unwrap();
-            to_leaves_recursive(array.field().as_ref(), leaves);
+            to_leaves_recursive(array.field().as_ref(), leaves)
         },
         Null | Boolean | Primitive(_) | Binary | FixedSizeBinary | LargeBinary | Utf8
-        | LargeUtf8 | Dictionary(_) | BinaryView | Utf8View => leaves.push(array),
-        other => todo!("Writing {:?} to parquet not yet implemented", other),
+        | LargeUtf8 | Dictionary(_) | BinaryView | Utf8View => {
+            leaves.push(array);
+            Ok(())
+        },
+        other => polars_bail!(InvalidOperation:
+            "Writing {:?} to parquet not yet implemented", other
+        ),
     }
 }
 
@@ -268,6 +687,44 @@ fn to_parquet_leaves_recursive(type_: ParquetType, leaves: &mut Vec<ParquetPrimi
     }
 }
 
+/// Returns the path of field names from `type_` to each of its leaves, in the DFS order of
+/// [`to_parquet_leaves`] and [`to_leaves`].
+///
+/// The path of a leaf starts with the name of `type_` and includes the groups that encode
+/// lists and maps, e.g. `["l", "list", "item"]`.
+pub fn leaf_paths(type_: &ParquetType) -> Vec<Vec<String>> {
+    let mut paths = vec![];
+    leaf_paths_recursive(type_, &mut vec![], &mut paths);
+    paths
+}
+
+fn leaf_paths_recursive(type_: &ParquetType, path: &mut Vec<String>, paths: &mut Vec<Vec<String>>) {
+    path.push(type_.name().to_string());
+    match type_ {
+        ParquetType::PrimitiveType(_) => paths.push(path.clone()),
+        ParquetType::GroupType { fields, .. } => fields
+            .iter()
+            .for_each(|type_| leaf_paths_recursive(type_, path, paths)),
+    }
+    path.pop();
+}
+
+/// Checks the nesting of the leaf columns of an array: in debug builds that it is consistent,
+/// and if `strict` that no required field has nulls.
+fn check_nested(nested: &[Vec<Nested>], strict: bool) -> PolarsResult<()> {
+    if cfg!(debug_assertions) {
+        validate_nested(nested)?;
+        for nested in nested {
+            let (rep, def) = compute_levels(nested)?;
+            validate_levels(nested, &def, &rep)?;
+        }
+    }
+    if strict {
+        nested.iter().try_for_each(|n| validate_required(n))?;
+    }
+    Ok(())
+}
+
 /// Returns a vector of iterators of [`Page`], one per leaf column in the array
 pub fn array_to_columns<A: AsRef<dyn Array> + Send + Sync>(
     array: A,
@@ -275,14 +732,18 @@ pub fn array_to_columns<A: AsRef<dyn Array> + Send + Sync>(
     options: WriteOptions,
     encoding: &[Encoding],
 ) -> PolarsResult<Vec<DynIter<'static, PolarsResult<Page>>>> {
-    let array = array.as_ref();
-    let nested = to_nested(array, &type_)?;
+    let array = normalize_struct_nulls(array.as_ref(), None)?;
+    let nested = to_nested(array.as_ref(), &type_)?;
+    check_nested(&nested, options.strict)?;
 
     let types = to_parquet_leaves(type_);
+    polars_ensure!(
+        encoding.len() == types.len(),
+        InvalidOperation: "expected {} encodings for {} leaf columns, got {}",
+        types.len(), types.len(), encoding.len()
+    );
 
-    let values = to_leaves(array);
-
-    assert_eq!(encoding.len(), types.len());
+    let values = to_leaves(array.as_ref())?;
 
     values
         .iter()
@@ -290,19 +751,190 @@ pub fn array_to_columns<A: AsRef<dyn Array> + Send + Sync>(
         .zip(types)
         .zip(encoding.iter())
         .map(|(((values, nested), type_), encoding)| {
-            array_to_pages(*values, type_, &nested, options, *encoding)
+            array_to_pages(*values, type_, &nested, options.clone(), *encoding)
         })
         .collect()
 }
 
+/// Like [`array_to_columns`], but the encoding of each leaf column is chosen by `encoding`
+/// from its parquet type instead of by its position in DFS order.
+pub fn array_to_columns_with<A, F>(
+    array: A,
+    type_: ParquetType,
+    options: WriteOptions,
+    encoding: F,
+) -> PolarsResult<Vec<DynIter<'static, PolarsResult<Page>>>>
+where
+    A: AsRef<dyn Array> + Send + Sync,
+    F: Fn(&ParquetPrimitiveType) -> Encoding,
+{
+    let encodings = to_parquet_leaves(type_.clone())
+        .iter()
+        .map(encoding)
+        .collect::<Vec<_>>();
+    array_to_columns(array, type_, options, &encodings)
+}
+
+/// The order of the values of a sorted column, recorded as a [`SortingColumn`] of the row
+/// groups that contain it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ColumnSortOrder {
+    /// Whether the values are in descending rather than ascending order
+    pub descending: bool,
+    /// Whether the nulls come before rather than after the values
+    pub nulls_first: bool,
+}
+
+impl ColumnSortOrder {
+    /// Returns the [`SortingColumn`] of the leaf column at `column_idx`, in the order of the
+    /// leaves of the parquet schema, sorted in this order.
+    pub fn to_sorting_column(self, column_idx: usize) -> SortingColumn {
+        SortingColumn {
+            column_idx: column_idx as i32,
+            descending: self.descending,
+            nulls_first: self.nulls_first,
+        }
+    }
+}
+
+/// Like [`array_to_columns`], for an array whose values are sorted in `sort_order`.
+///
+/// The sort order is recorded in the pages, and added to the sorting columns of the row
+/// group they are written to, unless
+/// [`FileWriter::set_sorting_columns`](super::FileWriter::set_sorting_columns) already sets an
+/// order for this column.
+///
+/// # Errors
+/// Errors if `sort_order` is set on a nested array or if, in debug builds, the values of
+/// `array` are not sorted in `sort_order`. Values without a parquet sort order, such as those
+/// of a dictionary, are not checked.
+pub fn array_to_columns_sorted<A: AsRef<dyn Array> + Send + Sync>(
+    array: A,
+    type_: ParquetType,
+    options: WriteOptions,
+    encoding: &[Encoding],
+    sort_order: Option<ColumnSortOrder>,
+) -> PolarsResult<Vec<DynIter<'static, PolarsResult<Page>>>> {
+    if let Some(sort_order) = sort_order {
+        let values = array.as_ref();
+        polars_ensure!(
+            !matches!(
+                values.data_type().to_physical_type(),
+                PhysicalType::Struct
+                    | PhysicalType::List
+                    | PhysicalType::LargeList
+                    | PhysicalType::FixedSizeList
+                    | PhysicalType::Map
+            ),
+            InvalidOperation: "a sort order cannot be set on the nested type {:?}",
+            values.data_type()
+        );
+        if cfg!(debug_assertions) {
+            check_sorted(values, sort_order)?;
+        }
+    }
+    let columns = array_to_columns(array, type_, options, encoding)?;
+    let Some(sort_order) = sort_order else {
+        return Ok(columns);
+    };
+    Ok(columns
+        .into_iter()
+        .map(|pages| {
+            let pages = pages.map(move |page| {
+                let mut page = page?;
+                if let Page::Data(page) = &mut page {
+                    // the column index is set when the row group is written
+                    page.sorting_column = Some(sort_order.to_sorting_column(0));
+                }
+                Ok(page)
+            });
+            DynIter::new(pages)
+        })
+        .collect())
+}
+
+/// Errors if the values of `array` are not sorted in `sort_order`.
+fn check_sorted(array: &dyn Array, sort_order: ColumnSortOrder) -> PolarsResult<()> {
+    let Some(compare) = value_comparator(array) else {
+        return Ok(());
+    };
+    let null_count = array.null_count();
+    let valid = if sort_order.nulls_first {
+        null_count..array.len()
+    } else {
+        0..array.len() - null_count
+    };
+    polars_ensure!(
+        (0..array.len()).all(|i| array.is_valid(i) == valid.contains(&i)),
+        ComputeError: "the nulls of a column sorted with {:?} are not all {}",
+        sort_order, if sort_order.nulls_first { "first" } else { "last" }
+    );
+
+    let out_of_order = if sort_order.descending {
+        Ordering::Less
+    } else {
+        Ordering::Greater
+    };
+    if let Some(i) = (valid.start + 1..valid.end).find(|&i| compare(i - 1, i) == out_of_order) {
+        polars_bail!(ComputeError:
+            "the values of a column sorted with {:?} are out of order at index {}", sort_order, i
+        )
+    }
+    Ok(())
+}
+
+/// Returns the pages of every leaf column of a record batch, in the order of the leaves of
+/// the parquet schema derived from `schema`.
+///
+/// `encodings` holds, for each field of `schema`, the encodings of its leaves as required by
+/// [`array_to_columns`].
+pub fn record_batch_to_columns<A: AsRef<dyn Array> + Send + Sync>(
+    schema: &ArrowSchema,
+    arrays: &[A],
+    options: WriteOptions,
+    encodings: &[Vec<Encoding>],
+) -> PolarsResult<Vec<DynIter<'static, PolarsResult<Page>>>> {
+    polars_ensure!(
+        schema.fields.len() == arrays.len() && arrays.len() == encodings.len(),
+        InvalidOperation: "the schema has {} fields, but {} arrays and {} encodings were passed",
+        schema.fields.len(), arrays.len(), encodings.len()
+    );
+
+    let mut columns = vec![];
+    for ((field, array), encoding) in schema.fields.iter().zip(arrays).zip(encodings) {
+        let type_ = to_parquet_type(field)?;
+        let num_leaves = to_parquet_leaves(type_.clone()).len();
+        polars_ensure!(
+            num_leaves == encoding.len(),
+            InvalidOperation: "the field \"{}\" has {} leaves, but {} encodings were passed",
+            field.name, num_leaves, encoding.len()
+        );
+        columns.extend(array_to_columns(
+            array.as_ref(),
+            type_,
+            options.clone(),
+            encoding,
+        )?);
+    }
+    Ok(columns)
+}
+
 pub fn arrays_to_columns<A: AsRef<dyn Array> + Send + Sync>(
     arrays: &[A],
     type_: ParquetType,
     options: WriteOptions,
     encoding: &[Encoding],
 ) -> PolarsResult<Vec<DynIter<'static, PolarsResult<Page>>>> {
-    let array = arrays[0].as_ref();
-    let nested = to_nested(array, &type_)?;
+    let arrays = arrays
+        .iter()
+        .map(|array| normalize_struct_nulls(array.as_ref(), None))
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let nested = to_nested(arrays[0].as_ref(), &type_)?;
+    if cfg!(debug_assertions) || options.strict {
+        for array in &arrays {
+            check_nested(&to_nested(array.as_ref(), &type_)?, options.strict)?;
+        }
+    }
 
     let types = to_parquet_leaves(type_);
 
@@ -312,9 +944,9 @@ pub fn arrays_to_columns<A: AsRef<dyn Array> + Send + Sync>(
 
     // Ensure we transpose the leaves. So that all the leaves from the same columns are at the same level vec.
     let mut scratch = vec![];
-    for arr in arrays {
+    for arr in &arrays {
         scratch.clear();
-        to_leaves_recursive(arr.as_ref(), &mut scratch);
+        to_leaves_recursive(arr.as_ref(), &mut scratch)?;
         for (i, leave) in scratch.iter().copied().enumerate() {
             while i < leaves.len() {
                 leaves.push(vec![]);
@@ -329,8 +961,17 @@ pub fn arrays_to_columns<A: AsRef<dyn Array> + Send + Sync>(
         .zip(types)
         .zip(encoding.iter())
         .map(move |(((values, nested), type_), encoding)| {
-            let iter = values.into_iter().map(|leave_values| {
-                array_to_pages(leave_values, type_.clone(), &nested, options, *encoding)
+            // a column chunk has a single bloom filter, over the values of every array
+            let mut chunk_options = options.clone();
+            let bloom_filter = chunk_options.bloom_filter.take();
+            let iter = values.iter().map(|leave_values| {
+                array_to_pages(
+                    *leave_values,
+                    type_.clone(),
+                    &nested,
+                    chunk_options.clone(),
+                    *encoding,
+                )
             });
 
             // Need a scratch to bubble up the error :/
@@ -338,7 +979,19 @@ pub fn arrays_to_columns<A: AsRef<dyn Array> + Send + Sync>(
             for v in iter {
                 scratch.push(v?)
             }
-            Ok(DynIter::new(scratch.into_iter().flatten()))
+            let pages = DynIter::new(scratch.into_iter().flatten());
+            match bloom_filter {
+                Some(bloom_filter) => {
+                    let (start, length) = slice_nested_leaf(&nested);
+                    let leaves = values
+                        .iter()
+                        .map(|leave_values| leave_values.sliced(start, length))
+                        .collect::<Vec<_>>();
+                    let leaves = leaves.iter().map(|leaf| leaf.as_ref()).collect::<Vec<_>>();
+                    with_bloom_filter(pages, &leaves, &bloom_filter)
+                },
+                None => Ok(pages),
+            }
         })
         .collect::<PolarsResult<Vec<_>>>()
 }
@@ -348,7 +1001,7 @@ mod tests {
     use arrow::array::*;
     use arrow::datatypes::*;
 
-    use super::super::{FieldInfo, ParquetPhysicalType};
+    use super::super::{FieldInfo, ParquetPhysicalType, Version};
     use super::*;
     use crate::parquet::schema::types::{
         GroupLogicalType, PrimitiveConvertedType, PrimitiveLogicalType,
@@ -522,6 +1175,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_leaf_paths() {
+        let inner = ArrowDataType::Struct(vec![
+            Field::new("x", ArrowDataType::Boolean, false),
+            Field::new("y", ArrowDataType::Int32, true),
+        ]);
+        let list = ArrowDataType::List(Box::new(Field::new("item", ArrowDataType::Int64, true)));
+        let field = Field::new(
+            "a",
+            ArrowDataType::Struct(vec![
+                Field::new("b", inner, true),
+                Field::new("c", list, true),
+            ]),
+            true,
+        );
+        let type_ = to_parquet_type(&field).unwrap();
+
+        let paths = leaf_paths(&type_)
+            .into_iter()
+            .map(|path| path.join("."))
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec!["a.b.x", "a.b.y", "a.c.list.item"]);
+        assert_eq!(paths.len(), to_parquet_leaves(type_).len());
+    }
+
     #[test]
     fn test_list_struct() {
         let boolean = BooleanArray::from_slice([false, false, true, true]).boxed();
@@ -700,24 +1378,791 @@ mod tests {
             a,
             vec![
                 vec![
-                    Nested::List(ListNested::<i32> {
-                        is_optional: false,
-                        offsets: vec![0, 2, 3, 4, 6].try_into().unwrap(),
-                        validity: None,
-                    }),
+                    Nested::Map(
+                        ListNested::<i32> {
+                            is_optional: false,
+                            offsets: vec![0, 2, 3, 4, 6].try_into().unwrap(),
+                            validity: None,
+                        },
+                        Box::new((
+                            Nested::Primitive(None, false, 6),
+                            Nested::Primitive(None, false, 6)
+                        )),
+                    ),
                     Nested::Struct(None, true, 6),
                     Nested::Primitive(None, false, 6),
                 ],
                 vec![
-                    Nested::List(ListNested::<i32> {
-                        is_optional: false,
-                        offsets: vec![0, 2, 3, 4, 6].try_into().unwrap(),
-                        validity: None,
-                    }),
+                    Nested::Map(
+                        ListNested::<i32> {
+                            is_optional: false,
+                            offsets: vec![0, 2, 3, 4, 6].try_into().unwrap(),
+                            validity: None,
+                        },
+                        Box::new((
+                            Nested::Primitive(None, false, 6),
+                            Nested::Primitive(None, false, 6)
+                        )),
+                    ),
                     Nested::Struct(None, true, 6),
                     Nested::Primitive(None, false, 6),
                 ],
             ]
         );
     }
+
+    #[test]
+    fn test_map_null_values() {
+        // [{"a": 1, "b": None}, {"c": None}]
+        let kv_type = |nullable_key| {
+            ArrowDataType::Struct(vec![
+                Field::new("key", ArrowDataType::Utf8, nullable_key),
+                Field::new("value", ArrowDataType::Int32, true),
+            ])
+        };
+        let map = |keys: Utf8Array<i32>, nullable_key| {
+            let values = Int32Array::from([Some(1), None, None]).boxed();
+            let kv = StructArray::new(kv_type(nullable_key), vec![keys.boxed(), values], None);
+            let field = Box::new(Field::new("entries", kv_type(nullable_key), false));
+            let map_type = ArrowDataType::Map(field, false);
+            let offsets = OffsetsBuffer::try_from(vec![0, 2, 3]).unwrap();
+            let array = MapArray::new(map_type.clone(), offsets, kv.boxed(), None);
+            let type_ = super::super::to_parquet_type(&Field::new("m", map_type, false)).unwrap();
+            to_nested(&array, &type_)
+        };
+
+        let nested = map(Utf8Array::from_slice(["a", "b", "c"]), false).unwrap();
+        assert_eq!(nested.len(), 2);
+        let Nested::Map(_, entries) = &nested[0][0] else {
+            panic!("expected a map, found {:?}", nested[0][0])
+        };
+        assert_eq!(entries.0, nested[0][2]);
+        assert_eq!(entries.1, nested[1][2]);
+        assert_eq!(nested[0][2], Nested::Primitive(None, false, 3));
+        assert_eq!(
+            nested[1][2],
+            Nested::Primitive(Some(Bitmap::from([true, false, false])), true, 3)
+        );
+
+        // keys must be required...
+        assert!(map(Utf8Array::from_slice(["a", "b", "c"]), true).is_err());
+        // ...and non-null
+        assert!(map(Utf8Array::from([Some("a"), None, Some("c")]), false).is_err());
+    }
+
+    #[test]
+    fn test_map_integer_keys() {
+        // [{1: 1.5, 2: None}, None, {}, {3: 0.5}]
+        let kv_type = ArrowDataType::Struct(vec![
+            Field::new("key", ArrowDataType::Int64, false),
+            Field::new("value", ArrowDataType::Float64, true),
+        ]);
+        let field = Box::new(Field::new("entries", kv_type.clone(), false));
+        let map_type = ArrowDataType::Map(field, false);
+        let map = |keys: Int64Array| {
+            let values = Float64Array::from([Some(1.5), None, Some(0.5)]).boxed();
+            let kv = StructArray::new(kv_type.clone(), vec![keys.boxed(), values], None);
+            MapArray::new(
+                map_type.clone(),
+                vec![0, 2, 2, 2, 3].try_into().unwrap(),
+                kv.boxed(),
+                Some(Bitmap::from([true, false, true, true])),
+            )
+        };
+        let type_ =
+            super::super::to_parquet_type(&Field::new("m", map_type.clone(), true)).unwrap();
+
+        let leaves = to_parquet_leaves(type_.clone());
+        assert_eq!(leaves[0].physical_type, ParquetPhysicalType::Int64);
+        assert_eq!(leaves[0].field_info.repetition, Repetition::Required);
+        assert_eq!(leaves[1].physical_type, ParquetPhysicalType::Double);
+        assert_eq!(leaves[1].field_info.repetition, Repetition::Optional);
+
+        let array = map(Int64Array::from_slice([1, 2, 3]));
+        let nested = to_nested(&array, &type_).unwrap();
+        let levels = nested
+            .iter()
+            .map(|nested| compute_levels(nested).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            levels,
+            vec![
+                (vec![0, 1, 0, 0, 0], vec![2, 2, 0, 1, 2]),
+                (vec![0, 1, 0, 0, 0], vec![3, 2, 0, 1, 3]),
+            ]
+        );
+
+        let options = WriteOptions::builder()
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let columns = array_to_columns(
+            &array as &dyn Array,
+            type_.clone(),
+            options,
+            &[Encoding::Plain, Encoding::Plain],
+        )
+        .unwrap();
+        for mut pages in columns {
+            match pages.next().unwrap().unwrap() {
+                Page::Data(page) => assert_eq!(page.num_values(), 5),
+                Page::Dict(_) => unreachable!(),
+            }
+        }
+
+        // a null key is an error
+        let array = map(Int64Array::from([Some(1), None, Some(3)]));
+        assert!(to_nested(&array, &type_).is_err());
+    }
+
+    #[test]
+    fn test_struct_null_list_values() {
+        let values = Int32Array::from_slice([1, 2, 3, 4]).boxed();
+        let list = ListArray::<i32>::new(
+            ArrowDataType::List(Box::new(Field::new("item", ArrowDataType::Int32, false))),
+            vec![0, 2, 3, 4].try_into().unwrap(),
+            values,
+            None,
+        );
+        let fields = vec![Field::new("l", list.data_type().clone(), false)];
+        let array = StructArray::new(
+            ArrowDataType::Struct(fields),
+            vec![list.boxed()],
+            Some(Bitmap::from([true, false, true])),
+        );
+
+        let type_ = ParquetType::GroupType {
+            field_info: FieldInfo {
+                name: "a".to_string(),
+                repetition: Repetition::Optional,
+                id: None,
+            },
+            logical_type: None,
+            converted_type: None,
+            fields: vec![ParquetType::GroupType {
+                field_info: FieldInfo {
+                    name: "l".to_string(),
+                    repetition: Repetition::Required,
+                    id: None,
+                },
+                logical_type: None,
+                converted_type: None,
+                fields: vec![ParquetType::GroupType {
+                    field_info: FieldInfo {
+                        name: "list".to_string(),
+                        repetition: Repetition::Repeated,
+                        id: None,
+                    },
+                    logical_type: None,
+                    converted_type: None,
+                    fields: vec![ParquetType::PrimitiveType(ParquetPrimitiveType {
+                        field_info: FieldInfo {
+                            name: "item".to_string(),
+                            repetition: Repetition::Required,
+                            id: None,
+                        },
+                        logical_type: None,
+                        converted_type: None,
+                        physical_type: ParquetPhysicalType::Int32,
+                    })],
+                }],
+            }],
+        };
+
+        let array = normalize_struct_nulls(&array, None).unwrap();
+        let a = to_nested(array.as_ref(), &type_).unwrap();
+
+        assert_eq!(
+            a,
+            vec![vec![
+                Nested::Struct(Some(Bitmap::from([true, false, true])), true, 3),
+                Nested::List(ListNested::<i32> {
+                    is_optional: false,
+                    offsets: vec![0, 2, 2, 3].try_into().unwrap(),
+                    validity: None,
+                }),
+                Nested::Primitive(None, false, 3),
+            ]]
+        );
+
+        let leaves = to_leaves(array.as_ref()).unwrap();
+        assert_eq!(
+            leaves[0].as_any().downcast_ref::<Int32Array>().unwrap(),
+            &Int32Array::from_slice([1, 2, 4])
+        );
+    }
+
+    #[test]
+    fn test_is_normalized() {
+        let fields = vec![Field::new("a", ArrowDataType::Int32, true)];
+        let struct_ = |validity| {
+            StructArray::new(
+                ArrowDataType::Struct(fields.clone()),
+                vec![Int32Array::from([Some(1), None, Some(3)]).boxed()],
+                validity,
+            )
+        };
+        assert!(is_normalized(&struct_(None)));
+        assert!(!is_normalized(&struct_(Some(Bitmap::from([
+            true, false, true
+        ])))));
+
+        let list = ListArray::<i32>::new(
+            ArrowDataType::List(Box::new(Field::new("item", ArrowDataType::Int32, true))),
+            vec![0, 1, 1, 3].try_into().unwrap(),
+            Int32Array::from([Some(1), None, Some(3)]).boxed(),
+            None,
+        );
+        assert!(is_normalized(&list));
+        // the offsets of a sliced list are rebased
+        assert!(!is_normalized(&list.clone().sliced(1, 2)));
+    }
+
+    #[test]
+    fn test_struct_nulls_required_children() {
+        let fields = vec![
+            Field::new("a", ArrowDataType::Int32, false),
+            Field::new("b", ArrowDataType::Int32, true),
+        ];
+        let array = StructArray::new(
+            ArrowDataType::Struct(fields),
+            vec![
+                Int32Array::from_slice([1, 2, 3]).boxed(),
+                Int32Array::from([Some(1), Some(2), None]).boxed(),
+            ],
+            Some(Bitmap::from([true, false, true])),
+        );
+
+        let array = normalize_struct_nulls(&array, None).unwrap();
+        let array = array.as_any().downcast_ref::<StructArray>().unwrap();
+
+        assert_eq!(array.validity(), Some(&Bitmap::from([true, false, true])));
+        assert_eq!(array.values()[0].validity(), None);
+        assert_eq!(
+            array.values()[1].validity(),
+            Some(&Bitmap::from([true, false, false]))
+        );
+    }
+
+    #[test]
+    fn test_leaves_unsupported() {
+        let data_type = ArrowDataType::Union(
+            vec![Field::new("a", ArrowDataType::Int32, true)],
+            None,
+            UnionMode::Sparse,
+        );
+        let array = UnionArray::new(
+            data_type,
+            vec![0i8, 0].into(),
+            vec![Int32Array::from_slice([1, 2]).boxed()],
+            None,
+        );
+        let array = StructArray::new(
+            ArrowDataType::Struct(vec![Field::new("u", array.data_type().clone(), true)]),
+            vec![array.boxed()],
+            None,
+        );
+
+        assert!(to_leaves(&array).is_err());
+        assert!(nested_from_arrow(&array).is_err());
+    }
+
+    #[test]
+    fn test_encoding_per_leaf_type() {
+        let fields = vec![
+            Field::new("a", ArrowDataType::Int32, true),
+            Field::new("b", ArrowDataType::LargeUtf8, false),
+            Field::new("c", ArrowDataType::Boolean, true),
+            Field::new("d", ArrowDataType::Int64, false),
+        ];
+        let array = StructArray::new(
+            ArrowDataType::Struct(fields.clone()),
+            vec![
+                Int32Array::from([Some(1), None, Some(3)]).boxed(),
+                Utf8Array::<i64>::from_slice(["x", "yy", "zzz"]).boxed(),
+                BooleanArray::from([Some(true), Some(false), None]).boxed(),
+                Int64Array::from_slice([4, 5, 6]).boxed(),
+            ],
+            Some(Bitmap::from([true, false, true])),
+        );
+        let type_ =
+            super::super::to_parquet_type(&Field::new("s", ArrowDataType::Struct(fields), true))
+                .unwrap();
+        let options = WriteOptions::builder()
+            .write_statistics(false)
+            .version(Version::V2)
+            .build()
+            .unwrap();
+
+        let columns = array_to_columns_with(&array as &dyn Array, type_, options, |type_| {
+            match type_.physical_type {
+                ParquetPhysicalType::Int32 | ParquetPhysicalType::Int64 => {
+                    Encoding::DeltaBinaryPacked
+                },
+                ParquetPhysicalType::ByteArray => Encoding::DeltaLengthByteArray,
+                _ => Encoding::Plain,
+            }
+        })
+        .unwrap();
+
+        let encodings = columns
+            .into_iter()
+            .map(|mut pages| match pages.next().unwrap().unwrap() {
+                Page::Data(page) => page.encoding(),
+                Page::Dict(_) => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            encodings,
+            vec![
+                Encoding::DeltaBinaryPacked,
+                Encoding::DeltaLengthByteArray,
+                Encoding::Plain,
+                Encoding::DeltaBinaryPacked,
+            ]
+        );
+    }
+
+    /// Returns the estimated and the actual number of bytes of the pages of `array`.
+    fn estimate_and_write(array: Box<dyn Array>, encoding: Encoding) -> (usize, usize) {
+        let field = Field::new("a", array.data_type().clone(), true);
+        let type_ = super::super::to_parquet_type(&field).unwrap();
+        let nested = to_nested(array.as_ref(), &type_).unwrap();
+        let leaves = to_leaves(array.as_ref()).unwrap();
+        let estimate = super::super::estimate_encoded_size(leaves[0], &nested[0], encoding);
+
+        let options = WriteOptions::builder()
+            .write_statistics(false)
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let actual = array_to_columns(array, type_, options, &[encoding])
+            .unwrap()
+            .remove(0)
+            .map(|page| match page.unwrap() {
+                Page::Data(page) => page.buffer().len(),
+                Page::Dict(page) => page.buffer.len(),
+            })
+            .sum();
+        (estimate, actual)
+    }
+
+    #[test]
+    fn test_estimate_encoded_size() {
+        let within_tolerance = |(estimate, actual): (usize, usize)| {
+            assert!(
+                estimate.abs_diff(actual) <= actual / 10 + 16,
+                "estimated {estimate} bytes, wrote {actual}"
+            );
+        };
+
+        let array = Int32Array::from_iter((0..10_000).map(|x| (x % 7 != 0).then_some(x)));
+        within_tolerance(estimate_and_write(array.boxed(), Encoding::Plain));
+
+        let values = Utf8Array::<i64>::from_iter_values((0..3_000).map(|x| format!("value {x}")));
+        let list = ListArray::<i32>::new(
+            ArrowDataType::List(Box::new(Field::new(
+                "item",
+                ArrowDataType::LargeUtf8,
+                false,
+            ))),
+            (0..=1_000)
+                .map(|x| x * 3)
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+            values.boxed(),
+            None,
+        );
+        within_tolerance(estimate_and_write(
+            list.clone().boxed(),
+            Encoding::DeltaLengthByteArray,
+        ));
+        within_tolerance(estimate_and_write(list.boxed(), Encoding::Plain));
+
+        let keys = Int32Array::from_iter((0..10_000).map(|x| (x % 5 != 0).then_some(x % 3)));
+        let array = DictionaryArray::try_new(
+            ArrowDataType::Dictionary(IntegerType::Int32, Box::new(ArrowDataType::Utf8), false),
+            keys,
+            Utf8Array::<i32>::from_slice(["a", "bb", "ccc"]).boxed(),
+        )
+        .unwrap();
+        within_tolerance(estimate_and_write(array.boxed(), Encoding::RleDictionary));
+    }
+
+    #[test]
+    fn test_mismatched_type() {
+        let list = ListArray::<i32>::new(
+            ArrowDataType::List(Box::new(Field::new("item", ArrowDataType::Int32, true))),
+            vec![0, 1].try_into().unwrap(),
+            Int32Array::from_slice([1]).boxed(),
+            None,
+        );
+        let type_ = ParquetType::from_physical("l".to_string(), ParquetPhysicalType::Int32);
+        let error = to_nested(&list, &type_).unwrap_err().to_string();
+        assert!(
+            error.contains("the parquet field \"l\" of a list array must be a group"),
+            "{error}"
+        );
+        assert!(error.contains("Int32"), "{error}");
+
+        let fields = vec![
+            Field::new("a", ArrowDataType::Int32, true),
+            Field::new("b", ArrowDataType::Int32, true),
+        ];
+        let array = StructArray::new(
+            ArrowDataType::Struct(fields.clone()),
+            vec![
+                Int32Array::from_slice([1]).boxed(),
+                Int32Array::from_slice([2]).boxed(),
+            ],
+            None,
+        );
+        let type_ = super::super::to_parquet_type(&Field::new(
+            "s",
+            ArrowDataType::Struct(fields[..1].to_vec()),
+            true,
+        ))
+        .unwrap();
+        let error = to_nested(&array, &type_).unwrap_err().to_string();
+        assert!(
+            error.contains(
+                "the parquet group \"s\" has 1 fields, but the struct array has 2 fields (a, b)"
+            ),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_timestamp_logical_type() {
+        use crate::parquet::schema::types::TimeUnit as ParquetTimeUnit;
+
+        let units = [
+            (TimeUnit::Millisecond, ParquetTimeUnit::Milliseconds),
+            (TimeUnit::Microsecond, ParquetTimeUnit::Microseconds),
+            (TimeUnit::Nanosecond, ParquetTimeUnit::Nanoseconds),
+        ];
+        let zones = [
+            (Some("+01:00".to_string()), true),
+            (Some("UTC".to_string()), true),
+            (Some(String::new()), false),
+            (None, false),
+        ];
+        for (unit, parquet_unit) in units {
+            for (zone, is_adjusted_to_utc) in zones.clone() {
+                let timestamp = ArrowDataType::Timestamp(unit, zone);
+                let list = ArrowDataType::List(Box::new(Field::new("item", timestamp, true)));
+                let type_ = super::super::to_parquet_type(&Field::new("l", list, true)).unwrap();
+
+                let leaves = to_parquet_leaves(type_);
+                assert_eq!(leaves.len(), 1);
+                assert_eq!(leaves[0].physical_type, ParquetPhysicalType::Int64);
+                assert_eq!(
+                    leaves[0].logical_type,
+                    Some(PrimitiveLogicalType::Timestamp {
+                        is_adjusted_to_utc,
+                        unit: parquet_unit,
+                    })
+                );
+            }
+        }
+
+        // seconds have no timestamp logical type
+        let timestamp = ArrowDataType::Timestamp(TimeUnit::Second, Some("UTC".to_string()));
+        let type_ = super::super::to_parquet_type(&Field::new("t", timestamp, true)).unwrap();
+        assert_eq!(to_parquet_leaves(type_)[0].logical_type, None);
+    }
+
+    #[test]
+    fn test_empty_struct() {
+        let empty = ArrowDataType::Struct(vec![]);
+        assert!(StructArray::try_new(empty.clone(), vec![], None).is_err());
+
+        let field = Field::new("s", empty.clone(), true);
+        let error = super::super::to_parquet_type(&field)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("empty structs"), "{error}");
+        assert!(error.contains("\"s\""), "{error}");
+
+        // nested empty structs are rejected too
+        let list = ArrowDataType::List(Box::new(Field::new("item", empty, true)));
+        assert!(super::super::to_parquet_type(&Field::new("l", list, true)).is_err());
+
+        // a group without fields does not match a struct array
+        let array = StructArray::new(
+            ArrowDataType::Struct(vec![Field::new("a", ArrowDataType::Int32, true)]),
+            vec![Int32Array::from_slice([1]).boxed()],
+            None,
+        );
+        let type_ = ParquetType::from_group(
+            "s".to_string(),
+            Repetition::Optional,
+            None,
+            None,
+            vec![],
+            None,
+        );
+        assert!(to_nested(&array, &type_).is_err());
+    }
+
+    #[test]
+    fn test_record_batch_to_columns() {
+        let struct_fields = vec![
+            Field::new("a", ArrowDataType::Int32, true),
+            Field::new("b", ArrowDataType::Boolean, false),
+        ];
+        let list_type =
+            ArrowDataType::LargeList(Box::new(Field::new("item", ArrowDataType::Int64, true)));
+        let schema = ArrowSchema::from(vec![
+            Field::new("s", ArrowDataType::Struct(struct_fields.clone()), true),
+            Field::new("l", list_type.clone(), true),
+        ]);
+
+        let struct_ = StructArray::new(
+            ArrowDataType::Struct(struct_fields),
+            vec![
+                Int32Array::from([Some(1), None, Some(3)]).boxed(),
+                BooleanArray::from_slice([true, false, true]).boxed(),
+            ],
+            Some(Bitmap::from([true, false, true])),
+        );
+        // [[1, None], None, [4, 5, 6]]
+        let list = ListArray::<i64>::new(
+            list_type,
+            vec![0, 2, 2, 5].try_into().unwrap(),
+            Int64Array::from([Some(1), None, Some(4), Some(5), Some(6)]).boxed(),
+            Some(Bitmap::from([true, false, true])),
+        );
+        let arrays = [struct_.boxed(), list.boxed()];
+
+        let options = WriteOptions::builder()
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let encodings = vec![vec![Encoding::Plain; 2], vec![Encoding::Plain]];
+        let columns =
+            record_batch_to_columns(&schema, &arrays, options.clone(), &encodings).unwrap();
+
+        let num_values = columns
+            .into_iter()
+            .map(|pages| {
+                pages
+                    .map(|page| match page.unwrap() {
+                        Page::Data(page) => page.num_values(),
+                        Page::Dict(_) => 0,
+                    })
+                    .sum::<usize>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(num_values, vec![3, 3, 6]);
+
+        // the encodings must match the leaves of each field
+        let encodings = vec![vec![Encoding::Plain], vec![Encoding::Plain]];
+        assert!(record_batch_to_columns(&schema, &arrays, options.clone(), &encodings).is_err());
+        assert!(record_batch_to_columns(&schema, &arrays[..1], options, &encodings).is_err());
+    }
+
+    #[test]
+    fn mismatched_encodings() {
+        let fields = vec![
+            Field::new("b", ArrowDataType::Boolean, false),
+            Field::new("c", ArrowDataType::Int32, false),
+        ];
+        let array = StructArray::new(
+            ArrowDataType::Struct(fields.clone()),
+            vec![
+                BooleanArray::from_slice([false, true]).boxed(),
+                Int32Array::from_slice([42, 28]).boxed(),
+            ],
+            None,
+        );
+        let type_ =
+            super::super::to_parquet_type(&Field::new("a", ArrowDataType::Struct(fields), false))
+                .unwrap();
+        let options = WriteOptions::builder().build().unwrap();
+
+        let Err(error) = array_to_columns(array.boxed(), type_, options, &[Encoding::Plain]) else {
+            panic!("a struct of two leaves requires two encodings")
+        };
+        assert!(
+            error
+                .to_string()
+                .contains("expected 2 encodings for 2 leaf columns, got 1"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn sorted_columns() {
+        let options = WriteOptions::builder()
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let write = |array: Box<dyn Array>, sort_order| {
+            let type_ =
+                super::super::to_parquet_type(&Field::new("a", array.data_type().clone(), true))
+                    .unwrap();
+            array_to_columns_sorted(
+                array,
+                type_,
+                options.clone(),
+                &[Encoding::Plain],
+                Some(sort_order),
+            )
+            .map(|_| ())
+        };
+        let ascending = ColumnSortOrder::default();
+        let descending = ColumnSortOrder {
+            descending: true,
+            nulls_first: false,
+        };
+        let nulls_first = ColumnSortOrder {
+            descending: false,
+            nulls_first: true,
+        };
+
+        let array = Int32Array::from([None, Some(-1), Some(2), Some(2), Some(5)]).boxed();
+        assert!(write(array.clone(), nulls_first).is_ok());
+        assert!(write(array.clone(), ascending).is_err());
+        assert!(write(array.sliced(1, 4), ascending).is_ok());
+        assert!(write(array.sliced(1, 4), descending).is_err());
+
+        let array = Utf8Array::<i32>::from([Some("b"), Some("a"), Some("a"), None]).boxed();
+        assert!(write(array.clone(), descending).is_ok());
+        assert!(write(array, nulls_first).is_err());
+
+        let array = Int32Array::from_slice([1, 3, 2]).boxed();
+        let error = write(array, ascending).unwrap_err();
+        assert!(
+            error.to_string().contains("out of order at index 2"),
+            "{error}"
+        );
+
+        let list = ListArray::<i32>::new(
+            ArrowDataType::List(Box::new(Field::new("item", ArrowDataType::Int32, false))),
+            vec![0, 1, 2].try_into().unwrap(),
+            Int32Array::from_slice([1, 2]).boxed(),
+            None,
+        );
+        assert!(write(list.boxed(), ascending).is_err());
+    }
+
+    #[test]
+    fn sorting_columns_metadata() {
+        use polars_error::PolarsError;
+
+        use crate::parquet::error::Error as ParquetError;
+        use crate::parquet::write::{Compressor, DynStreamingIterator};
+        use crate::parquet::FallibleStreamingIterator;
+
+        let field = Field::new("id", ArrowDataType::Int64, false);
+        let schema = ArrowSchema::from(vec![field.clone()]);
+        let options = WriteOptions::builder()
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let sort_order = ColumnSortOrder {
+            descending: true,
+            nulls_first: false,
+        };
+
+        let array = Int64Array::from_slice([9, 7, 7, 3]);
+        let type_ = super::super::to_parquet_type(&field).unwrap();
+        let columns = array_to_columns_sorted(
+            array.boxed(),
+            type_,
+            options.clone(),
+            &[Encoding::Plain],
+            Some(sort_order),
+        )
+        .unwrap()
+        .into_iter()
+        .map(|pages| {
+            let pages =
+                DynIter::new(pages.map(|page| page.map_err(|e| ParquetError::oos(e.to_string()))));
+            let pages =
+                Compressor::new(pages, options.compression, vec![]).map_err(PolarsError::from);
+            Ok(DynStreamingIterator::new(pages))
+        });
+
+        let mut writer =
+            super::super::FileWriter::try_new(vec![], schema, options.clone()).unwrap();
+        writer.write(DynIter::new(columns)).unwrap();
+        writer.end(None).unwrap();
+        let (_, metadata) = writer.into_inner_and_metadata();
+
+        assert_eq!(
+            metadata.row_groups[0].sorting_columns,
+            Some(vec![SortingColumn {
+                column_idx: 0,
+                descending: true,
+                nulls_first: false,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_nested_from_arrow() {
+        // the nullability of every field matches whether its array has a validity
+        let same_as_to_nested = |array: &dyn Array, nullable: bool| {
+            let field = Field::new("a", array.data_type().clone(), nullable);
+            let type_ = super::super::to_parquet_type(&field).unwrap();
+            let normalized = normalize_struct_nulls(array, None).unwrap();
+            assert_eq!(
+                nested_from_arrow(array).unwrap(),
+                to_nested(normalized.as_ref(), &type_).unwrap()
+            );
+        };
+
+        let fields = vec![
+            Field::new("b", ArrowDataType::Boolean, false),
+            Field::new("c", ArrowDataType::Int32, true),
+        ];
+        let struct_ = StructArray::new(
+            ArrowDataType::Struct(fields),
+            vec![
+                BooleanArray::from_slice([false, false, true, true]).boxed(),
+                Int32Array::from([Some(42), None, Some(19), Some(31)]).boxed(),
+            ],
+            Some(Bitmap::from([true, true, false, true])),
+        );
+        same_as_to_nested(&struct_, true);
+
+        let list = ListArray::<i32>::new(
+            ArrowDataType::List(Box::new(Field::new(
+                "item",
+                struct_.data_type().clone(),
+                true,
+            ))),
+            vec![0, 2, 2, 4].try_into().unwrap(),
+            struct_.boxed(),
+            Some(Bitmap::from([true, false, true])),
+        );
+        same_as_to_nested(&list, true);
+
+        let list = ListArray::<i64>::new(
+            ArrowDataType::LargeList(Box::new(Field::new("item", ArrowDataType::Int32, false))),
+            vec![0, 1, 3].try_into().unwrap(),
+            Int32Array::from_slice([1, 2, 3]).boxed(),
+            None,
+        );
+        same_as_to_nested(&list, false);
+
+        // an array without a validity is required, whatever its field
+        let array = Int32Array::from_slice([1, 2]);
+        assert_eq!(
+            nested_from_arrow(&array).unwrap(),
+            vec![vec![Nested::Primitive(None, false, 2)]]
+        );
+        let array = NullArray::new(ArrowDataType::Null, 2);
+        assert_eq!(
+            nested_from_arrow(&array).unwrap(),
+            vec![vec![Nested::Primitive(
+                Some(Bitmap::new_zeroed(2)),
+                true,
+                2
+            )]]
+        );
+    }
 }
@@ -6,7 +6,7 @@ use super::super::{utils, WriteOptions};
 use crate::arrow::read::schema::is_nullable;
 use crate::arrow::write::utils::ExactSizedIter;
 use crate::parquet::encoding::delta_bitpacked::encode;
-use crate::parquet::encoding::Encoding;
+use crate::parquet::encoding::{byte_stream_split, Encoding};
 use crate::parquet::page::DataPage;
 use crate::parquet::schema::types::PrimitiveType;
 use crate::parquet::statistics::{serialize_statistics, PrimitiveStatistics};
@@ -73,6 +73,28 @@ where
     buffer
 }
 
+pub(crate) fn encode_byte_stream_split<T, P>(
+    array: &PrimitiveArray<T>,
+    is_optional: bool,
+    mut buffer: Vec<u8>,
+) -> Vec<u8>
+where
+    T: NativeType,
+    P: ParquetNativeType,
+    T: num_traits::AsPrimitive<P>,
+{
+    if is_optional {
+        // append the non-null values
+        let iterator = array.non_null_values_iter().map(|x| x.as_());
+        let iterator = ExactSizedIter::new(iterator, array.len() - array.null_count());
+        byte_stream_split::encode::<P>(iterator, &mut buffer)
+    } else {
+        // append all values
+        byte_stream_split::encode::<P>(array.values().iter().map(|x| x.as_()), &mut buffer)
+    }
+    buffer
+}
+
 pub fn array_to_page_plain<T, P>(
     array: &PrimitiveArray<T>,
     options: WriteOptions,
@@ -106,6 +128,27 @@ where
     .map(Page::Data)
 }
 
+pub fn array_to_page_float<T, P>(
+    array: &PrimitiveArray<T>,
+    options: WriteOptions,
+    type_: PrimitiveType,
+    encoding: Encoding,
+) -> PolarsResult<Page>
+where
+    T: NativeType,
+    P: ParquetNativeType,
+    T: num_traits::AsPrimitive<P>,
+{
+    match encoding {
+        Encoding::Plain => array_to_page(array, options, type_, encoding, encode_plain),
+        Encoding::ByteStreamSplit => {
+            array_to_page(array, options, type_, encoding, encode_byte_stream_split)
+        },
+        other => polars_bail!(nyi = "Encoding float as {other:?}"),
+    }
+    .map(Page::Data)
+}
+
 pub fn array_to_page<T, P, F: Fn(&PrimitiveArray<T>, bool, Vec<u8>) -> Vec<u8>>(
     array: &PrimitiveArray<T>,
     options: WriteOptions,
@@ -123,7 +166,8 @@ where
 
     let validity = array.validity();
 
-    let mut buffer = vec![];
+    let mut buffer = utils::page_buffer(&options);
+    let levels_start = buffer.len();
     utils::write_def_levels(
         &mut buffer,
         is_optional,
@@ -132,7 +176,7 @@ where
         options.version,
     )?;
 
-    let definition_levels_byte_length = buffer.len();
+    let definition_levels_byte_length = buffer.len() - levels_start;
 
     let buffer = encode(array, is_optional, buffer);
 
@@ -188,3 +232,102 @@ where
             .min_by(|x, y| x.ord(y)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Float64Array, Int32Array};
+
+    use super::super::super::{array_to_page, Nested, Version};
+    use super::*;
+    use crate::parquet::page::{split_buffer, Page};
+    use crate::parquet::schema::types::PhysicalType;
+    use crate::parquet::statistics::PrimitiveStatistics;
+
+    #[test]
+    fn statistics() {
+        let options = WriteOptions::builder()
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let array = Int32Array::from([Some(3), None, Some(-7), Some(12), None]);
+        let type_ = PrimitiveType::from_physical("a".to_string(), PhysicalType::Int32);
+        let nested = [Nested::Primitive(
+            array.validity().cloned(),
+            true,
+            array.len(),
+        )];
+
+        let page = array_to_page(&array, type_, &nested, options, Encoding::Plain).unwrap();
+        let Page::Data(page) = page else {
+            unreachable!()
+        };
+        let statistics = page.statistics().unwrap().unwrap();
+        let statistics = statistics
+            .as_any()
+            .downcast_ref::<PrimitiveStatistics<i32>>()
+            .unwrap();
+
+        assert_eq!(statistics.null_count, Some(2));
+        assert_eq!(statistics.min_value, Some(-7));
+        assert_eq!(statistics.max_value, Some(12));
+    }
+
+    #[test]
+    fn byte_stream_split() {
+        let options = WriteOptions::builder()
+            .write_statistics(false)
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let array = Float64Array::from([Some(0.5), None, Some(-1.25), Some(1e10)]);
+        let type_ = PrimitiveType::from_physical("a".to_string(), PhysicalType::Double);
+        let nested = [Nested::Primitive(
+            array.validity().cloned(),
+            true,
+            array.len(),
+        )];
+
+        let page = array_to_page(&array, type_, &nested, options, Encoding::ByteStreamSplit);
+        let Page::Data(page) = page.unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(page.encoding(), Encoding::ByteStreamSplit);
+
+        let (_, _, values) = split_buffer(&page).unwrap();
+        let values = byte_stream_split::decode::<f64>(values).unwrap();
+        assert_eq!(values, vec![0.5, -1.25, 1e10]);
+    }
+
+    #[test]
+    fn required_has_no_levels() {
+        use arrow::datatypes::{ArrowDataType, Field};
+
+        use super::super::super::{to_parquet_leaves, to_parquet_type};
+
+        let array = Int32Array::from_slice([1, 2, 3, 4, 5]);
+        let type_ = to_parquet_type(&Field::new("a", ArrowDataType::Int32, false)).unwrap();
+        let type_ = to_parquet_leaves(type_).pop().unwrap();
+        let nested = [Nested::Primitive(None, false, array.len())];
+        let values = array
+            .values()
+            .iter()
+            .flat_map(|x| i32::to_le_bytes(*x))
+            .collect::<Vec<_>>();
+
+        for version in [Version::V1, Version::V2] {
+            let options = WriteOptions::builder()
+                .write_statistics(false)
+                .version(version)
+                .build()
+                .unwrap();
+            let page = array_to_page(&array, type_.clone(), &nested, options, Encoding::Plain);
+            let Page::Data(page) = page.unwrap() else {
+                unreachable!()
+            };
+            // neither level stream is written, not even the length prefix of v1
+            assert_eq!(page.buffer(), values);
+            let (rep, def, _) = split_buffer(&page).unwrap();
+            assert!(rep.is_empty() && def.is_empty());
+        }
+    }
+}
@@ -1,10 +1,9 @@
 use arrow::array::{Array, PrimitiveArray};
 use arrow::types::NativeType as ArrowNativeType;
-use polars_error::PolarsResult;
+use polars_error::{polars_bail, PolarsResult};
 
 use super::super::{nested, utils, WriteOptions};
-use super::basic::{build_statistics, encode_plain};
-use crate::arrow::read::schema::is_nullable;
+use super::basic::{build_statistics, encode_byte_stream_split, encode_delta, encode_plain};
 use crate::arrow::write::Nested;
 use crate::parquet::encoding::Encoding;
 use crate::parquet::page::DataPage;
@@ -23,14 +22,83 @@ where
     R: NativeType,
     T: num_traits::AsPrimitive<R>,
 {
-    let is_optional = is_nullable(&type_.field_info);
+    array_to_page_with::<T, R, _>(array, options, type_, nested, Encoding::Plain, encode_plain)
+}
+
+pub fn array_to_page_integer<T, R>(
+    array: &PrimitiveArray<T>,
+    options: WriteOptions,
+    type_: PrimitiveType,
+    nested: &[Nested],
+    encoding: Encoding,
+) -> PolarsResult<DataPage>
+where
+    T: ArrowNativeType,
+    R: NativeType,
+    T: num_traits::AsPrimitive<R>,
+    R: num_traits::AsPrimitive<i64>,
+{
+    match encoding {
+        Encoding::Plain => {
+            array_to_page_with::<T, R, _>(array, options, type_, nested, encoding, encode_plain)
+        },
+        Encoding::DeltaBinaryPacked => {
+            array_to_page_with::<T, R, _>(array, options, type_, nested, encoding, encode_delta)
+        },
+        other => polars_bail!(nyi = "Encoding integer as {other:?}"),
+    }
+}
+
+pub fn array_to_page_float<T, R>(
+    array: &PrimitiveArray<T>,
+    options: WriteOptions,
+    type_: PrimitiveType,
+    nested: &[Nested],
+    encoding: Encoding,
+) -> PolarsResult<DataPage>
+where
+    T: ArrowNativeType,
+    R: NativeType,
+    T: num_traits::AsPrimitive<R>,
+{
+    match encoding {
+        Encoding::Plain => {
+            array_to_page_with::<T, R, _>(array, options, type_, nested, encoding, encode_plain)
+        },
+        Encoding::ByteStreamSplit => array_to_page_with::<T, R, _>(
+            array,
+            options,
+            type_,
+            nested,
+            encoding,
+            encode_byte_stream_split,
+        ),
+        other => polars_bail!(nyi = "Encoding float as {other:?}"),
+    }
+}
+
+fn array_to_page_with<T, R, F: Fn(&PrimitiveArray<T>, bool, Vec<u8>) -> Vec<u8>>(
+    array: &PrimitiveArray<T>,
+    options: WriteOptions,
+    type_: PrimitiveType,
+    nested: &[Nested],
+    encoding: Encoding,
+    encode: F,
+) -> PolarsResult<DataPage>
+where
+    T: ArrowNativeType,
+    R: NativeType,
+    T: num_traits::AsPrimitive<R>,
+{
+    let is_optional = nested::is_optional_leaf(&type_, array);
 
-    let mut buffer = vec![];
+    let mut buffer = utils::page_buffer(&options);
 
+    let info = nested::NestedInfo::new(nested);
     let (repetition_levels_byte_length, definition_levels_byte_length) =
-        nested::write_rep_and_def(options.version, nested, &mut buffer)?;
+        nested::write_rep_and_def_with_info(options.version, nested, &info, &mut buffer)?;
 
-    let buffer = encode_plain(array, is_optional, buffer);
+    let buffer = encode(array, is_optional, buffer);
 
     let statistics = if options.write_statistics {
         Some(serialize_statistics(&build_statistics(
@@ -43,7 +111,7 @@ where
 
     utils::build_plain_page(
         buffer,
-        nested::num_values(nested),
+        info.value_count,
         nested[0].len(),
         array.null_count(),
         repetition_levels_byte_length,
@@ -51,6 +119,61 @@ where
         statistics,
         type_,
         options,
-        Encoding::Plain,
+        encoding,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::Int64Array;
+
+    use super::super::super::pages::ListNested;
+    use super::super::super::{array_to_page, Version};
+    use super::*;
+    use crate::parquet::encoding::delta_bitpacked::Decoder;
+    use crate::parquet::page::{split_buffer, Page};
+    use crate::parquet::schema::types::PhysicalType;
+
+    fn decode(array: &Int64Array, nested: &[Nested]) -> Vec<i64> {
+        let options = WriteOptions::builder()
+            .write_statistics(false)
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let type_ = PrimitiveType::from_physical("a".to_string(), PhysicalType::Int64);
+        let page = array_to_page(array, type_, nested, options, Encoding::DeltaBinaryPacked);
+        let Page::Data(page) = page.unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(page.encoding(), Encoding::DeltaBinaryPacked);
+
+        let (_, _, values) = split_buffer(&page).unwrap();
+        Decoder::try_new(values)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn delta_binary_packed() {
+        let array = Int64Array::from_iter((0..300).map(|x| Some(1_700_000_000 + 7 * x)));
+        let nested = [Nested::Primitive(None, true, array.len())];
+        let expected = array.values().to_vec();
+        assert_eq!(decode(&array, &nested), expected);
+    }
+
+    #[test]
+    fn delta_binary_packed_nested() {
+        // [[10, 11, None], [], [13, 14]]
+        let array = Int64Array::from([Some(10), Some(11), None, Some(13), Some(14)]);
+        let nested = [
+            Nested::List(ListNested::new(
+                vec![0i32, 3, 3, 5].try_into().unwrap(),
+                None,
+                true,
+            )),
+            Nested::Primitive(array.validity().cloned(), true, array.len()),
+        ];
+        assert_eq!(decode(&array, &nested), vec![10, 11, 13, 14]);
+    }
+}
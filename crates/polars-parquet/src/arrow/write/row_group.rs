@@ -33,7 +33,8 @@ pub fn row_group_iter<A: AsRef<dyn Array> + 'static + Send + Sync>(
             .zip(fields)
             .zip(encodings)
             .flat_map(move |((array, type_), encoding)| {
-                let encoded_columns = array_to_columns(array, type_, options, &encoding).unwrap();
+                let encoded_columns =
+                    array_to_columns(array, type_, options.clone(), &encoding).unwrap();
                 encoded_columns
                     .into_iter()
                     .map(|encoded_pages| {
@@ -46,6 +47,7 @@ pub fn row_group_iter<A: AsRef<dyn Array> + 'static + Send + Sync>(
                         );
 
                         let compressed_pages = Compressor::new(pages, options.compression, vec![])
+                            .with_checksums(options.write_page_checksums)
                             .map_err(to_compute_err);
                         Ok(DynStreamingIterator::new(compressed_pages))
                     })
@@ -111,7 +113,7 @@ impl<
     type Item = PolarsResult<RowGroupIterColumns<'static, PolarsError>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let options = self.options;
+        let options = self.options.clone();
 
         self.iter.next().map(|maybe_chunk| {
             let chunk = maybe_chunk?;
@@ -77,6 +77,11 @@ pub fn schema_to_metadata_key(schema: &ArrowSchema) -> KeyValue {
 }
 
 /// Creates a [`ParquetType`] from a [`Field`].
+///
+/// # Errors
+/// Errors if the data type of `field` can't be written to parquet. This includes structs
+/// without fields: every parquet group must contain a leaf column, which is what carries the
+/// number of rows, so an empty struct has no representation.
 pub fn to_parquet_type(field: &Field) -> PolarsResult<ParquetType> {
     let name = field.name.clone();
     let repetition = if field.is_nullable {
@@ -129,6 +134,15 @@ pub fn to_parquet_type(field: &Field) -> PolarsResult<ParquetType> {
             None,
             None,
         )?),
+        // the FLOAT16 logical type is not part of parquet-format v2.9
+        ArrowDataType::Float16 => Ok(ParquetType::try_from_primitive(
+            name,
+            PhysicalType::FixedLenByteArray(2),
+            repetition,
+            None,
+            None,
+            None,
+        )?),
         ArrowDataType::Float32 => Ok(ParquetType::try_from_primitive(
             name,
             PhysicalType::Float,
@@ -290,7 +304,8 @@ pub fn to_parquet_type(field: &Field) -> PolarsResult<ParquetType> {
         ArrowDataType::Struct(fields) => {
             if fields.is_empty() {
                 polars_bail!(InvalidOperation:
-                    "Parquet does not support writing empty structs".to_string(),
+                    "Parquet does not support writing empty structs, the struct field \"{}\" has no fields",
+                    name
                 )
             }
             // recursively convert children to types/nodes
@@ -370,12 +385,13 @@ pub fn to_parquet_type(field: &Field) -> PolarsResult<ParquetType> {
                     None,
                 )?)
             } else {
+                // errors if the precision does not fit in 32 bytes
                 Ok(ParquetType::try_from_primitive(
                     name,
                     PhysicalType::FixedLenByteArray(32),
                     repetition,
-                    None,
-                    None,
+                    Some(PrimitiveConvertedType::Decimal(precision, scale)),
+                    logical_type,
                     None,
                 )?)
             }
@@ -131,7 +131,7 @@ where
                 item,
                 this.encodings.clone(),
                 this.parquet_schema.fields().to_vec(),
-                this.options,
+                this.options.clone(),
             );
             this.task = Some(Box::pin(async move {
                 writer.write(rows).await?;
@@ -4,7 +4,7 @@ use polars_error::*;
 
 use super::{Version, WriteOptions};
 use crate::parquet::compression::CompressionOptions;
-use crate::parquet::encoding::hybrid_rle::encode;
+use crate::parquet::encoding::hybrid_rle::{encode, encode_constant_u32};
 use crate::parquet::encoding::Encoding;
 use crate::parquet::metadata::Descriptor;
 use crate::parquet::page::{DataPage, DataPageHeader, DataPageHeaderV1, DataPageHeaderV2};
@@ -14,9 +14,7 @@ use crate::parquet::statistics::ParquetStatistics;
 fn encode_iter_v1<I: Iterator<Item = bool>>(buffer: &mut Vec<u8>, iter: I) -> PolarsResult<()> {
     buffer.extend_from_slice(&[0; 4]);
     let start = buffer.len();
-    encode::<bool, _, _>(buffer, iter, 1)?;
-    let end = buffer.len();
-    let length = end - start;
+    let length = encode::<bool, _, _>(buffer, iter, 1)?;
 
     // write the first 4 bytes as length
     let length = (length as i32).to_le_bytes();
@@ -25,7 +23,8 @@ fn encode_iter_v1<I: Iterator<Item = bool>>(buffer: &mut Vec<u8>, iter: I) -> Po
 }
 
 fn encode_iter_v2<I: Iterator<Item = bool>>(writer: &mut Vec<u8>, iter: I) -> PolarsResult<()> {
-    Ok(encode::<bool, _, _>(writer, iter, 1)?)
+    encode::<bool, _, _>(writer, iter, 1)?;
+    Ok(())
 }
 
 fn encode_iter<I: Iterator<Item = bool>>(
@@ -39,6 +38,27 @@ fn encode_iter<I: Iterator<Item = bool>>(
     }
 }
 
+/// Writes `len` definition levels of `level` (0 or 1) as a single RLE run.
+fn encode_constant(
+    writer: &mut Vec<u8>,
+    level: u32,
+    len: usize,
+    version: Version,
+) -> PolarsResult<()> {
+    match version {
+        Version::V1 => {
+            let start = writer.len();
+            writer.extend_from_slice(&[0; 4]);
+            let length = encode_constant_u32(writer, level, len, 1)?;
+            writer[start..start + 4].copy_from_slice(&(length as i32).to_le_bytes());
+        },
+        Version::V2 => {
+            encode_constant_u32(writer, level, len, 1)?;
+        },
+    }
+    Ok(())
+}
+
 /// writes the def levels to a `Vec<u8>` and returns it.
 pub fn write_def_levels(
     writer: &mut Vec<u8>,
@@ -49,12 +69,30 @@ pub fn write_def_levels(
 ) -> PolarsResult<()> {
     // encode def levels
     match (is_optional, validity) {
-        (true, Some(validity)) => encode_iter(writer, validity.iter(), version),
-        (true, None) => encode_iter(writer, std::iter::repeat(true).take(len), version),
+        // e.g. a `NullArray`
+        (true, Some(validity)) if validity.unset_bits() == len => {
+            encode_constant(writer, 0, len, version)
+        },
+        (true, Some(validity)) if validity.unset_bits() > 0 => {
+            encode_iter(writer, validity.iter(), version)
+        },
+        (true, _) => encode_constant(writer, 1, len, version),
         _ => Ok(()), // is required => no def levels
     }
 }
 
+/// Returns the buffer of a data page, which starts with the
+/// [`WriteOptions::level_stream_prefix`] so that the levels are encoded after it.
+pub fn page_buffer(options: &WriteOptions) -> Vec<u8> {
+    options.level_stream_prefix.clone().unwrap_or_default()
+}
+
+fn prefix_len(options: &WriteOptions) -> usize {
+    options.level_stream_prefix.as_ref().map_or(0, Vec::len)
+}
+
+/// Builds a data page from `buffer`, which starts with the [`WriteOptions::level_stream_prefix`]
+/// (see [`page_buffer`]) followed by levels of the given byte lengths and the values.
 #[allow(clippy::too_many_arguments)]
 pub fn build_plain_page(
     buffer: Vec<u8>,
@@ -68,6 +106,9 @@ pub fn build_plain_page(
     options: WriteOptions,
     encoding: Encoding,
 ) -> PolarsResult<DataPage> {
+    // the prefix at the start of `buffer` is accounted as part of the repetition levels
+    let repetition_levels_byte_length = repetition_levels_byte_length + prefix_len(&options);
+
     let header = match options.version {
         Version::V1 => DataPageHeader::V1(DataPageHeaderV1 {
             num_values: num_values as i32,
@@ -140,6 +181,8 @@ impl<T, I: Iterator<Item = T>> Iterator for ExactSizedIter<T, I> {
     }
 }
 
+impl<T, I: Iterator<Item = T>> ExactSizeIterator for ExactSizedIter<T, I> {}
+
 /// Returns the number of bits needed to bitpack `max`
 #[inline]
 pub fn get_bit_width(max: u64) -> u32 {
@@ -153,3 +196,64 @@ pub(super) fn invalid_encoding(encoding: Encoding, data_type: &ArrowDataType) ->
         encoding
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::Int32Array;
+
+    use super::super::pages::ListNested;
+    use super::super::{array_to_page, Nested};
+    use super::*;
+    use crate::parquet::page::{split_buffer, Page};
+    use crate::parquet::schema::types::PhysicalType;
+
+    fn page(options: WriteOptions, nested: bool) -> DataPage {
+        let array = Int32Array::from([Some(1), None, Some(3)]);
+        let nested = if nested {
+            vec![
+                Nested::List(ListNested::new(
+                    vec![0i32, 2, 3].try_into().unwrap(),
+                    None,
+                    true,
+                )),
+                Nested::Primitive(array.validity().cloned(), true, 3),
+            ]
+        } else {
+            vec![Nested::Primitive(array.validity().cloned(), true, 3)]
+        };
+        let type_ = PrimitiveType::from_physical("a".to_string(), PhysicalType::Int32);
+        match array_to_page(&array, type_, &nested, options, Encoding::Plain).unwrap() {
+            Page::Data(page) => page,
+            Page::Dict(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_level_stream_prefix() {
+        let options = WriteOptions::builder()
+            .write_statistics(false)
+            .version(Version::V2);
+        let prefix = vec![0xAB, 0xCD];
+
+        for nested in [true, false] {
+            let expected = page(options.clone().build().unwrap(), nested);
+            let result = page(
+                options
+                    .clone()
+                    .level_stream_prefix(Some(prefix.clone()))
+                    .build()
+                    .unwrap(),
+                nested,
+            );
+
+            assert_eq!(&result.buffer()[..prefix.len()], prefix);
+            assert_eq!(&result.buffer()[prefix.len()..], expected.buffer());
+
+            let (_, expected_def, expected_values) = split_buffer(&expected).unwrap();
+            let (rep, def, values) = split_buffer(&result).unwrap();
+            assert_eq!(&rep[..prefix.len()], prefix);
+            assert_eq!(def, expected_def);
+            assert_eq!(values, expected_values);
+        }
+    }
+}
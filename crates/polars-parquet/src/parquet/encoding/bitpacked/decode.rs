@@ -1,4 +1,6 @@
+use super::encode::limb_widths;
 use super::{Packed, Unpackable, Unpacked};
+use crate::parquet::encoding::ceil8;
 use crate::parquet::error::Error;
 
 /// An [`Iterator`] of [`Unpackable`] unpacked from a bitpacked slice of bytes.
@@ -84,6 +86,35 @@ impl<'a, T: Unpackable> Iterator for Decoder<'a, T> {
     }
 }
 
+/// Unpacks `length` values of `num_bits` bits packed by [`pack_wide`](super::pack_wide).
+///
+/// # Errors
+/// Errors iff `num_bits` is larger than 128 or `packed` is too short.
+pub fn unpack_wide(packed: &[u8], num_bits: usize, length: usize) -> Result<Vec<u128>, Error> {
+    if num_bits > 128 {
+        return Err(Error::oos(format!(
+            "Unpacking wide values requires num_bits <= 128, got {num_bits}"
+        )));
+    }
+    let mut unpacked = vec![0u128; length];
+    let mut offset = 0;
+    for (i, limb_bits) in limb_widths(num_bits).enumerate() {
+        let size = ceil8(length * limb_bits);
+        let stream = packed.get(offset..offset + size).ok_or_else(|| {
+            Error::oos(format!(
+                "Unpacking {length} items with a number of bits {num_bits} requires more than {} bytes.",
+                packed.len()
+            ))
+        })?;
+        let limbs = Decoder::<u64>::try_new(stream, limb_bits, length)?;
+        for (value, limb) in unpacked.iter_mut().zip(limbs) {
+            *value |= (limb as u128) << (i * 64);
+        }
+        offset += size;
+    }
+    Ok(unpacked)
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::tests::case1;
@@ -1,4 +1,5 @@
 use super::{Packed, Unpackable, Unpacked};
+use crate::parquet::encoding::ceil8;
 
 /// Encodes (packs) a slice of [`Unpackable`] into bitpacked bytes `packed`, using `num_bits` per value.
 ///
@@ -42,7 +43,7 @@ pub fn encode<T: Unpackable>(unpacked: &[T], num_bits: usize, packed: &mut [u8])
 /// Only the first `ceil8(unpacked.len() * num_bits)` of `packed` are populated.
 #[inline]
 pub fn encode_pack<T: Unpackable>(unpacked: &[T], num_bits: usize, packed: &mut [u8]) {
-    if unpacked.len() < T::Packed::LENGTH {
+    if unpacked.len() < T::Unpacked::LENGTH {
         let mut complete_unpacked = T::Unpacked::zero();
         complete_unpacked.as_mut()[..unpacked.len()].copy_from_slice(unpacked);
         T::pack(&complete_unpacked, num_bits, packed)
@@ -50,3 +51,66 @@ pub fn encode_pack<T: Unpackable>(unpacked: &[T], num_bits: usize, packed: &mut
         T::pack(&unpacked.try_into().unwrap(), num_bits, packed)
     }
 }
+
+/// Packs `unpacked`, a slice of at most one pack of [`Unpackable`], into `packed` using
+/// `num_bits` per value, padding it with zeros to a complete pack.
+///
+/// Returns the number of bytes written to `packed`, `ceil8(unpacked.len() * num_bits)`.
+/// # Panics
+/// This function panics iff `unpacked.len()` is larger than a pack or `packed` is smaller
+/// than the number of bytes written.
+pub fn pack_slice<T: Unpackable>(unpacked: &[T], packed: &mut [u8], num_bits: usize) -> usize {
+    assert!(
+        unpacked.len() <= T::Unpacked::LENGTH,
+        "pack_slice expects at most {} values, got {}",
+        T::Unpacked::LENGTH,
+        unpacked.len()
+    );
+    let length = ceil8(unpacked.len() * num_bits);
+
+    let mut complete_unpacked = T::Unpacked::zero();
+    complete_unpacked.as_mut()[..unpacked.len()].copy_from_slice(unpacked);
+    let mut complete_packed = T::Packed::zero();
+    T::pack(&complete_unpacked, num_bits, complete_packed.as_mut());
+
+    packed[..length].copy_from_slice(&complete_packed.as_ref()[..length]);
+    length
+}
+
+/// Returns the bit widths of the 64-bit limbs of values of `num_bits` bits, from the least to
+/// the most significant: all of them are 64 bits but the last.
+pub(super) fn limb_widths(num_bits: usize) -> impl Iterator<Item = usize> {
+    (0..num_bits.div_ceil(64)).map(move |i| (num_bits - i * 64).min(64))
+}
+
+/// Packs `unpacked` into `packed` using `num_bits` per value, for bit widths larger than the 64
+/// bits of [`encode`].
+///
+/// Every value is split into limbs of 64 bits, from the least significant one, the last limb
+/// holding the remaining bits, and the stream of each limb is bitpacked after the stream of the
+/// previous one. Each stream takes `ceil8(unpacked.len() * limb_bits)` bytes. Use
+/// [`unpack_wide`](super::unpack_wide) to unpack them.
+///
+/// This function assumes that the values in `unpacked` fit in `num_bits` bits.
+///
+/// Returns the number of bytes written to `packed`.
+/// # Panics
+/// This function panics iff `num_bits` is larger than 128 or `packed` is smaller than the
+/// number of bytes written.
+pub fn pack_wide(unpacked: &[u128], num_bits: usize, packed: &mut [u8]) -> usize {
+    assert!(
+        num_bits <= 128,
+        "pack_wide: num_bits {num_bits} exceeds the bit width 128"
+    );
+    let mut written = 0;
+    let mut limbs = [0u64; 64];
+    for (i, limb_bits) in limb_widths(num_bits).enumerate() {
+        for chunk in unpacked.chunks(limbs.len()) {
+            for (limb, value) in limbs.iter_mut().zip(chunk) {
+                *limb = (value >> (i * 64)) as u64;
+            }
+            written += pack_slice(&limbs[..chunk.len()], &mut packed[written..], limb_bits);
+        }
+    }
+    written
+}
@@ -3,8 +3,10 @@ mod encode;
 mod pack;
 mod unpack;
 
-pub use decode::Decoder;
-pub use encode::{encode, encode_pack};
+pub use decode::{unpack_wide, Decoder};
+pub use encode::{encode, encode_pack, pack_slice, pack_wide};
+pub use pack::{pack16, pack32, pack64, pack8};
+pub use unpack::{unpack16, unpack32, unpack64, unpack8};
 
 /// A byte slice (e.g. `[u8; 8]`) denoting types that represent complete packs.
 pub trait Packed:
@@ -217,4 +219,68 @@ mod tests {
 
         assert_eq!(&packed[..3], expected);
     }
+
+    #[test]
+    fn test_pack_slice() {
+        let values = (0..32u32).map(|x| (x * 7) % 32).collect::<Vec<_>>();
+        for num_bits in [1, 5, 11, 32] {
+            let values = values
+                .iter()
+                .map(|x| x & ((1u64 << num_bits) - 1) as u32)
+                .collect::<Vec<_>>();
+            for length in 1..=32 {
+                let mut packed = vec![0u8; 4 * 32];
+                let written = pack_slice(&values[..length], &mut packed, num_bits);
+                assert_eq!(written, (length * num_bits).div_ceil(8));
+
+                let mut unpacked = [0u32; 32];
+                unpack::unpack32(&packed, &mut unpacked, num_bits);
+                assert_eq!(&unpacked[..length], &values[..length]);
+                assert!(unpacked[length..].iter().all(|x| *x == 0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_pack8_public() {
+        let values = [0u8, 1, 2, 3, 4, 5, 6, 7];
+        let mut packed = [0u8; 3];
+        pack8(&values, &mut packed, 3);
+        assert_eq!(packed, [0b10001000u8, 0b11000110, 0b11111010]);
+
+        let mut unpacked = [0u8; 8];
+        unpack8(&packed, &mut unpacked, 3);
+        assert_eq!(unpacked, values);
+    }
+
+    #[test]
+    fn test_pack_wide() {
+        // a deterministic sequence of 128-bit values
+        let values = (0..200u128)
+            .map(|x| (x + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15_F39C_C060_5CED_C835))
+            .collect::<Vec<_>>();
+        for num_bits in [0, 7, 64, 65, 96, 127, 128] {
+            let mask = u128::MAX.checked_shr(128 - num_bits as u32).unwrap_or(0);
+            let values = values.iter().map(|x| x & mask).collect::<Vec<_>>();
+            for length in [0, 1, 63, 64, 65, 200] {
+                let mut packed = vec![0u8; 2 * 16 * 200];
+                let written = pack_wide(&values[..length], num_bits, &mut packed);
+                let expected =
+                    (num_bits / 64) * length * 8 + (length * (num_bits % 64)).div_ceil(8);
+                assert_eq!(written, expected);
+
+                let unpacked = unpack_wide(&packed[..written], num_bits, length).unwrap();
+                assert_eq!(
+                    unpacked,
+                    &values[..length],
+                    "{num_bits} bits, {length} values"
+                );
+            }
+        }
+
+        let mut packed = vec![0u8; 2 * 16];
+        let written = pack_wide(&[u128::MAX, 1], 128, &mut packed);
+        assert!(unpack_wide(&packed[..written - 1], 128, 2).is_err());
+        assert!(unpack_wide(&packed, 129, 2).is_err());
+    }
 }
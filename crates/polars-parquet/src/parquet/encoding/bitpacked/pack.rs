@@ -56,8 +56,30 @@ macro_rules! pack {
             pack_impl!($t, $bytes, $bits);
         }
 
-        /// Pack unpacked `input` into `output` with a bit width of `num_bits`
+        /// Pack unpacked `input` into `output` with a bit width of `num_bits`.
+        ///
+        /// `output` must hold at least `ceil8(input.len() * num_bits)` bytes; since `input` is a
+        /// complete pack, this is `num_bits * size_of::<T>()`.
+        ///
+        /// # Panics
+        /// If `num_bits` exceeds the bit width of the type or `output` is too short.
         pub fn $name(input: &[$t; $bits], output: &mut [u8], num_bits: usize) {
+            assert!(
+                num_bits <= $bits,
+                "{}: num_bits {} exceeds the bit width {}",
+                stringify!($name),
+                num_bits,
+                $bits
+            );
+            let required = num_bits * $bytes;
+            assert!(
+                output.len() >= required,
+                "{}: output of {} bytes is too short, {} bytes are required for num_bits {}",
+                stringify!($name),
+                output.len(),
+                required,
+                num_bits
+            );
             // This will get optimised into a jump table
             seq_macro::seq!(i in 0..=$bits {
                 if i == num_bits {
@@ -105,4 +127,20 @@ mod tests {
             assert_eq!(other, input);
         }
     }
+
+    #[test]
+    #[should_panic(expected = "pack32: output of 15 bytes is too short, 16 bytes are required")]
+    fn test_short_output() {
+        let input = [1u32; 32];
+        let mut output = [0u8; 15];
+        pack32(&input, &mut output, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "pack8: num_bits 9 exceeds the bit width 8")]
+    fn test_invalid_num_bits() {
+        let input = [1u8; 8];
+        let mut output = [0u8; 16];
+        pack8(&input, &mut output, 9);
+    }
 }
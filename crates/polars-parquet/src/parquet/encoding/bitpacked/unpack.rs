@@ -76,7 +76,10 @@ macro_rules! unpack {
             unpack_impl!($t, $bytes, $bits);
         }
 
-        /// Unpack packed `input` into `output` with a bit width of `num_bits`
+        /// Unpack packed `input` into `output` with a bit width of `num_bits`.
+        ///
+        /// `input` must hold at least `ceil8(output.len() * num_bits)` bytes; since `output` is a
+        /// complete pack, this is `num_bits * size_of::<T>()`.
         pub fn $name(input: &[u8], output: &mut [$t; $bits], num_bits: usize) {
             // This will get optimised into a jump table
             seq_macro::seq!(i in 0..=$bits {
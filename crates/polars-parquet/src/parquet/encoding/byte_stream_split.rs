@@ -0,0 +1,74 @@
+//! Encodes and decodes according to [Byte stream split](https://github.com/apache/parquet-format/blob/master/Encodings.md#byte-stream-split-byte_stream_split--9):
+//! byte `k` of every value is written to stream `k`, and the streams are concatenated.
+use crate::parquet::error::Error;
+use crate::parquet::types::NativeType;
+
+/// Encodes `values` as byte stream split, appending the result to `buffer`.
+pub fn encode<T: NativeType>(values: impl ExactSizeIterator<Item = T>, buffer: &mut Vec<u8>) {
+    let size = std::mem::size_of::<T>();
+    let length = values.len();
+
+    let start = buffer.len();
+    buffer.resize(start + length * size, 0);
+    let streams = &mut buffer[start..];
+
+    for (i, value) in values.enumerate() {
+        for (k, byte) in value.to_le_bytes().as_ref().iter().enumerate() {
+            streams[k * length + i] = *byte;
+        }
+    }
+}
+
+/// Decodes byte stream split `values` back into values of type `T`.
+pub fn decode<T: NativeType>(values: &[u8]) -> Result<Vec<T>, Error> {
+    let size = std::mem::size_of::<T>();
+    if values.len() % size != 0 {
+        return Err(Error::oos(
+            "A byte stream split page must have a length multiple of the type's size",
+        ));
+    }
+    let length = values.len() / size;
+
+    let mut bytes = vec![0u8; size];
+    Ok((0..length)
+        .map(|i| {
+            for (k, byte) in bytes.iter_mut().enumerate() {
+                *byte = values[k * length + i];
+            }
+            T::from_le_bytes(bytes.as_slice().try_into().unwrap())
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout() {
+        let data = [1.0f32, -2.5];
+
+        let mut buffer = vec![];
+        encode(data.iter().copied(), &mut buffer);
+
+        // 1.0 = 0x3F800000, -2.5 = 0xC0200000, in little-endian byte planes.
+        assert_eq!(buffer, vec![0x00, 0x00, 0x00, 0x00, 0x80, 0x20, 0x3F, 0xC0]);
+    }
+
+    #[test]
+    fn roundtrip() -> Result<(), Error> {
+        let data = (0..100).map(|x| x as f64 * 1.5 - 30.0).collect::<Vec<_>>();
+
+        let mut buffer = vec![1, 2, 3];
+        encode(data.iter().copied(), &mut buffer);
+        assert_eq!(buffer.len(), 3 + data.len() * 8);
+
+        assert_eq!(decode::<f64>(&buffer[3..])?, data);
+        Ok(())
+    }
+
+    #[test]
+    fn truncated() {
+        assert!(decode::<f32>(&[0, 1, 2]).is_err());
+    }
+}
@@ -67,11 +67,12 @@ impl<'a> Iterator for BitmapIter<'a> {
     }
 }
 
-/// Writes an iterator of bools into writer, with LSB first.
+/// Writes an iterator of bools into writer, with LSB first. Returns the number of bytes
+/// written, i.e. one per 8 values. Nothing is written for an empty iterator.
 pub fn encode_bool<W: Write, I: Iterator<Item = bool>>(
     writer: &mut W,
     mut iterator: I,
-) -> std::io::Result<()> {
+) -> std::io::Result<usize> {
     // the length of the iterator.
     let length = iterator.size_hint().1.unwrap();
 
@@ -95,8 +96,9 @@ pub fn encode_bool<W: Write, I: Iterator<Item = bool>>(
                 last = set(last, i)
             }
         });
-        writer.write_all(&[last])
+        writer.write_all(&[last])?;
+        Ok(chunks + 1)
     } else {
-        Ok(())
+        Ok(chunks)
     }
 }
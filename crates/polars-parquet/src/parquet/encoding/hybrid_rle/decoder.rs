@@ -1,6 +1,8 @@
+use std::io::Read;
+
 use polars_utils::slice::GetSaferUnchecked;
 
-use super::super::{ceil8, uleb128};
+use super::super::{bitpacked, ceil8, uleb128};
 use super::HybridEncoded;
 use crate::parquet::error::Error;
 
@@ -59,9 +61,70 @@ impl<'a> Iterator for Decoder<'a> {
     }
 }
 
+/// Decodes `num_values` values of `num_bits` bits from the hybrid-RLE stream in `reader`.
+///
+/// Unlike [`super::HybridRleDecoder`], this errors if the stream ends before `num_values`
+/// values were decoded, or if `num_bits` is larger than 32.
+pub fn decode_u32<R: Read>(
+    reader: &mut R,
+    num_bits: u32,
+    num_values: usize,
+) -> Result<Vec<u32>, Error> {
+    if num_bits > 32 {
+        return Err(Error::oos(format!(
+            "A bit width of {num_bits} is too large to decode u32 values"
+        )));
+    }
+    if num_bits == 0 {
+        return Ok(vec![0; num_values]);
+    }
+    let num_bits = num_bits as usize;
+
+    let mut data = vec![];
+    reader.read_to_end(&mut data)?;
+
+    let mut values = Vec::with_capacity(num_values);
+    let mut decoder = Decoder::new(&data, num_bits);
+    while values.len() < num_values {
+        let remaining = num_values - values.len();
+        match decoder.next().transpose()? {
+            Some(HybridEncoded::Bitpacked(packed)) => {
+                let length = std::cmp::min(packed.len() * 8 / num_bits, remaining);
+                values.extend(bitpacked::Decoder::<u32>::try_new(
+                    packed, num_bits, length,
+                )?);
+            },
+            Some(HybridEncoded::Rle(pack, run_length)) => {
+                let mut bytes = [0u8; std::mem::size_of::<u32>()];
+                bytes[..pack.len()].copy_from_slice(pack);
+                let value = u32::from_le_bytes(bytes);
+                values.extend(std::iter::repeat(value).take(std::cmp::min(run_length, remaining)));
+            },
+            None => {
+                return Err(Error::oos(format!(
+                    "The hybrid-RLE stream ended after {} of {num_values} values",
+                    values.len()
+                )))
+            },
+        }
+    }
+    Ok(values)
+}
+
+/// Decodes `num_values` booleans from the hybrid-RLE stream in `reader`, i.e. a stream of
+/// bit width 1.
+///
+/// Like [`decode_u32`], this errors if the stream ends before `num_values` values were decoded.
+pub fn decode_bool<R: Read>(reader: &mut R, num_values: usize) -> Result<Vec<bool>, Error> {
+    Ok(decode_u32(reader, 1, num_values)?
+        .into_iter()
+        .map(|x| x != 0)
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::super::super::bitpacked;
+    use super::super::encode;
     use super::*;
 
     #[test]
@@ -135,4 +198,29 @@ mod tests {
             panic!()
         };
     }
+
+    #[test]
+    fn decode_u32_roundtrip() {
+        // a run, a literal group and a trailing run
+        let values = [[5u32; 20].as_slice(), &[0, 1, 2, 3, 4, 5, 6, 7, 1], &[2; 9]].concat();
+
+        let mut buffer = vec![];
+        encode::<u32, _, _>(&mut buffer, values.iter().copied(), 3).unwrap();
+
+        let decoded = decode_u32(&mut buffer.as_slice(), 3, values.len()).unwrap();
+        assert_eq!(decoded, values);
+
+        // a stream ending on a run has no padding to decode
+        let mut buffer = vec![];
+        encode::<u32, _, _>(&mut buffer, std::iter::repeat(5).take(20), 3).unwrap();
+        assert!(decode_u32(&mut buffer.as_slice(), 3, 21).is_err());
+        assert_eq!(decode_u32(&mut [].as_slice(), 0, 3).unwrap(), vec![0; 3]);
+    }
+
+    #[test]
+    fn decode_u32_invalid_bit_width() {
+        // a run of 2 values, whose 40-bit value does not fit a u32
+        let buffer = [2 << 1, 1, 2, 3, 4, 5];
+        assert!(decode_u32(&mut buffer.as_slice(), 40, 2).is_err());
+    }
 }
@@ -3,14 +3,21 @@ use std::io::Write;
 use super::bitpacked_encode;
 use crate::parquet::encoding::{bitpacked, ceil8, uleb128};
 
+/// The default minimum number of repeated values written as an RLE run.
+pub const DEFAULT_RLE_THRESHOLD: usize = 8;
+
+/// The default maximum number of values in a literal (bitpacked) run.
 // Arbitrary value that balances memory usage and storage overhead
-const MAX_VALUES_PER_LITERAL_RUN: usize = (1 << 10) * 8;
+pub const MAX_VALUES_PER_LITERAL_RUN: usize = (1 << 10) * 8;
 
 pub trait Encoder<T: PartialEq + Default + Copy> {
+    /// Writes a bitpacked run. When `pad_to_block` is set, the run is padded with zeros to a
+    /// multiple of 32 values. This is only valid for the last run of a stream.
     fn bitpacked_encode<W: Write, I: Iterator<Item = T>>(
         writer: &mut W,
         iterator: I,
         num_bits: usize,
+        pad_to_block: bool,
     ) -> std::io::Result<()>;
 
     fn run_length_encode<W: Write>(
@@ -28,19 +35,25 @@ impl Encoder<u32> for u32 {
         writer: &mut W,
         mut iterator: I,
         num_bits: usize,
+        pad_to_block: bool,
     ) -> std::io::Result<()> {
         // the length of the iterator.
         let length = iterator.size_hint().1.unwrap();
 
-        let mut header = ceil8(length) as u64;
+        let chunks = length / U32_BLOCK_LEN;
+        let remainder = length - chunks * U32_BLOCK_LEN;
+        let pad_to_block = pad_to_block && remainder != 0;
+
+        let mut header = if pad_to_block {
+            ((chunks + 1) * U32_BLOCK_LEN / 8) as u64
+        } else {
+            ceil8(length) as u64
+        };
         header <<= 1;
         header |= 1; // it is bitpacked => first bit is set
         let mut container = [0; 10];
         let used = uleb128::encode(header, &mut container);
         writer.write_all(&container[..used])?;
-
-        let chunks = length / U32_BLOCK_LEN;
-        let remainder = length - chunks * U32_BLOCK_LEN;
         let mut buffer = [0u32; U32_BLOCK_LEN];
 
         // simplified from ceil8(U32_BLOCK_LEN * num_bits) since U32_BLOCK_LEN = 32
@@ -59,13 +72,6 @@ impl Encoder<u32> for u32 {
         }
 
         if remainder != 0 {
-            // Must be careful here to ensure we write a multiple of `num_bits`
-            // (the bit width) to align with the spec. Some readers also rely on
-            // this - see https://github.com/pola-rs/polars/pull/13883.
-
-            // this is ceil8(remainder * num_bits), but we ensure the output is a
-            // multiple of num_bits by rewriting it as ceil8(remainder) * num_bits
-            let compressed_remainder_size = ceil8(remainder) * num_bits;
             iterator
                 .by_ref()
                 .take(remainder)
@@ -73,15 +79,21 @@ impl Encoder<u32> for u32 {
                 .for_each(|(item, buf)| *buf = item);
 
             let mut packed = [0u8; 4 * U32_BLOCK_LEN];
-            // No need to zero rest of buffer because remainder is either:
-            // * Multiple of 8: We pad non-terminal literal runs to have a
-            //   multiple of 8 values. Once compressed, the data will end on
-            //   clean byte boundaries and packed[..compressed_remainder_size]
-            //   will include only the remainder values and nothing extra.
-            // * Final run: Extra values from buffer will be included in
-            //   packed[..compressed_remainder_size] but ignored when decoding
-            //   because they extend beyond known column length
-            bitpacked::encode_pack(&buffer, num_bits, packed.as_mut());
+            bitpacked::pack_slice(&buffer[..remainder], &mut packed, num_bits);
+
+            let compressed_remainder_size = if pad_to_block {
+                // Pad the last block with zeros, which readers ignore since they extend
+                // beyond the known column length.
+                compressed_chunk_size
+            } else {
+                // Must be careful here to ensure we write a multiple of `num_bits`
+                // (the bit width) to align with the spec. Some readers also rely on
+                // this - see https://github.com/pola-rs/polars/pull/13883.
+
+                // this is ceil8(remainder * num_bits), but we ensure the output is a
+                // multiple of num_bits by rewriting it as ceil8(remainder) * num_bits
+                ceil8(remainder) * num_bits
+            };
             writer.write_all(&packed[..compressed_remainder_size])?;
         };
         Ok(())
@@ -112,17 +124,26 @@ impl Encoder<bool> for bool {
         writer: &mut W,
         iterator: I,
         _num_bits: usize,
+        pad_to_block: bool,
     ) -> std::io::Result<()> {
         // the length of the iterator.
         let length = iterator.size_hint().1.unwrap();
 
-        let mut header = ceil8(length) as u64;
+        let num_groups = if pad_to_block {
+            length.div_ceil(U32_BLOCK_LEN) * U32_BLOCK_LEN / 8
+        } else {
+            ceil8(length)
+        };
+        let mut header = num_groups as u64;
         header <<= 1;
         header |= 1; // it is bitpacked => first bit is set
         let mut container = [0; 10];
         let used = uleb128::encode(header, &mut container);
         writer.write_all(&container[..used])?;
         bitpacked_encode(writer, iterator)?;
+        // a group of 8 booleans is a single byte
+        let padding = num_groups - ceil8(length);
+        writer.write_all(&[0; U32_BLOCK_LEN / 8][..padding])?;
         Ok(())
     }
 
@@ -143,23 +164,311 @@ impl Encoder<bool> for bool {
     }
 }
 
+/// Writes `run_length` repetitions of `value` as RLE runs of at most `max_run_length` values.
+fn run_length_encode<T: PartialEq + Default + Copy + Encoder<T>, W: Write>(
+    writer: &mut W,
+    mut run_length: usize,
+    value: T,
+    num_bits: u32,
+    max_run_length: usize,
+) -> std::io::Result<()> {
+    while run_length > 0 {
+        let length = run_length.min(max_run_length);
+        T::run_length_encode(writer, length, value, num_bits)?;
+        run_length -= length;
+    }
+    Ok(())
+}
+
+/// Writes the buffered `values` as a literal run. With `min_embedded_run`, aligned groups of 8
+/// equal values spanning at least that many values are written as RLE runs instead, splitting
+/// the literal run around them.
+///
+/// `values` must be a multiple of 8 values unless they end the stream, and only the last literal
+/// run written is padded to a block.
+fn encode_literal_run<T: PartialEq + Default + Copy + Encoder<T>, W: Write>(
+    writer: &mut W,
+    mut values: &[T],
+    num_bits: u32,
+    pad_to_block: bool,
+    min_embedded_run: Option<usize>,
+    max_run_length: usize,
+) -> std::io::Result<()> {
+    if let Some(min_embedded_run) = min_embedded_run {
+        // runs are only split out on groups of 8 values, so that the literal runs around them
+        // stay a multiple of 8 values
+        let mut start = 0;
+        let mut group = 0;
+        while group + 8 <= values.len() {
+            let value = values[group];
+            let mut end = group;
+            while end + 8 <= values.len() && values[end..end + 8].iter().all(|x| *x == value) {
+                end += 8;
+            }
+            if end - group >= min_embedded_run {
+                if group > start {
+                    T::bitpacked_encode(
+                        writer,
+                        values[start..group].iter().copied(),
+                        num_bits as usize,
+                        false,
+                    )?;
+                }
+                run_length_encode(writer, end - group, value, num_bits, max_run_length)?;
+                start = end;
+            }
+            group = end.max(group + 8);
+        }
+        values = &values[start..];
+    }
+    if !values.is_empty() {
+        T::bitpacked_encode(
+            writer,
+            values.iter().copied(),
+            num_bits as usize,
+            pad_to_block,
+        )?;
+    }
+    Ok(())
+}
+
+/// A [`Write`] that counts the bytes written through it.
+struct CountingWriter<'a, W: Write> {
+    writer: &'a mut W,
+    written: usize,
+}
+
+impl<W: Write> Write for CountingWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        self.written += written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// A hybrid RLE encoder that owns its literal-run buffer.
+///
+/// [`encode`] initializes this buffer on the stack on every call; writers that produce many
+/// small pages should keep a [`HybridRleEncoder`] around and reuse it instead.
+#[derive(Debug, Clone)]
+pub struct HybridRleEncoder<T> {
+    buffered_bits: Vec<T>,
+    options: RunOptions,
+}
+
+/// How the values are split into RLE and bitpacked runs.
+#[derive(Debug, Clone, Copy)]
+struct RunOptions {
+    pad_bitpacked_to_block: bool,
+    max_run_length: usize,
+    rle_threshold: usize,
+    min_embedded_run: Option<usize>,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            pad_bitpacked_to_block: false,
+            max_run_length: usize::MAX,
+            rle_threshold: DEFAULT_RLE_THRESHOLD,
+            min_embedded_run: None,
+        }
+    }
+}
+
+impl<T: PartialEq + Default + Copy + Encoder<T>> Default for HybridRleEncoder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialEq + Default + Copy + Encoder<T>> HybridRleEncoder<T> {
+    /// Returns a new [`HybridRleEncoder`]
+    pub fn new() -> Self {
+        Self {
+            buffered_bits: vec![T::default(); MAX_VALUES_PER_LITERAL_RUN],
+            options: RunOptions::default(),
+        }
+    }
+
+    /// Returns a new [`HybridRleEncoder`] whose literal runs hold at most `max_literal_run`
+    /// values. A larger cap reduces the number of bitpacked runs (and their headers), a
+    /// smaller one reduces memory usage.
+    /// # Errors
+    /// Errors iff `max_literal_run` is not a positive multiple of 8.
+    pub fn try_with_max_literal_run(max_literal_run: usize) -> std::io::Result<Self> {
+        if max_literal_run == 0 || max_literal_run % 8 != 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "the maximum literal run length must be a positive multiple of 8, got {}",
+                    max_literal_run
+                ),
+            ));
+        }
+        Ok(Self {
+            buffered_bits: vec![T::default(); max_literal_run],
+            options: RunOptions::default(),
+        })
+    }
+
+    /// Sets whether a trailing bitpacked run is padded with zeros to a full block of 32
+    /// values, for readers that expect the bitpacked section to be a multiple of the block
+    /// size. Padding values are ignored when decoding.
+    pub fn with_pad_bitpacked_to_block(mut self, pad_bitpacked_to_block: bool) -> Self {
+        self.options.pad_bitpacked_to_block = pad_bitpacked_to_block;
+        self
+    }
+
+    /// Sets the maximum number of values of a single RLE run. Longer runs are split into
+    /// consecutive runs of at most `max_run_length` values, for readers that limit the
+    /// length of a run.
+    /// # Errors
+    /// Errors iff `max_run_length` is 0.
+    pub fn try_with_max_run_length(mut self, max_run_length: usize) -> std::io::Result<Self> {
+        if max_run_length == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "the maximum run length must be positive",
+            ));
+        }
+        self.options.max_run_length = max_run_length;
+        Ok(self)
+    }
+
+    /// Sets the number of repeated values above which they are written as an RLE run instead
+    /// of being part of a literal run (8 by default). A larger threshold writes fewer, longer
+    /// literal runs. Runs never exceed the maximum literal run, so a threshold at least as
+    /// large writes no RLE run.
+    /// # Errors
+    /// Errors iff `rle_threshold` is not a positive multiple of 8, which keeps literal runs
+    /// a multiple of 8 values.
+    pub fn try_with_rle_threshold(mut self, rle_threshold: usize) -> std::io::Result<Self> {
+        if rle_threshold == 0 || rle_threshold % 8 != 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "the RLE threshold must be a positive multiple of 8, got {}",
+                    rle_threshold
+                ),
+            ));
+        }
+        self.options.rle_threshold = rle_threshold;
+        Ok(self)
+    }
+
+    /// Enables an aggressive RLE mode, where the literal runs are scanned before being written
+    /// and repeats of at least `min_run_length` values within them are written as RLE runs.
+    /// Only repeats covering aligned groups of 8 values are split out. This finds shorter
+    /// runs than the RLE threshold at the cost of scanning every literal run twice, and only
+    /// pays off for bit widths large enough to outweigh the additional run headers.
+    /// # Errors
+    /// Errors iff `min_run_length` is not a positive multiple of 8.
+    pub fn try_with_aggressive_rle(mut self, min_run_length: usize) -> std::io::Result<Self> {
+        if min_run_length == 0 || min_run_length % 8 != 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "the minimum embedded run length must be a positive multiple of 8, got {}",
+                    min_run_length
+                ),
+            ));
+        }
+        self.options.min_embedded_run = Some(min_run_length);
+        Ok(self)
+    }
+
+    /// Resets the buffered literal run, keeping its allocation.
+    pub fn reset(&mut self) {
+        self.buffered_bits.fill(T::default());
+    }
+
+    /// Writes `iterator` to `writer` with the literal-run buffer and the options of this
+    /// encoder.
+    fn encode<W: Write, I: Iterator<Item = T>>(
+        &mut self,
+        writer: &mut W,
+        iterator: I,
+        num_bits: u32,
+    ) -> std::io::Result<usize> {
+        encode_runs(
+            writer,
+            iterator,
+            num_bits,
+            &mut self.buffered_bits,
+            &self.options,
+        )
+    }
+}
+
+impl HybridRleEncoder<u32> {
+    /// Writes `iterator` to `writer` as hybrid RLE with a bit width of `num_bits`. Returns the
+    /// number of bytes written.
+    ///
+    /// With `num_bits == 0` the values are not inspected and a single RLE run of their count
+    /// is written.
+    ///
+    /// Nothing is written for an empty `iterator`: an empty stream is a valid encoding of
+    /// zero values, and readers must not expect a run header.
+    pub fn encode_u32<W: Write, I: Iterator<Item = u32>>(
+        &mut self,
+        writer: &mut W,
+        iterator: I,
+        num_bits: u32,
+    ) -> std::io::Result<usize> {
+        self.encode(writer, iterator, num_bits)
+    }
+}
+
+impl HybridRleEncoder<bool> {
+    /// [`HybridRleEncoder::encode_u32`] of `bool` values, such as validities.
+    pub fn encode_bool<W: Write, I: Iterator<Item = bool>>(
+        &mut self,
+        writer: &mut W,
+        iterator: I,
+        num_bits: u32,
+    ) -> std::io::Result<usize> {
+        self.encode(writer, iterator, num_bits)
+    }
+}
+
+/// Writes `iterator` to `writer` as hybrid RLE with a bit width of `num_bits`, buffering
+/// literal runs of at most `buffered_bits.len()` values. Returns the number of bytes written.
 #[allow(clippy::comparison_chain)]
-pub fn encode<T: PartialEq + Default + Copy + Encoder<T>, W: Write, I: Iterator<Item = T>>(
+fn encode_runs<T: PartialEq + Default + Copy + Encoder<T>, W: Write, I: Iterator<Item = T>>(
     writer: &mut W,
     iterator: I,
     num_bits: u32,
-) -> std::io::Result<()> {
+    buffered_bits: &mut [T],
+    options: &RunOptions,
+) -> std::io::Result<usize> {
+    let writer = &mut CountingWriter { writer, written: 0 };
+    if num_bits == 0 {
+        // A bit width of 0 can only represent zeros, so the whole stream is a single run
+        // without value bytes.
+        let length = iterator.count();
+        run_length_encode(writer, length, T::default(), 0, options.max_run_length)?;
+        return Ok(writer.written);
+    }
+
+    let max_run_length = options.max_run_length;
+    let threshold = options.rle_threshold;
+    let min_embedded_run = options.min_embedded_run;
+    let max_literal_run = buffered_bits.len();
     let mut consecutive_repeats: usize = 0;
     let mut previous_val = T::default();
-    let mut buffered_bits = [previous_val; MAX_VALUES_PER_LITERAL_RUN];
     let mut buffer_idx = 0;
     let mut literal_run_idx = 0;
     for val in iterator {
         if val == previous_val {
             consecutive_repeats += 1;
-            if consecutive_repeats >= 8 {
+            if consecutive_repeats >= threshold {
                 // Run is long enough to RLE, no need to buffer values
-                if consecutive_repeats > 8 {
+                if consecutive_repeats > threshold {
                     continue;
                 } else {
                     // When we encounter a run long enough to potentially RLE,
@@ -169,23 +478,33 @@ pub fn encode<T: PartialEq + Default + Copy + Encoder<T>, W: Write, I: Iterator<
                     let literal_padding = (8 - (literal_run_idx % 8)) % 8;
                     consecutive_repeats -= literal_padding;
                     literal_run_idx += literal_padding;
+                    debug_assert!(literal_run_idx <= buffer_idx);
                 }
             }
             // Too short to RLE, continue to buffer values
-        } else if consecutive_repeats > 8 {
+        } else if consecutive_repeats > threshold {
             // Value changed so start a new run but the current run is long
             // enough to RLE. First, bit-pack any buffered literal run. Then,
             // RLE current run and reset consecutive repeat counter and buffer.
             if literal_run_idx > 0 {
                 debug_assert!(literal_run_idx % 8 == 0);
-                T::bitpacked_encode(
+                encode_literal_run(
                     writer,
-                    buffered_bits.iter().take(literal_run_idx).copied(),
-                    num_bits as usize,
+                    &buffered_bits[..literal_run_idx],
+                    num_bits,
+                    false,
+                    min_embedded_run,
+                    max_run_length,
                 )?;
                 literal_run_idx = 0;
             }
-            T::run_length_encode(writer, consecutive_repeats, previous_val, num_bits)?;
+            run_length_encode(
+                writer,
+                consecutive_repeats,
+                previous_val,
+                num_bits,
+                max_run_length,
+            )?;
             consecutive_repeats = 1;
             buffer_idx = 0;
         } else {
@@ -196,12 +515,19 @@ pub fn encode<T: PartialEq + Default + Copy + Encoder<T>, W: Write, I: Iterator<
             consecutive_repeats = 1;
         }
         // If buffer is full, bit-pack as literal run and reset
-        if buffer_idx == MAX_VALUES_PER_LITERAL_RUN {
-            T::bitpacked_encode(writer, buffered_bits.iter().copied(), num_bits as usize)?;
+        if buffer_idx == max_literal_run {
+            encode_literal_run(
+                writer,
+                buffered_bits,
+                num_bits,
+                false,
+                min_embedded_run,
+                max_run_length,
+            )?;
             // If buffer fills up in the middle of a run, all but the last
             // repeat is consolidated into the literal run.
             debug_assert!(
-                (consecutive_repeats < 8)
+                (consecutive_repeats < threshold)
                     && (buffer_idx - literal_run_idx == consecutive_repeats - 1)
             );
             consecutive_repeats = 1;
@@ -213,20 +539,199 @@ pub fn encode<T: PartialEq + Default + Copy + Encoder<T>, W: Write, I: Iterator<
         buffer_idx += 1;
     }
     // Final run not long enough to RLE, extend literal run.
-    if consecutive_repeats <= 8 {
+    if consecutive_repeats <= threshold {
         literal_run_idx = buffer_idx;
     }
-    // Bit-pack final buffered literal run, if any
+    // The literal run can only hold values that were buffered. It is sliced rather than
+    // taken from the buffer, so that breaking this panics instead of dropping values.
+    debug_assert!(literal_run_idx <= buffer_idx);
+    // Bit-pack final buffered literal run, if any. It can only be padded when no RLE
+    // run follows it.
     if literal_run_idx > 0 {
-        T::bitpacked_encode(
+        encode_literal_run(
             writer,
-            buffered_bits.iter().take(literal_run_idx).copied(),
-            num_bits as usize,
+            &buffered_bits[..literal_run_idx],
+            num_bits,
+            options.pad_bitpacked_to_block && consecutive_repeats <= threshold,
+            min_embedded_run,
+            max_run_length,
         )?;
     }
     // RLE final consecutive run if long enough
-    if consecutive_repeats > 8 {
-        T::run_length_encode(writer, consecutive_repeats, previous_val, num_bits)?;
+    if consecutive_repeats > threshold {
+        run_length_encode(
+            writer,
+            consecutive_repeats,
+            previous_val,
+            num_bits,
+            max_run_length,
+        )?;
+    }
+    Ok(writer.written)
+}
+
+/// Writes `iterator` to `writer` as hybrid RLE with a bit width of `num_bits`. Returns the
+/// number of bytes written.
+///
+/// Nothing is written for an empty `iterator`. The literal-run buffer lives on the stack.
+pub fn encode<T: PartialEq + Default + Copy + Encoder<T>, W: Write, I: Iterator<Item = T>>(
+    writer: &mut W,
+    iterator: I,
+    num_bits: u32,
+) -> std::io::Result<usize> {
+    let mut buffered_bits = [T::default(); MAX_VALUES_PER_LITERAL_RUN];
+    encode_runs(
+        writer,
+        iterator,
+        num_bits,
+        &mut buffered_bits,
+        &RunOptions::default(),
+    )
+}
+
+/// Writes `iterator` to `writer` as hybrid RLE with a bit width of `num_bits` and literal runs
+/// of at most `max_literal_run` values.
+/// # Errors
+/// Errors iff `max_literal_run` is not a positive multiple of 8 or writing fails.
+pub fn encode_with_max_literal_run<
+    T: PartialEq + Default + Copy + Encoder<T>,
+    W: Write,
+    I: Iterator<Item = T>,
+>(
+    writer: &mut W,
+    iterator: I,
+    num_bits: u32,
+    max_literal_run: usize,
+) -> std::io::Result<usize> {
+    HybridRleEncoder::<T>::try_with_max_literal_run(max_literal_run)?
+        .encode(writer, iterator, num_bits)
+}
+
+/// Writes `iterator` to `writer` as hybrid RLE with a bit width of `num_bits`, splitting RLE
+/// runs longer than `max_run_length` values.
+/// # Errors
+/// Errors iff `max_run_length` is 0 or writing fails.
+pub fn encode_with_max_run_length<
+    T: PartialEq + Default + Copy + Encoder<T>,
+    W: Write,
+    I: Iterator<Item = T>,
+>(
+    writer: &mut W,
+    iterator: I,
+    num_bits: u32,
+    max_run_length: usize,
+) -> std::io::Result<usize> {
+    HybridRleEncoder::<T>::new()
+        .try_with_max_run_length(max_run_length)?
+        .encode(writer, iterator, num_bits)
+}
+
+/// Writes `iterator` to `writer` as hybrid RLE with a bit width of `num_bits`, writing only
+/// runs of more than `rle_threshold` repeated values as RLE runs.
+/// # Errors
+/// Errors iff `rle_threshold` is not a positive multiple of 8 or writing fails.
+pub fn encode_with_rle_threshold<
+    T: PartialEq + Default + Copy + Encoder<T>,
+    W: Write,
+    I: Iterator<Item = T>,
+>(
+    writer: &mut W,
+    iterator: I,
+    num_bits: u32,
+    rle_threshold: usize,
+) -> std::io::Result<usize> {
+    HybridRleEncoder::<T>::new()
+        .try_with_rle_threshold(rle_threshold)?
+        .encode(writer, iterator, num_bits)
+}
+
+/// Writes `count` repetitions of `value` to `writer` as a single RLE run, without iterating
+/// over the values. Returns the number of bytes written.
+///
+/// For `count > 8` this is the same output as [`encode`] over `repeat(value).take(count)`.
+/// Nothing is written when `count == 0`.
+pub fn encode_constant_u32<W: Write>(
+    writer: &mut W,
+    value: u32,
+    count: usize,
+    num_bits: u32,
+) -> std::io::Result<usize> {
+    if count == 0 {
+        return Ok(0);
+    }
+    let mut container = [0; 10];
+    let header_size = uleb128::encode((count as u64) << 1, &mut container);
+    u32::run_length_encode(writer, count, value, num_bits)?;
+    Ok(header_size + ceil8(num_bits as usize))
+}
+
+/// Zigzag-encodes `iterator` and writes it as hybrid RLE. Returns the number of bytes written.
+///
+/// `num_bits` must be large enough to hold the largest zigzag-encoded value, i.e.
+/// `(n << 1) ^ (n >> 31)` reinterpreted as a [`u32`].
+pub fn encode_i32<W: Write, I: Iterator<Item = i32>>(
+    writer: &mut W,
+    iterator: I,
+    num_bits: u32,
+) -> std::io::Result<usize> {
+    let iterator = iterator.map(|n| ((n << 1) ^ (n >> 31)) as u32);
+    encode::<u32, _, _>(writer, iterator, num_bits)
+}
+
+/// Returns the number of bytes of the run at the start of `values`, a hybrid-RLE stream written
+/// with a bit width of `num_bits`.
+fn run_size(values: &[u8], num_bits: usize) -> usize {
+    let (indicator, consumed) = uleb128::decode(values).unwrap();
+    let body = if indicator & 1 == 1 {
+        (indicator as usize >> 1) * num_bits
+    } else {
+        ceil8(num_bits)
+    };
+    (consumed + body).min(values.len())
+}
+
+/// Writes `iterator` to `writer` as hybrid RLE with a bit width of `num_bits`, in pages of at
+/// most `max_page_bytes` bytes. `on_page` is called with the size of every page once it was
+/// written.
+///
+/// Pages are only split between runs, so that the concatenation of all pages is the same
+/// stream as [`encode`] with literal runs short enough to fit a page. A page is only larger
+/// than `max_page_bytes` when a single run does not fit in it, which is the case for budgets
+/// below the size of a run header and 8 bitpacked values.
+pub fn encode_u32_paged<W: Write, I: Iterator<Item = u32>>(
+    writer: &mut W,
+    iterator: I,
+    num_bits: u32,
+    max_page_bytes: usize,
+    mut on_page: impl FnMut(usize),
+) -> std::io::Result<()> {
+    // a run header is at most 10 bytes
+    let max_literal_run = (max_page_bytes.saturating_sub(10) * 8 / (num_bits as usize).max(1))
+        .clamp(8, MAX_VALUES_PER_LITERAL_RUN)
+        / 8
+        * 8;
+
+    let mut buffer = vec![];
+    HybridRleEncoder::<u32>::try_with_max_literal_run(max_literal_run)?.encode_u32(
+        &mut buffer,
+        iterator,
+        num_bits,
+    )?;
+
+    let mut remaining = buffer.as_slice();
+    while !remaining.is_empty() {
+        let mut page_size = run_size(remaining, num_bits as usize);
+        while page_size < remaining.len() {
+            let next = run_size(&remaining[page_size..], num_bits as usize);
+            if page_size + next > max_page_bytes {
+                break;
+            }
+            page_size += next;
+        }
+        let (page, rest) = remaining.split_at(page_size);
+        writer.write_all(page)?;
+        on_page(page_size);
+        remaining = rest;
     }
     Ok(())
 }
@@ -234,6 +739,7 @@ pub fn encode<T: PartialEq + Default + Copy + Encoder<T>, W: Write, I: Iterator<
 #[cfg(test)]
 mod tests {
     use super::super::bitmap::BitmapIter;
+    use super::super::{decode_bool, decode_u32, Decoder, HybridEncoded, HybridRleDecoder};
     use super::*;
 
     #[test]
@@ -242,10 +748,13 @@ mod tests {
 
         let mut vec = vec![];
 
-        encode::<bool, _, _>(&mut vec, iter, 1)?;
+        let written = encode::<bool, _, _>(&mut vec, iter, 1)?;
 
         assert_eq!(vec, vec![(2 << 1 | 1), 0b10011101u8, 0b00011101]);
+        assert_eq!(written, vec.len());
 
+        let expected = BitmapIter::new(&[0b10011101u8, 0b10011101], 0, 14).collect::<Vec<_>>();
+        assert_eq!(decode_bool(&mut vec.as_slice(), 14).unwrap(), expected);
         Ok(())
     }
 
@@ -260,6 +769,199 @@ mod tests {
         )?;
 
         assert_eq!(vec, vec![(1 << 1 | 1), 0b11111111]);
+        assert_eq!(decode_bool(&mut vec.as_slice(), 8).unwrap(), vec![true; 8]);
+        Ok(())
+    }
+
+    #[test]
+    fn bool_roundtrip_random() -> std::io::Result<()> {
+        // xorshift, so that the cases are reproducible
+        let mut state = 0x2545F4914F6CDD1Du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        for _ in 0..500 {
+            let length = (next() % 2000) as usize;
+            // alternate random bits with runs of a single value, of random lengths
+            let mut values = Vec::with_capacity(length);
+            while values.len() < length {
+                let run = (next() % 100) as usize + 1;
+                let run = run.min(length - values.len());
+                if next() % 2 == 0 {
+                    let value = next() % 2 == 0;
+                    values.extend(std::iter::repeat(value).take(run));
+                } else {
+                    values.extend((0..run).map(|_| next() % 2 == 0));
+                }
+            }
+
+            let mut vec = vec![];
+            let written = encode::<bool, _, _>(&mut vec, values.iter().copied(), 1)?;
+            assert_eq!(decode_bool(&mut vec.as_slice(), length).unwrap(), values);
+            assert_eq!(written, vec.len());
+
+            let mut vec = vec![];
+            let written =
+                encode_with_max_literal_run::<bool, _, _>(&mut vec, values.iter().copied(), 1, 16)?;
+            assert_eq!(decode_bool(&mut vec.as_slice(), length).unwrap(), values);
+            assert_eq!(written, vec.len());
+
+            // the stream does not hold more values than were encoded
+            if length > 0 && length % 8 == 0 {
+                assert!(decode_bool(&mut vec.as_slice(), length + 8).is_err());
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn u32_roundtrip_literal_runs() -> std::io::Result<()> {
+        let mut state = 0x9E3779B97F4A7C15u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        for _ in 0..200 {
+            let length = (next() % 600) as usize;
+            // runs around the threshold, so that literal runs are padded with repeats
+            let mut values = Vec::with_capacity(length);
+            while values.len() < length {
+                let run = (next() % 20) as usize + 1;
+                let run = run.min(length - values.len());
+                values.extend(std::iter::repeat((next() % 8) as u32).take(run));
+            }
+
+            for max_literal_run in [8, 16, 64] {
+                for threshold in [8, 16] {
+                    let mut vec = vec![];
+                    let written =
+                        HybridRleEncoder::<u32>::try_with_max_literal_run(max_literal_run)?
+                            .try_with_rle_threshold(threshold)?
+                            .encode_u32(&mut vec, values.iter().copied(), 3)?;
+                    assert_eq!(written, vec.len());
+                    assert_eq!(decode_u32(&mut vec.as_slice(), 3, length).unwrap(), values);
+
+                    // the literal runs hold no values beyond the encoded ones
+                    if length % 8 == 0 {
+                        assert!(decode_u32(&mut vec.as_slice(), 3, length + 8).is_err());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn rle_threshold() -> std::io::Result<()> {
+        // runs of 12 repeated values, separated by distinct values
+        let values = (0..20u32)
+            .flat_map(|i| {
+                std::iter::repeat(i % 4)
+                    .take(12)
+                    .chain([(i + 1) % 4, i % 4])
+            })
+            .collect::<Vec<_>>();
+
+        let mut default = vec![];
+        encode::<u32, _, _>(&mut default, values.iter().copied(), 2)?;
+        let mut at_8 = vec![];
+        encode_with_rle_threshold::<u32, _, _>(&mut at_8, values.iter().copied(), 2, 8)?;
+        assert_eq!(at_8, default);
+
+        // the runs are not long enough to RLE, so a single literal run is written
+        let mut at_16 = vec![];
+        encode_with_rle_threshold::<u32, _, _>(&mut at_16, values.iter().copied(), 2, 16)?;
+        assert_ne!(at_16.len(), at_8.len());
+        assert_eq!(Decoder::new(&at_16, 2).count(), 1);
+
+        for encoded in [&at_8, &at_16] {
+            let decoded = decode_u32(&mut encoded.as_slice(), 2, values.len()).unwrap();
+            assert_eq!(decoded, values);
+        }
+
+        // with booleans, mixing runs above and below both thresholds
+        let bools = (0..50)
+            .flat_map(|i| std::iter::repeat(i % 2 == 0).take(i % 30 + 1))
+            .collect::<Vec<_>>();
+        for threshold in [8, 16, 24] {
+            for max_literal_run in [16, MAX_VALUES_PER_LITERAL_RUN] {
+                let mut vec = vec![];
+                HybridRleEncoder::<bool>::try_with_max_literal_run(max_literal_run)?
+                    .try_with_rle_threshold(threshold)?
+                    .encode_bool(&mut vec, bools.iter().copied(), 1)?;
+                assert_eq!(
+                    decode_bool(&mut vec.as_slice(), bools.len()).unwrap(),
+                    bools
+                );
+            }
+        }
+
+        assert!(HybridRleEncoder::<u32>::new()
+            .try_with_rle_threshold(12)
+            .is_err());
+        assert!(HybridRleEncoder::<u32>::new()
+            .try_with_rle_threshold(0)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn aggressive_rle() -> std::io::Result<()> {
+        // runs of 12 values are too short to be split out of the literal runs by default, but
+        // each holds an aligned group of 8 values
+        let values = (0..50u32)
+            .flat_map(|i| {
+                [1, 2, 3, 4]
+                    .into_iter()
+                    .chain(std::iter::repeat(i + 10).take(12))
+            })
+            .collect::<Vec<_>>();
+
+        let mut default = vec![];
+        encode::<u32, _, _>(&mut default, values.iter().copied(), 8)?;
+        assert_eq!(Decoder::new(&default, 8).count(), 1);
+
+        let mut aggressive = vec![];
+        let written = HybridRleEncoder::<u32>::new()
+            .try_with_aggressive_rle(8)?
+            .encode_u32(&mut aggressive, values.iter().copied(), 8)?;
+        assert_eq!(written, aggressive.len());
+        // a literal run of 8 values and a RLE run for every 16 values
+        assert_eq!(aggressive.len(), 50 * (1 + 8 + 1 + 1));
+        assert!(aggressive.len() < default.len());
+        let decoded = decode_u32(&mut aggressive.as_slice(), 8, values.len()).unwrap();
+        assert_eq!(decoded, values);
+
+        // embedded runs shorter than the minimum are kept in the literal runs
+        let mut vec = vec![];
+        HybridRleEncoder::<u32>::new()
+            .try_with_aggressive_rle(16)?
+            .encode_u32(&mut vec, values.iter().copied(), 8)?;
+        assert_eq!(vec, default);
+
+        // also for literal runs split by the maximum literal run, and ending the stream
+        for length in [values.len(), values.len() - 3] {
+            let values = &values[..length];
+            let mut vec = vec![];
+            HybridRleEncoder::<u32>::try_with_max_literal_run(24)?
+                .try_with_aggressive_rle(8)?
+                .with_pad_bitpacked_to_block(true)
+                .encode_u32(&mut vec, values.iter().copied(), 8)?;
+            assert_eq!(decode_u32(&mut vec.as_slice(), 8, length).unwrap(), values);
+        }
+
+        for min_run_length in [0, 12] {
+            assert!(HybridRleEncoder::<u32>::new()
+                .try_with_aggressive_rle(min_run_length)
+                .is_err());
+        }
         Ok(())
     }
 
@@ -267,7 +969,9 @@ mod tests {
     fn test_encode_u32() -> std::io::Result<()> {
         let mut vec = vec![];
 
-        encode::<u32, _, _>(&mut vec, vec![0, 1, 2, 1, 2, 1, 1, 0, 3].into_iter(), 2)?;
+        let written =
+            encode::<u32, _, _>(&mut vec, vec![0, 1, 2, 1, 2, 1, 1, 0, 3].into_iter(), 2)?;
+        assert_eq!(written, 5);
 
         assert_eq!(
             vec,
@@ -288,7 +992,7 @@ mod tests {
 
         let values = (0..128).map(|x| x % 4);
 
-        encode::<u32, _, _>(&mut vec, values, 2)?;
+        let written = encode::<u32, _, _>(&mut vec, values, 2)?;
 
         let length = 128;
         let expected = 0b11_10_01_00u8;
@@ -297,18 +1001,326 @@ mod tests {
         expected.insert(0, ((length / 8) as u8) << 1 | 1);
 
         assert_eq!(vec, expected);
+        assert_eq!(written, expected.len());
         Ok(())
     }
 
     #[test]
     fn test_u32_other() -> std::io::Result<()> {
-        let values = vec![3, 3, 0, 3, 2, 3, 3, 3, 3, 1, 3, 3, 3, 0, 3].into_iter();
+        let values = vec![3, 3, 0, 3, 2, 3, 3, 3, 3, 1, 3, 3, 3, 0, 3];
 
         let mut vec = vec![];
-        encode::<u32, _, _>(&mut vec, values, 2)?;
+        encode::<u32, _, _>(&mut vec, values.iter().copied(), 2)?;
+
+        let decoded = decode_u32(&mut vec.as_slice(), 2, values.len()).unwrap();
+        assert_eq!(decoded, values);
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_i32() -> std::io::Result<()> {
+        let values = vec![
+            -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, 0, 3, -4, 2, 2, -2, 1, -3,
+        ];
+        let num_bits = 3;
+
+        let mut vec = vec![];
+        encode_i32(&mut vec, values.iter().copied(), num_bits)?;
+
+        let decoded = HybridRleDecoder::try_new(&vec, num_bits, values.len())
+            .unwrap()
+            .map(|n| ((n >> 1) as i32) ^ -((n & 1) as i32))
+            .collect::<Vec<_>>();
+        assert_eq!(decoded, values);
+        Ok(())
+    }
+
+    #[test]
+    fn test_encoder_reuse() -> std::io::Result<()> {
+        let mut encoder = HybridRleEncoder::<u32>::new();
+
+        for values in [
+            vec![0, 1, 2, 1, 2, 1, 1, 0, 3],
+            vec![3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 1],
+            vec![],
+            (0..128).map(|x| x % 4).collect(),
+        ] {
+            let mut expected = vec![];
+            encode::<u32, _, _>(&mut expected, values.iter().copied(), 2)?;
+
+            let mut vec = vec![];
+            encoder.encode_u32(&mut vec, values.iter().copied(), 2)?;
+            assert_eq!(vec, expected);
+            encoder.reset();
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_pad_bitpacked_to_block() -> std::io::Result<()> {
+        let values = vec![0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3, 4];
+        let num_bits = 3;
+
+        let mut unpadded = vec![];
+        encode::<u32, _, _>(&mut unpadded, values.iter().copied(), num_bits)?;
+        assert_eq!(unpadded.len(), 1 + 2 * 3);
+
+        let mut padded = vec![];
+        HybridRleEncoder::new()
+            .with_pad_bitpacked_to_block(true)
+            .encode_u32(&mut padded, values.iter().copied(), num_bits)?;
+        assert_eq!(padded[0], (4 << 1 | 1));
+        assert_eq!(padded.len(), 1 + 4 * 3);
+        assert_eq!(&padded[1..7], &unpadded[1..]);
+
+        for data in [unpadded, padded] {
+            let decoded = HybridRleDecoder::try_new(&data, num_bits, values.len())
+                .unwrap()
+                .collect::<Vec<_>>();
+            assert_eq!(decoded, values);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_pad_bitpacked_to_block_bool() -> std::io::Result<()> {
+        let values = [
+            true, false, true, true, false, false, true, false, true, true,
+        ];
+
+        let mut padded = vec![];
+        HybridRleEncoder::new()
+            .with_pad_bitpacked_to_block(true)
+            .encode_bool(&mut padded, values.iter().copied(), 1)?;
+        assert_eq!(padded, vec![(4 << 1 | 1), 0b01001101, 0b00000011, 0, 0]);
+        Ok(())
+    }
 
-        let expected = vec![5, 207, 254, 247, 51];
-        assert_eq!(expected, vec);
+    #[test]
+    fn test_pad_bitpacked_to_block_before_rle() -> std::io::Result<()> {
+        // the literal run is followed by an RLE run and must not be padded
+        let values = (0..8)
+            .chain(std::iter::repeat(1).take(20))
+            .collect::<Vec<_>>();
+        let num_bits = 3;
+
+        let mut unpadded = vec![];
+        encode::<u32, _, _>(&mut unpadded, values.iter().copied(), num_bits)?;
+
+        let mut padded = vec![];
+        HybridRleEncoder::new()
+            .with_pad_bitpacked_to_block(true)
+            .encode_u32(&mut padded, values.iter().copied(), num_bits)?;
+        assert_eq!(padded, unpadded);
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_literal_run() -> std::io::Result<()> {
+        let num_bits = 5;
+        let values = (0..200u32)
+            .map(|x| if x % 50 < 20 { 7 } else { x % 31 })
+            .collect::<Vec<_>>();
+
+        let mut sizes = vec![];
+        for max_literal_run in [8, 16, MAX_VALUES_PER_LITERAL_RUN] {
+            let mut vec = vec![];
+            encode_with_max_literal_run::<u32, _, _>(
+                &mut vec,
+                values.iter().copied(),
+                num_bits,
+                max_literal_run,
+            )?;
+            let decoded = HybridRleDecoder::try_new(&vec, num_bits, values.len())
+                .unwrap()
+                .collect::<Vec<_>>();
+            assert_eq!(decoded, values);
+            sizes.push(vec.len());
+        }
+        // smaller caps need more headers
+        assert!(sizes[0] > sizes[1]);
+        assert!(sizes[1] >= sizes[2]);
+
+        let mut vec = vec![];
+        encode_with_max_literal_run::<bool, _, _>(&mut vec, [true, false].into_iter(), 1, 16)?;
+        assert_eq!(vec, vec![(1 << 1 | 1), 0b00000001]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_literal_run_invalid() {
+        for max_literal_run in [0, 12] {
+            let result = encode_with_max_literal_run::<u32, _, _>(
+                &mut vec![],
+                [1, 2].into_iter(),
+                2,
+                max_literal_run,
+            );
+            assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+        }
+    }
+
+    #[test]
+    fn test_encode_constant_u32() -> std::io::Result<()> {
+        for (value, count, num_bits) in [(0, 9, 1), (3, 100, 2), (1, 1 << 20, 1), (300, 1000, 9)] {
+            let mut expected = vec![];
+            encode::<u32, _, _>(
+                &mut expected,
+                std::iter::repeat(value).take(count),
+                num_bits,
+            )?;
+
+            let mut vec = vec![];
+            let written = encode_constant_u32(&mut vec, value, count, num_bits)?;
+            assert_eq!(vec, expected);
+            assert_eq!(written, vec.len());
+        }
+
+        let mut vec = vec![];
+        assert_eq!(encode_constant_u32(&mut vec, 2, 3, 2)?, 2);
+        let decoded = HybridRleDecoder::try_new(&vec, 2, 3)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(decoded, vec![2, 2, 2]);
+
+        assert_eq!(encode_constant_u32(&mut vec![], 2, 0, 2)?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_literal_run_padding() -> std::io::Result<()> {
+        // 13 values without repetitions are bit-packed into 2 groups of 8 values; the 3
+        // padding values are only delimited by the number of values the reader expects.
+        let values = [1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5];
+
+        let mut vec = vec![];
+        encode::<u32, _, _>(&mut vec, values.iter().copied(), 3)?;
+        assert_eq!(vec[0], 2 << 1 | 1);
+        assert_eq!(vec.len(), 1 + 2 * 3);
+
+        let mut decoder = HybridRleDecoder::try_new(&vec, 3, values.len()).unwrap();
+        let decoded = decoder.by_ref().collect::<Vec<_>>();
+        assert_eq!(decoded, values);
+        assert_eq!(decoder.next(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_u32_zero_bits() -> std::io::Result<()> {
+        for length in [3, 20, 1000] {
+            let mut vec = vec![];
+            let written = encode::<u32, _, _>(&mut vec, std::iter::repeat(5).take(length), 0)?;
+
+            let mut expected = vec![];
+            encode_constant_u32(&mut expected, 0, length, 0)?;
+            assert_eq!(vec, expected);
+            assert_eq!(written, vec.len());
+
+            let decoded = decode_u32(&mut vec.as_slice(), 0, length).unwrap();
+            assert_eq!(decoded, vec![0; length]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_run_length() -> std::io::Result<()> {
+        let values = std::iter::repeat(7).take(1000);
+
+        let mut vec = vec![];
+        let written = encode_with_max_run_length::<u32, _, _>(&mut vec, values, 3, 100)?;
+        assert_eq!(written, vec.len());
+
+        let runs = Decoder::new(&vec, 3)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(runs, vec![HybridEncoded::Rle(&[7], 100); 10]);
+
+        let decoded = decode_u32(&mut vec.as_slice(), 3, 1000).unwrap();
+        assert_eq!(decoded, vec![7; 1000]);
+
+        assert!(HybridRleEncoder::<u32>::new()
+            .try_with_max_run_length(0)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_u32_paged() -> std::io::Result<()> {
+        // alternate literal and RLE runs
+        let values = (0..2000u32)
+            .map(|i| if (i / 100) % 2 == 0 { i % 7 } else { 3 })
+            .collect::<Vec<_>>();
+
+        for max_page_bytes in [16, 64, 200] {
+            let mut vec = vec![];
+            let mut pages = vec![];
+            encode_u32_paged(
+                &mut vec,
+                values.iter().copied(),
+                3,
+                max_page_bytes,
+                |size| pages.push(size),
+            )?;
+
+            assert!(pages.len() > 1);
+            assert!(pages.iter().all(|size| *size <= max_page_bytes));
+            assert_eq!(pages.iter().sum::<usize>(), vec.len());
+
+            // every page starts at a run
+            let mut start = 0;
+            for size in pages {
+                let page = &vec[start..start + size];
+                let runs = Decoder::new(page, 3)
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap();
+                assert!(!runs.is_empty());
+                start += size;
+            }
+
+            let decoded = decode_u32(&mut vec.as_slice(), 3, values.len()).unwrap();
+            assert_eq!(decoded, values);
+        }
+
+        let mut pages = 0;
+        encode_u32_paged(&mut vec![], std::iter::empty(), 3, 16, |_| pages += 1)?;
+        assert_eq!(pages, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_empty() -> std::io::Result<()> {
+        for num_bits in [0, 1, 3, 32] {
+            let mut vec = vec![];
+            assert_eq!(
+                encode::<u32, _, _>(&mut vec, std::iter::empty(), num_bits)?,
+                0
+            );
+            assert!(vec.is_empty());
+            assert!(decode_u32(&mut vec.as_slice(), num_bits, 0)
+                .unwrap()
+                .is_empty());
+        }
+
+        let mut vec = vec![];
+        encode::<bool, _, _>(&mut vec, std::iter::empty(), 1)?;
+        assert!(vec.is_empty());
+
+        let mut vec = vec![];
+        HybridRleEncoder::<bool>::new()
+            .with_pad_bitpacked_to_block(true)
+            .encode_bool(&mut vec, std::iter::empty(), 1)?;
+        assert!(vec.is_empty());
+
+        let mut vec = vec![];
+        assert_eq!(bitpacked_encode(&mut vec, std::iter::empty())?, 0);
+        assert!(vec.is_empty());
+
+        // a byte per 8 values, rounded up
+        for (length, expected) in [(1, 1), (8, 1), (9, 2), (16, 2)] {
+            let mut vec = vec![];
+            let written = bitpacked_encode(&mut vec, std::iter::repeat(true).take(length))?;
+            assert_eq!((written, vec.len()), (expected, expected));
+        }
         Ok(())
     }
 }
@@ -3,8 +3,12 @@ mod bitmap;
 mod decoder;
 mod encoder;
 pub use bitmap::{encode_bool as bitpacked_encode, BitmapIter};
-pub use decoder::Decoder;
-pub use encoder::encode;
+pub use decoder::{decode_bool, decode_u32, Decoder};
+pub use encoder::{
+    encode, encode_constant_u32, encode_i32, encode_u32_paged, encode_with_max_literal_run,
+    encode_with_max_run_length, encode_with_rle_threshold, HybridRleEncoder, DEFAULT_RLE_THRESHOLD,
+    MAX_VALUES_PER_LITERAL_RUN,
+};
 use polars_utils::iter::FallibleIterator;
 
 use super::bitpacked;
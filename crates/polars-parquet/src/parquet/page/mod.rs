@@ -4,7 +4,7 @@ use crate::parquet::compression::Compression;
 use crate::parquet::encoding::{get_length, Encoding};
 use crate::parquet::error::{Error, Result};
 use crate::parquet::indexes::Interval;
-use crate::parquet::metadata::Descriptor;
+use crate::parquet::metadata::{Descriptor, SortingColumn};
 pub use crate::parquet::parquet_bridge::{DataPageHeaderExt, PageType};
 use crate::parquet::statistics::{deserialize_statistics, Statistics};
 pub use crate::parquet::thrift_format::{
@@ -25,6 +25,13 @@ pub struct CompressedDataPage {
     pub(crate) compression: Compression,
     uncompressed_page_size: usize,
     pub(crate) descriptor: Descriptor,
+    /// The CRC32 checksum of `buffer`, if any.
+    pub(crate) crc: Option<i32>,
+    /// The bitset of the bloom filter of the column chunk this page starts, if any.
+    pub(crate) bloom_filter: Option<Vec<u8>>,
+    /// The order of the values of the column chunk this page starts, if they are sorted. Its
+    /// `column_idx` is set when the row group is written.
+    pub(crate) sorting_column: Option<SortingColumn>,
 
     // The offset and length in rows
     pub(crate) selected_rows: Option<Vec<Interval>>,
@@ -65,6 +72,9 @@ impl CompressedDataPage {
             compression,
             uncompressed_page_size,
             descriptor,
+            crc: None,
+            bloom_filter: None,
+            sorting_column: None,
             selected_rows,
         }
     }
@@ -141,6 +151,11 @@ pub struct DataPage {
     pub(super) buffer: Vec<u8>,
     pub descriptor: Descriptor,
     pub selected_rows: Option<Vec<Interval>>,
+    /// The bitset of the bloom filter of the column chunk this page starts, if any.
+    pub(crate) bloom_filter: Option<Vec<u8>>,
+    /// The order of the values of the column chunk this page starts, if they are sorted. Its
+    /// `column_idx` is set when the row group is written.
+    pub(crate) sorting_column: Option<SortingColumn>,
 }
 
 impl DataPage {
@@ -169,6 +184,8 @@ impl DataPage {
             buffer,
             descriptor,
             selected_rows,
+            bloom_filter: None,
+            sorting_column: None,
         }
     }
 
@@ -302,6 +319,23 @@ impl CompressedPage {
             CompressedPage::Dict(page) => page.uncompressed_page_size,
         }
     }
+
+    /// The CRC32 checksum of the (compressed) buffer of this page, if one was computed.
+    pub fn crc(&self) -> Option<i32> {
+        match self {
+            CompressedPage::Data(page) => page.crc,
+            CompressedPage::Dict(page) => page.crc,
+        }
+    }
+
+    /// Computes the CRC32 checksum of the buffer of this page, which is written in its header.
+    pub(crate) fn compute_crc(&mut self) {
+        let crc = crc32fast::hash(self.buffer()) as i32;
+        match self {
+            CompressedPage::Data(page) => page.crc = Some(crc),
+            CompressedPage::Dict(page) => page.crc = Some(crc),
+        }
+    }
 }
 
 /// An uncompressed, encoded dictionary page.
@@ -330,6 +364,8 @@ pub struct CompressedDictPage {
     pub(crate) num_values: usize,
     pub(crate) uncompressed_page_size: usize,
     pub is_sorted: bool,
+    /// The CRC32 checksum of `buffer`, if any.
+    pub(crate) crc: Option<i32>,
 }
 
 impl CompressedDictPage {
@@ -346,6 +382,7 @@ impl CompressedDictPage {
             uncompressed_page_size,
             num_values,
             is_sorted,
+            crc: None,
         }
     }
 
@@ -132,3 +132,163 @@ pub fn serialize_statistics(statistics: &dyn Statistics) -> ParquetStatistics {
         },
     }
 }
+
+/// [`Statistics`] typed by their physical type, for callers that inspect them rather than pass
+/// them along.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnStatistics {
+    Boolean(BooleanStatistics),
+    Int32(PrimitiveStatistics<i32>),
+    Int64(PrimitiveStatistics<i64>),
+    Int96(PrimitiveStatistics<[u32; 3]>),
+    Float(PrimitiveStatistics<f32>),
+    Double(PrimitiveStatistics<f64>),
+    ByteArray(BinaryStatistics),
+    FixedLenByteArray(FixedLenStatistics),
+}
+
+impl ColumnStatistics {
+    /// Downcasts `statistics` by its physical type.
+    pub fn from_dyn(statistics: &dyn Statistics) -> Self {
+        let any = statistics.as_any();
+        match statistics.physical_type() {
+            PhysicalType::Boolean => Self::Boolean(any.downcast_ref().cloned().unwrap()),
+            PhysicalType::Int32 => Self::Int32(any.downcast_ref().cloned().unwrap()),
+            PhysicalType::Int64 => Self::Int64(any.downcast_ref().cloned().unwrap()),
+            PhysicalType::Int96 => Self::Int96(any.downcast_ref().cloned().unwrap()),
+            PhysicalType::Float => Self::Float(any.downcast_ref().cloned().unwrap()),
+            PhysicalType::Double => Self::Double(any.downcast_ref().cloned().unwrap()),
+            PhysicalType::ByteArray => Self::ByteArray(any.downcast_ref().cloned().unwrap()),
+            PhysicalType::FixedLenByteArray(_) => {
+                Self::FixedLenByteArray(any.downcast_ref().cloned().unwrap())
+            },
+        }
+    }
+
+    /// Deserializes a raw parquet statistics of a column of `primitive_type`.
+    /// # Error
+    /// Errors under the same conditions as [`deserialize_statistics`].
+    pub fn deserialize(
+        statistics: &ParquetStatistics,
+        primitive_type: PrimitiveType,
+    ) -> Result<Self> {
+        deserialize_statistics(statistics, primitive_type).map(|x| Self::from_dyn(x.as_ref()))
+    }
+
+    /// Serializes these statistics into a raw parquet statistics.
+    pub fn serialize(&self) -> ParquetStatistics {
+        serialize_statistics(self.as_dyn())
+    }
+
+    pub fn as_dyn(&self) -> &dyn Statistics {
+        match self {
+            Self::Boolean(x) => x,
+            Self::Int32(x) => x,
+            Self::Int64(x) => x,
+            Self::Int96(x) => x,
+            Self::Float(x) => x,
+            Self::Double(x) => x,
+            Self::ByteArray(x) => x,
+            Self::FixedLenByteArray(x) => x,
+        }
+    }
+
+    pub fn physical_type(&self) -> &PhysicalType {
+        self.as_dyn().physical_type()
+    }
+
+    pub fn null_count(&self) -> Option<i64> {
+        self.as_dyn().null_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn primitive<T: crate::parquet::types::NativeType>(min: T, max: T) -> PrimitiveStatistics<T> {
+        PrimitiveStatistics {
+            primitive_type: PrimitiveType::from_physical("a".to_string(), T::TYPE),
+            null_count: Some(1),
+            distinct_count: None,
+            min_value: Some(min),
+            max_value: Some(max),
+        }
+    }
+
+    /// Serializes `statistics`, checks the raw min and max, and deserializes them back.
+    fn roundtrip(statistics: ColumnStatistics, type_: PhysicalType, min: &[u8], max: &[u8]) {
+        assert_eq!(statistics.physical_type(), &type_);
+        let raw = statistics.serialize();
+        assert_eq!(raw.min_value.as_deref(), Some(min));
+        assert_eq!(raw.max_value.as_deref(), Some(max));
+        assert_eq!(raw.null_count, statistics.null_count());
+
+        let primitive_type = PrimitiveType::from_physical("a".to_string(), type_);
+        let deserialized = ColumnStatistics::deserialize(&raw, primitive_type).unwrap();
+        assert_eq!(deserialized, statistics);
+    }
+
+    #[test]
+    fn boolean() {
+        let statistics = ColumnStatistics::Boolean(BooleanStatistics {
+            null_count: Some(0),
+            distinct_count: None,
+            max_value: Some(true),
+            min_value: Some(false),
+        });
+        roundtrip(statistics, PhysicalType::Boolean, &[0], &[1]);
+    }
+
+    #[test]
+    fn integers() {
+        let statistics = ColumnStatistics::Int32(primitive(-1i32, 256));
+        roundtrip(statistics, PhysicalType::Int32, &[255; 4], &[0, 1, 0, 0]);
+
+        let statistics = ColumnStatistics::Int64(primitive(i64::MIN, 1));
+        let min = i64::MIN.to_le_bytes();
+        roundtrip(statistics, PhysicalType::Int64, &min, &1i64.to_le_bytes());
+
+        let statistics = ColumnStatistics::Int96(primitive([1, 0, 0], [0, 0, 2]));
+        let max = [0, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0];
+        roundtrip(
+            statistics,
+            PhysicalType::Int96,
+            &[1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            &max,
+        );
+    }
+
+    #[test]
+    fn floats() {
+        let statistics = ColumnStatistics::Float(primitive(-0.5f32, 2.0));
+        let (min, max) = ((-0.5f32).to_le_bytes(), 2.0f32.to_le_bytes());
+        roundtrip(statistics, PhysicalType::Float, &min, &max);
+
+        let statistics = ColumnStatistics::Double(primitive(-0.5f64, 2.0));
+        let (min, max) = ((-0.5f64).to_le_bytes(), 2.0f64.to_le_bytes());
+        roundtrip(statistics, PhysicalType::Double, &min, &max);
+    }
+
+    #[test]
+    fn byte_arrays() {
+        let statistics = ColumnStatistics::ByteArray(BinaryStatistics {
+            primitive_type: PrimitiveType::from_physical("a".to_string(), PhysicalType::ByteArray),
+            null_count: Some(2),
+            distinct_count: None,
+            min_value: Some(b"apple".to_vec()),
+            max_value: Some(b"pear".to_vec()),
+        });
+        roundtrip(statistics, PhysicalType::ByteArray, b"apple", b"pear");
+
+        let type_ = PhysicalType::FixedLenByteArray(2);
+        let statistics = ColumnStatistics::FixedLenByteArray(FixedLenStatistics {
+            primitive_type: PrimitiveType::from_physical("a".to_string(), type_),
+            null_count: Some(0),
+            distinct_count: None,
+            min_value: Some(vec![0, 1]),
+            max_value: Some(vec![1, 0]),
+        });
+        roundtrip(statistics, type_, &[0, 1], &[1, 0]);
+    }
+}
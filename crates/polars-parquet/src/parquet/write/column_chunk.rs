@@ -1,11 +1,14 @@
 use std::io::Write;
 
 #[cfg(feature = "async")]
-use futures::AsyncWrite;
+use futures::{AsyncWrite, AsyncWriteExt};
 use parquet_format_safe::thrift::protocol::TCompactOutputProtocol;
 #[cfg(feature = "async")]
 use parquet_format_safe::thrift::protocol::TCompactOutputStreamProtocol;
-use parquet_format_safe::{ColumnChunk, ColumnMetaData, Type};
+use parquet_format_safe::{
+    BloomFilterAlgorithm, BloomFilterCompression, BloomFilterHash, BloomFilterHeader, ColumnChunk,
+    ColumnMetaData, SplitBlockAlgorithm, Type, Uncompressed, XxHash,
+};
 use polars_utils::aliases::PlHashSet;
 
 #[cfg(feature = "async")]
@@ -16,7 +19,7 @@ use super::DynStreamingIterator;
 use crate::parquet::compression::Compression;
 use crate::parquet::encoding::Encoding;
 use crate::parquet::error::{Error, Result};
-use crate::parquet::metadata::ColumnDescriptor;
+use crate::parquet::metadata::{ColumnDescriptor, SortingColumn};
 use crate::parquet::page::{CompressedPage, PageType};
 use crate::parquet::statistics::serialize_statistics;
 use crate::parquet::FallibleStreamingIterator;
@@ -26,7 +29,7 @@ pub fn write_column_chunk<W, E>(
     mut offset: u64,
     descriptor: &ColumnDescriptor,
     mut compressed_pages: DynStreamingIterator<'_, CompressedPage, E>,
-) -> Result<(ColumnChunk, Vec<PageWriteSpec>, u64)>
+) -> Result<(ColumnChunk, Vec<PageWriteSpec>, u64, Option<SortingColumn>)>
 where
     W: Write,
     Error: From<E>,
@@ -37,14 +40,33 @@ where
     let initial = offset;
 
     let mut specs = vec![];
+    let mut first_data_page = true;
+    let mut bloom_filter = None;
+    let mut sorting_column = None;
     while let Some(compressed_page) = compressed_pages.next()? {
+        if let (true, CompressedPage::Data(page)) = (first_data_page, compressed_page) {
+            first_data_page = false;
+            bloom_filter.clone_from(&page.bloom_filter);
+            sorting_column.clone_from(&page.sorting_column);
+        }
         let spec = write_page(writer, offset, compressed_page)?;
         offset += spec.bytes_written;
         specs.push(spec);
     }
     let mut bytes_written = offset - initial;
 
-    let column_chunk = build_column_chunk(&specs, descriptor)?;
+    let mut column_chunk = build_column_chunk(&specs, descriptor)?;
+
+    // the bloom filter sits between the pages and the metadata
+    if let Some(bitset) = bloom_filter {
+        column_chunk.meta_data.as_mut().unwrap().bloom_filter_offset = Some(offset as i64);
+        let mut protocol = TCompactOutputProtocol::new(&mut *writer);
+        let header_size = bloom_filter_header(&bitset).write_to_out_protocol(&mut protocol)?;
+        writer.write_all(&bitset)?;
+        let filter_size = (header_size + bitset.len()) as u64;
+        bytes_written += filter_size;
+        column_chunk.file_offset += filter_size as i64;
+    }
 
     // write metadata
     let mut protocol = TCompactOutputProtocol::new(writer);
@@ -54,7 +76,7 @@ where
         .unwrap()
         .write_to_out_protocol(&mut protocol)? as u64;
 
-    Ok((column_chunk, specs, bytes_written))
+    Ok((column_chunk, specs, bytes_written, sorting_column))
 }
 
 #[cfg(feature = "async")]
@@ -64,7 +86,7 @@ pub async fn write_column_chunk_async<W, E>(
     mut offset: u64,
     descriptor: &ColumnDescriptor,
     mut compressed_pages: DynStreamingIterator<'_, CompressedPage, E>,
-) -> Result<(ColumnChunk, Vec<PageWriteSpec>, u64)>
+) -> Result<(ColumnChunk, Vec<PageWriteSpec>, u64, Option<SortingColumn>)>
 where
     W: AsyncWrite + Unpin + Send,
     Error: From<E>,
@@ -73,14 +95,35 @@ where
     let initial = offset;
     // write every page
     let mut specs = vec![];
+    let mut first_data_page = true;
+    let mut bloom_filter = None;
+    let mut sorting_column = None;
     while let Some(compressed_page) = compressed_pages.next()? {
+        if let (true, CompressedPage::Data(page)) = (first_data_page, compressed_page) {
+            first_data_page = false;
+            bloom_filter.clone_from(&page.bloom_filter);
+            sorting_column.clone_from(&page.sorting_column);
+        }
         let spec = write_page_async(writer, offset, compressed_page).await?;
         offset += spec.bytes_written;
         specs.push(spec);
     }
     let mut bytes_written = offset - initial;
 
-    let column_chunk = build_column_chunk(&specs, descriptor)?;
+    let mut column_chunk = build_column_chunk(&specs, descriptor)?;
+
+    // the bloom filter sits between the pages and the metadata
+    if let Some(bitset) = bloom_filter {
+        column_chunk.meta_data.as_mut().unwrap().bloom_filter_offset = Some(offset as i64);
+        let mut protocol = TCompactOutputStreamProtocol::new(&mut *writer);
+        let header_size = bloom_filter_header(&bitset)
+            .write_to_out_stream_protocol(&mut protocol)
+            .await?;
+        writer.write_all(&bitset).await?;
+        let filter_size = (header_size + bitset.len()) as u64;
+        bytes_written += filter_size;
+        column_chunk.file_offset += filter_size as i64;
+    }
 
     // write metadata
     let mut protocol = TCompactOutputStreamProtocol::new(writer);
@@ -91,7 +134,17 @@ where
         .write_to_out_stream_protocol(&mut protocol)
         .await? as u64;
 
-    Ok((column_chunk, specs, bytes_written))
+    Ok((column_chunk, specs, bytes_written, sorting_column))
+}
+
+/// Returns the header of the split-block bloom filter `bitset`, written uncompressed.
+fn bloom_filter_header(bitset: &[u8]) -> BloomFilterHeader {
+    BloomFilterHeader {
+        num_bytes: bitset.len() as i32,
+        algorithm: BloomFilterAlgorithm::BLOCK(SplitBlockAlgorithm {}),
+        hash: BloomFilterHash::XXHASH(XxHash {}),
+        compression: BloomFilterCompression::UNCOMPRESSED(Uncompressed {}),
+    }
 }
 
 fn build_column_chunk(
@@ -17,6 +17,8 @@ fn compress_data(
         header,
         descriptor,
         selected_rows,
+        bloom_filter,
+        sorting_column,
     } = page;
     let uncompressed_page_size = buffer.len();
     if compression != CompressionOptions::Uncompressed {
@@ -39,14 +41,17 @@ fn compress_data(
     } else {
         std::mem::swap(&mut buffer, &mut compressed_buffer);
     };
-    Ok(CompressedDataPage::new_read(
+    let mut page = CompressedDataPage::new_read(
         header,
         compressed_buffer,
         compression.into(),
         uncompressed_page_size,
         descriptor,
         selected_rows,
-    ))
+    );
+    page.bloom_filter = bloom_filter;
+    page.sorting_column = sorting_column;
+    Ok(page)
 }
 
 fn compress_dict(
@@ -103,6 +108,7 @@ pub struct Compressor<I: Iterator<Item = Result<Page>>> {
     compression: CompressionOptions,
     buffer: Vec<u8>,
     current: Option<CompressedPage>,
+    checksums: bool,
 }
 
 impl<I: Iterator<Item = Result<Page>>> Compressor<I> {
@@ -113,9 +119,17 @@ impl<I: Iterator<Item = Result<Page>>> Compressor<I> {
             compression,
             buffer,
             current: None,
+            checksums: false,
         }
     }
 
+    /// Sets whether the CRC32 checksum of every compressed page is computed, so that it is
+    /// written in the page header. Defaults to `false`.
+    pub fn with_checksums(mut self, checksums: bool) -> Self {
+        self.checksums = checksums;
+        self
+    }
+
     /// Creates a new [`Compressor`] (same as `new`)
     pub fn new_from_vec(iter: I, compression: CompressionOptions, buffer: Vec<u8>) -> Self {
         Self::new(iter, compression, buffer)
@@ -150,7 +164,12 @@ impl<I: Iterator<Item = Result<Page>>> FallibleStreamingIterator for Compressor<
             .next()
             .map(|x| x.and_then(|page| compress(page, compressed_buffer, self.compression)))
             .transpose()?;
-        self.current = next;
+        self.current = next.map(|mut page| {
+            if self.checksums {
+                page.compute_crc();
+            }
+            page
+        });
         Ok(())
     }
 
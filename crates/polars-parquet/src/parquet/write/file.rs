@@ -1,7 +1,7 @@
 use std::io::Write;
 
 use parquet_format_safe::thrift::protocol::TCompactOutputProtocol;
-use parquet_format_safe::RowGroup;
+use parquet_format_safe::{RowGroup, SortingColumn};
 
 use super::indexes::{write_column_index, write_offset_index};
 use super::page::PageWriteSpec;
@@ -61,6 +61,7 @@ pub struct FileWriter<W: Write> {
     offset: u64,
     row_groups: Vec<RowGroup>,
     page_specs: Vec<Vec<Vec<PageWriteSpec>>>,
+    sorting_columns: Option<Vec<SortingColumn>>,
     /// Used to store the current state for writing the file
     state: State,
     // when the file is written, metadata becomes available
@@ -120,6 +121,7 @@ impl<W: Write> FileWriter<W> {
             offset: 0,
             row_groups: vec![],
             page_specs: vec![],
+            sorting_columns: None,
             state: State::Initialised,
             metadata: None,
         }
@@ -143,6 +145,15 @@ impl<W: Write> FileWriter<W> {
         }
     }
 
+    /// Sets the columns by which the rows of every row group written after this call are
+    /// sorted, recorded in their metadata so that readers can rely on it.
+    ///
+    /// The values are not checked to be sorted. The orders recorded by the pages of other
+    /// columns are added to them.
+    pub fn set_sorting_columns(&mut self, sorting_columns: Option<Vec<SortingColumn>>) {
+        self.sorting_columns = sorting_columns;
+    }
+
     /// Writes a row group to the file.
     ///
     /// This call is IO-bounded
@@ -161,6 +172,7 @@ impl<W: Write> FileWriter<W> {
             self.schema.columns(),
             row_group,
             ordinal,
+            self.sorting_columns.clone(),
         )?;
         self.offset += size;
         self.row_groups.push(group);
@@ -4,7 +4,7 @@ mod file;
 mod indexes;
 pub(crate) mod page;
 mod row_group;
-mod statistics;
+pub(crate) mod statistics;
 
 #[cfg(feature = "async")]
 mod stream;
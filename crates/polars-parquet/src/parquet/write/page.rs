@@ -151,7 +151,7 @@ fn assemble_data_page_header(page: &CompressedDataPage) -> Result<ParquetPageHea
         },
         uncompressed_page_size,
         compressed_page_size,
-        crc: None,
+        crc: page.crc,
         data_page_header: None,
         index_page_header: None,
         dictionary_page_header: None,
@@ -184,13 +184,13 @@ fn assemble_dict_page_header(page: &CompressedDictPage) -> Result<ParquetPageHea
         type_: PageType::DICTIONARY_PAGE,
         uncompressed_page_size,
         compressed_page_size,
-        crc: None,
+        crc: page.crc,
         data_page_header: None,
         index_page_header: None,
         dictionary_page_header: Some(DictionaryPageHeader {
             num_values,
             encoding: Encoding::PLAIN,
-            is_sorted: None,
+            is_sorted: page.is_sorted.then_some(true),
         }),
         data_page_header_v2: None,
     })
@@ -240,4 +240,53 @@ mod tests {
         );
         assert!(assemble_dict_page_header(&page).is_err());
     }
+
+    #[test]
+    fn page_checksums() {
+        use arrow::array::Int32Array;
+        use arrow::datatypes::{ArrowDataType, Field};
+
+        use crate::arrow::write::{array_to_columns, to_parquet_type, Version, WriteOptions};
+        use crate::parquet::compression::CompressionOptions;
+        use crate::parquet::write::Compressor;
+        use crate::parquet::FallibleStreamingIterator;
+
+        let compressions = [CompressionOptions::Uncompressed]
+            .into_iter()
+            .chain(cfg!(feature = "snappy").then_some(CompressionOptions::Snappy));
+
+        let array = Int32Array::from_iter((0..1000).map(|x| (x % 3 != 0).then_some(x % 10)));
+        let type_ = to_parquet_type(&Field::new("a", ArrowDataType::Int32, true)).unwrap();
+        for compression in compressions {
+            for write_page_checksums in [false, true] {
+                let options = WriteOptions::builder()
+                    .version(Version::V2)
+                    .compression(compression)
+                    .write_page_checksums(write_page_checksums)
+                    .build()
+                    .unwrap();
+                let pages = array_to_columns(
+                    array.clone().boxed(),
+                    type_.clone(),
+                    options.clone(),
+                    &[crate::parquet::encoding::Encoding::Plain],
+                )
+                .unwrap()
+                .remove(0)
+                .map(|page| page.map_err(|e| Error::oos(e.to_string())));
+                let mut compressor = Compressor::new(pages, compression, vec![])
+                    .with_checksums(options.write_page_checksums);
+
+                let page = compressor.next().unwrap().unwrap();
+                let mut bytes = vec![];
+                let spec = write_page(&mut bytes, 0, page).unwrap();
+
+                let payload = &bytes[spec.header_size as usize..];
+                assert_eq!(payload.len(), spec.header.compressed_page_size as usize);
+                let expected = write_page_checksums.then(|| crc32fast::hash(payload) as i32);
+                assert_eq!(spec.header.crc, expected);
+                assert_eq!(page.crc(), expected);
+            }
+        }
+    }
 }
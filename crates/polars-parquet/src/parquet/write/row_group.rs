@@ -2,7 +2,7 @@ use std::io::Write;
 
 #[cfg(feature = "async")]
 use futures::AsyncWrite;
-use parquet_format_safe::{ColumnChunk, RowGroup};
+use parquet_format_safe::{ColumnChunk, RowGroup, SortingColumn};
 
 use super::column_chunk::write_column_chunk;
 #[cfg(feature = "async")]
@@ -68,6 +68,40 @@ fn compute_num_rows(columns: &[(ColumnChunk, Vec<PageWriteSpec>)]) -> Result<i64
         .unwrap_or(Ok(0))
 }
 
+/// Adds the order recorded by the pages of the column at `column_idx`, if any, to `recorded`.
+fn record_sorting_column(
+    recorded: &mut Vec<SortingColumn>,
+    column_idx: usize,
+    sorting_column: Option<SortingColumn>,
+) {
+    if let Some(sorting_column) = sorting_column {
+        recorded.push(SortingColumn {
+            column_idx: column_idx as i32,
+            ..sorting_column
+        });
+    }
+}
+
+/// Returns the sorting columns of a row group: those set on the writer, followed by those
+/// recorded by the pages of columns the writer has no order for.
+fn merge_sorting_columns(
+    sorting_columns: Option<Vec<SortingColumn>>,
+    recorded: Vec<SortingColumn>,
+) -> Option<Vec<SortingColumn>> {
+    let Some(mut sorting_columns) = sorting_columns else {
+        return (!recorded.is_empty()).then_some(recorded);
+    };
+    for sorting_column in recorded {
+        if !sorting_columns
+            .iter()
+            .any(|c| c.column_idx == sorting_column.column_idx)
+        {
+            sorting_columns.push(sorting_column);
+        }
+    }
+    Some(sorting_columns)
+}
+
 pub fn write_row_group<
     'a,
     W,
@@ -78,6 +112,7 @@ pub fn write_row_group<
     descriptors: &[ColumnDescriptor],
     columns: DynIter<'a, std::result::Result<DynStreamingIterator<'a, CompressedPage, E>, E>>,
     ordinal: usize,
+    sorting_columns: Option<Vec<SortingColumn>>,
 ) -> Result<(RowGroup, Vec<Vec<PageWriteSpec>>, u64)>
 where
     W: Write,
@@ -87,15 +122,19 @@ where
     let column_iter = descriptors.iter().zip(columns);
 
     let initial = offset;
+    let mut recorded = vec![];
     let columns = column_iter
-        .map(|(descriptor, page_iter)| {
-            let (column, page_specs, size) =
+        .enumerate()
+        .map(|(column_idx, (descriptor, page_iter))| {
+            let (column, page_specs, size, sorting_column) =
                 write_column_chunk(writer, offset, descriptor, page_iter?)?;
             offset += size;
+            record_sorting_column(&mut recorded, column_idx, sorting_column);
             Ok((column, page_specs))
         })
         .collect::<Result<Vec<_>>>()?;
     let bytes_written = offset - initial;
+    let sorting_columns = merge_sorting_columns(sorting_columns, recorded);
 
     let num_rows = compute_num_rows(&columns)?;
 
@@ -123,7 +162,7 @@ where
             columns,
             total_byte_size,
             num_rows,
-            sorting_columns: None,
+            sorting_columns,
             file_offset,
             total_compressed_size: Some(total_compressed_size),
             ordinal: ordinal.try_into().ok(),
@@ -145,6 +184,7 @@ pub async fn write_row_group_async<
     descriptors: &[ColumnDescriptor],
     columns: DynIter<'a, std::result::Result<DynStreamingIterator<'a, CompressedPage, E>, E>>,
     ordinal: usize,
+    sorting_columns: Option<Vec<SortingColumn>>,
 ) -> Result<(RowGroup, Vec<Vec<PageWriteSpec>>, u64)>
 where
     W: AsyncWrite + Unpin + Send,
@@ -154,14 +194,17 @@ where
     let column_iter = descriptors.iter().zip(columns);
 
     let initial = offset;
+    let mut recorded = vec![];
     let mut columns = vec![];
-    for (descriptor, page_iter) in column_iter {
-        let (column, page_specs, size) =
+    for (column_idx, (descriptor, page_iter)) in column_iter.enumerate() {
+        let (column, page_specs, size, sorting_column) =
             write_column_chunk_async(writer, offset, descriptor, page_iter?).await?;
         offset += size;
+        record_sorting_column(&mut recorded, column_idx, sorting_column);
         columns.push((column, page_specs));
     }
     let bytes_written = offset - initial;
+    let sorting_columns = merge_sorting_columns(sorting_columns, recorded);
 
     let num_rows = compute_num_rows(&columns)?;
 
@@ -189,7 +232,7 @@ where
             columns,
             total_byte_size,
             num_rows: num_rows as i64,
-            sorting_columns: None,
+            sorting_columns,
             file_offset,
             total_compressed_size: Some(total_compressed_size),
             ordinal: ordinal.try_into().ok(),
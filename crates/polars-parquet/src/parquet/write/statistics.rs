@@ -81,10 +81,7 @@ pub fn reduce(stats: &[&Option<Arc<dyn Statistics>>]) -> Result<Option<Arc<dyn S
 fn reduce_binary<'a, I: Iterator<Item = &'a BinaryStatistics>>(mut stats: I) -> BinaryStatistics {
     let initial = stats.next().unwrap().clone();
     stats.fold(initial, |mut acc, new| {
-        acc.min_value = reduce_vec8(acc.min_value, &new.min_value, false);
-        acc.max_value = reduce_vec8(acc.max_value, &new.max_value, true);
-        acc.null_count = reduce_single(acc.null_count, new.null_count, |x, y| x + y);
-        acc.distinct_count = None;
+        acc.merge(new);
         acc
     })
 }
@@ -94,35 +91,17 @@ fn reduce_fix_len_binary<'a, I: Iterator<Item = &'a FixedLenStatistics>>(
 ) -> FixedLenStatistics {
     let initial = stats.next().unwrap().clone();
     stats.fold(initial, |mut acc, new| {
-        acc.min_value = reduce_vec8(acc.min_value, &new.min_value, false);
-        acc.max_value = reduce_vec8(acc.max_value, &new.max_value, true);
-        acc.null_count = reduce_single(acc.null_count, new.null_count, |x, y| x + y);
-        acc.distinct_count = None;
+        acc.merge(new);
         acc
     })
 }
 
+/// Byte arrays are ordered as unsigned bytes, lexicographically, with a prefix ordered first.
 fn ord_binary(a: Vec<u8>, b: Vec<u8>, max: bool) -> Vec<u8> {
-    for (v1, v2) in a.iter().zip(b.iter()) {
-        match v1.cmp(v2) {
-            std::cmp::Ordering::Greater => {
-                if max {
-                    return a;
-                } else {
-                    return b;
-                }
-            },
-            std::cmp::Ordering::Less => {
-                if max {
-                    return b;
-                } else {
-                    return a;
-                }
-            },
-            _ => {},
-        }
+    match (a.cmp(&b), max) {
+        (std::cmp::Ordering::Less, true) | (std::cmp::Ordering::Greater, false) => b,
+        _ => a,
     }
-    a
 }
 
 fn reduce_boolean<'a, I: Iterator<Item = &'a BooleanStatistics>>(
@@ -130,18 +109,7 @@ fn reduce_boolean<'a, I: Iterator<Item = &'a BooleanStatistics>>(
 ) -> BooleanStatistics {
     let initial = stats.next().unwrap().clone();
     stats.fold(initial, |mut acc, new| {
-        acc.min_value = reduce_single(
-            acc.min_value,
-            new.min_value,
-            |x, y| if x & !(y) { y } else { x },
-        );
-        acc.max_value = reduce_single(
-            acc.max_value,
-            new.max_value,
-            |x, y| if x & !(y) { x } else { y },
-        );
-        acc.null_count = reduce_single(acc.null_count, new.null_count, |x, y| x + y);
-        acc.distinct_count = None;
+        acc.merge(new);
         acc
     })
 }
@@ -155,22 +123,71 @@ fn reduce_primitive<
 ) -> PrimitiveStatistics<T> {
     let initial = stats.next().unwrap().clone();
     stats.fold(initial, |mut acc, new| {
-        acc.min_value = reduce_single(
-            acc.min_value,
-            new.min_value,
-            |x, y| if x > y { y } else { x },
-        );
-        acc.max_value = reduce_single(
-            acc.max_value,
-            new.max_value,
-            |x, y| if x > y { x } else { y },
-        );
-        acc.null_count = reduce_single(acc.null_count, new.null_count, |x, y| x + y);
-        acc.distinct_count = None;
+        acc.merge(new);
         acc
     })
 }
 
+/// Returns whether `x` is unordered with itself, i.e. a NaN.
+#[inline]
+fn is_nan<T: PartialOrd>(x: &T) -> bool {
+    x.partial_cmp(x).is_none()
+}
+
+impl BinaryStatistics {
+    /// Merges the statistics of another page of the same column into these.
+    pub fn merge(&mut self, other: &Self) {
+        self.min_value = reduce_vec8(self.min_value.take(), &other.min_value, false);
+        self.max_value = reduce_vec8(self.max_value.take(), &other.max_value, true);
+        self.null_count = reduce_single(self.null_count, other.null_count, |x, y| x + y);
+        self.distinct_count = None;
+    }
+}
+
+impl FixedLenStatistics {
+    /// Merges the statistics of another page of the same column into these.
+    pub fn merge(&mut self, other: &Self) {
+        self.min_value = reduce_vec8(self.min_value.take(), &other.min_value, false);
+        self.max_value = reduce_vec8(self.max_value.take(), &other.max_value, true);
+        self.null_count = reduce_single(self.null_count, other.null_count, |x, y| x + y);
+        self.distinct_count = None;
+    }
+}
+
+impl BooleanStatistics {
+    /// Merges the statistics of another page of the same column into these.
+    pub fn merge(&mut self, other: &Self) {
+        self.min_value = reduce_single(self.min_value, other.min_value, |x, y| x & y);
+        self.max_value = reduce_single(self.max_value, other.max_value, |x, y| x | y);
+        self.null_count = reduce_single(self.null_count, other.null_count, |x, y| x + y);
+        self.distinct_count = None;
+    }
+}
+
+impl<T: NativeType + PartialOrd> PrimitiveStatistics<T> {
+    /// Merges the statistics of another page of the same column into these.
+    ///
+    /// A NaN bound is only kept when neither side has an ordered one.
+    pub fn merge(&mut self, other: &Self) {
+        self.min_value = reduce_single(self.min_value, other.min_value, |x, y| {
+            if is_nan(&x) || y < x {
+                y
+            } else {
+                x
+            }
+        });
+        self.max_value = reduce_single(self.max_value, other.max_value, |x, y| {
+            if is_nan(&x) || y > x {
+                y
+            } else {
+                x
+            }
+        });
+        self.null_count = reduce_single(self.null_count, other.null_count, |x, y| x + y);
+        self.distinct_count = None;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -320,4 +337,66 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn merge_pages() {
+        let page = |min, max| PrimitiveStatistics {
+            null_count: Some(1),
+            distinct_count: None,
+            min_value: Some(min),
+            max_value: Some(max),
+            primitive_type: PrimitiveType::from_physical("bla".to_string(), PhysicalType::Int64),
+        };
+        let mut stats = page(-3i64, 5);
+        stats.merge(&page(-10, -4));
+        stats.merge(&page(0, 20));
+
+        assert_eq!(stats.min_value, Some(-10));
+        assert_eq!(stats.max_value, Some(20));
+        assert_eq!(stats.null_count, Some(3));
+    }
+
+    #[test]
+    fn merge_nan() {
+        let page = |min, max| PrimitiveStatistics {
+            null_count: Some(0),
+            distinct_count: None,
+            min_value: Some(min),
+            max_value: Some(max),
+            primitive_type: PrimitiveType::from_physical("bla".to_string(), PhysicalType::Double),
+        };
+        let mut stats = page(f64::NAN, f64::NAN);
+        stats.merge(&page(1.0, 2.0));
+        stats.merge(&page(f64::NAN, f64::NAN));
+        stats.merge(&page(-1.0, 1.5));
+
+        assert_eq!(stats.min_value, Some(-1.0));
+        assert_eq!(stats.max_value, Some(2.0));
+
+        let mut stats = page(f64::NAN, f64::NAN);
+        stats.merge(&page(f64::NAN, f64::NAN));
+        assert!(stats.min_value.unwrap().is_nan());
+    }
+
+    #[test]
+    fn merge_binary_unsigned() {
+        let page = |min: &[u8], max: &[u8]| BinaryStatistics {
+            primitive_type: PrimitiveType::from_physical(
+                "bla".to_string(),
+                PhysicalType::ByteArray,
+            ),
+            null_count: None,
+            distinct_count: None,
+            min_value: Some(min.to_vec()),
+            max_value: Some(max.to_vec()),
+        };
+        let mut stats = page(&[1, 2], &[1, 2]);
+        stats.merge(&page(&[1], &[0x80]));
+        stats.merge(&page(&[1, 2, 0], &[0x7f, 0xff]));
+
+        // a prefix is smaller and bytes compare as unsigned
+        assert_eq!(stats.min_value, Some(vec![1]));
+        assert_eq!(stats.max_value, Some(vec![0x80]));
+        assert_eq!(stats.null_count, None);
+    }
 }
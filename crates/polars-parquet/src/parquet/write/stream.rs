@@ -2,7 +2,7 @@ use std::io::Write;
 
 use futures::{AsyncWrite, AsyncWriteExt};
 use parquet_format_safe::thrift::protocol::TCompactOutputStreamProtocol;
-use parquet_format_safe::{FileMetaData, RowGroup};
+use parquet_format_safe::{FileMetaData, RowGroup, SortingColumn};
 
 use super::row_group::write_row_group_async;
 use super::{RowGroupIterColumns, WriteOptions};
@@ -51,6 +51,7 @@ pub struct FileStreamer<W: AsyncWrite + Unpin + Send> {
     offset: u64,
     row_groups: Vec<RowGroup>,
     page_specs: Vec<Vec<Vec<PageWriteSpec>>>,
+    sorting_columns: Option<Vec<SortingColumn>>,
     /// Used to store the current state for writing the file
     state: State,
 }
@@ -84,6 +85,7 @@ impl<W: AsyncWrite + Unpin + Send> FileStreamer<W> {
             offset: 0,
             row_groups: vec![],
             page_specs: vec![],
+            sorting_columns: None,
             state: State::Initialised,
         }
     }
@@ -106,6 +108,15 @@ impl<W: AsyncWrite + Unpin + Send> FileStreamer<W> {
         }
     }
 
+    /// Sets the columns by which the rows of every row group written after this call are
+    /// sorted, recorded in their metadata so that readers can rely on it.
+    ///
+    /// The values are not checked to be sorted. The orders recorded by the pages of other
+    /// columns are added to them.
+    pub fn set_sorting_columns(&mut self, sorting_columns: Option<Vec<SortingColumn>>) {
+        self.sorting_columns = sorting_columns;
+    }
+
     /// Writes a row group to the file.
     pub async fn write<E>(&mut self, row_group: RowGroupIterColumns<'_, E>) -> Result<()>
     where
@@ -123,6 +134,7 @@ impl<W: AsyncWrite + Unpin + Send> FileStreamer<W> {
             self.schema.columns(),
             row_group,
             ordinal,
+            self.sorting_columns.clone(),
         )
         .await?;
         self.offset += size;
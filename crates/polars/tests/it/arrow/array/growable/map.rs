@@ -0,0 +1,87 @@
+use arrow::array::growable::{Growable, GrowableMap};
+use arrow::array::{Array, Int32Array, MapArray, StructArray, Utf8Array};
+use arrow::bitmap::Bitmap;
+use arrow::datatypes::{ArrowDataType, Field};
+
+fn kv_type() -> ArrowDataType {
+    ArrowDataType::Struct(vec![
+        Field::new("key", ArrowDataType::Utf8, false),
+        Field::new("value", ArrowDataType::Int32, true),
+    ])
+}
+
+fn map_type() -> ArrowDataType {
+    ArrowDataType::Map(Box::new(Field::new("entries", kv_type(), false)), false)
+}
+
+fn create_map_array(
+    keys: &[&str],
+    values: &[Option<i32>],
+    offsets: Vec<i32>,
+    validity: Option<Bitmap>,
+) -> MapArray {
+    let field = StructArray::new(
+        kv_type(),
+        vec![
+            Utf8Array::<i32>::from_slice(keys).boxed(),
+            Int32Array::from(values).boxed(),
+        ],
+        None,
+    );
+    MapArray::new(
+        map_type(),
+        offsets.try_into().unwrap(),
+        field.boxed(),
+        validity,
+    )
+}
+
+#[test]
+fn basic() {
+    // [{"a": 1, "b": None}, None, {"c": 3}]
+    let array = create_map_array(
+        &["a", "b", "c"],
+        &[Some(1), None, Some(3)],
+        vec![0, 2, 2, 3],
+        Some(Bitmap::from([true, false, true])),
+    );
+
+    let mut a = GrowableMap::new(vec![&array], false, 0);
+    unsafe {
+        a.extend(0, 1, 2);
+    }
+    assert_eq!(a.len(), 2);
+
+    let result: MapArray = a.into();
+
+    let expected = create_map_array(
+        &["c"],
+        &[Some(3)],
+        vec![0, 0, 1],
+        Some(Bitmap::from([false, true])),
+    );
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn test_from_two_maps() {
+    let array_1 = create_map_array(&["a", "b"], &[Some(1), Some(2)], vec![0, 1, 2], None);
+    let array_2 = create_map_array(&["c", "d"], &[None, Some(4)], vec![0, 0, 2], None);
+
+    let mut a = GrowableMap::new(vec![&array_1, &array_2], false, 3);
+    unsafe {
+        a.extend(0, 1, 1);
+        a.extend(1, 0, 2);
+    }
+    assert_eq!(a.len(), 3);
+
+    let result: MapArray = a.into();
+
+    let expected = create_map_array(
+        &["b", "c", "d"],
+        &[Some(2), None, Some(4)],
+        vec![0, 1, 1, 3],
+        None,
+    );
+    assert_eq!(result, expected);
+}
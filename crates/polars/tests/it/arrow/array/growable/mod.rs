@@ -4,6 +4,7 @@ mod dictionary;
 mod fixed_binary;
 mod fixed_size_list;
 mod list;
+mod map;
 mod null;
 mod primitive;
 mod struct_;
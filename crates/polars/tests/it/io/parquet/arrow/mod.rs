@@ -1256,12 +1256,7 @@ fn integration_write(
     schema: &ArrowSchema,
     chunks: &[RecordBatchT<Box<dyn Array>>],
 ) -> PolarsResult<Vec<u8>> {
-    let options = WriteOptions {
-        write_statistics: true,
-        compression: CompressionOptions::Uncompressed,
-        version: Version::V1,
-        data_pagesize_limit: None,
-    };
+    let options = WriteOptions::builder().build().unwrap();
 
     let encodings = schema
         .fields
@@ -1277,8 +1272,12 @@ fn integration_write(
         })
         .collect();
 
-    let row_groups =
-        RowGroupIterator::try_new(chunks.iter().cloned().map(Ok), schema, options, encodings)?;
+    let row_groups = RowGroupIterator::try_new(
+        chunks.iter().cloned().map(Ok),
+        schema,
+        options.clone(),
+        encodings,
+    )?;
 
     let writer = Cursor::new(vec![]);
 
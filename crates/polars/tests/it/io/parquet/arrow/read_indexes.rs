@@ -28,12 +28,7 @@ fn pages(
 
     let parquet_schema = to_parquet_schema(&schema)?;
 
-    let options = WriteOptions {
-        write_statistics: true,
-        compression: CompressionOptions::Uncompressed,
-        version: Version::V1,
-        data_pagesize_limit: None,
-    };
+    let options = WriteOptions::builder().build().unwrap();
 
     let pages1 = [array11, array12, array13]
         .into_iter()
@@ -45,7 +40,7 @@ fn pages(
                     .primitive_type
                     .clone(),
                 &[Nested::Primitive(None, true, array.len())],
-                options,
+                options.clone(),
                 Encoding::Plain,
             )
         })
@@ -61,7 +56,7 @@ fn pages(
                     .primitive_type
                     .clone(),
                 &[Nested::Primitive(None, true, array.len())],
-                options,
+                options.clone(),
                 encoding,
             )
             .unwrap()
@@ -78,12 +73,7 @@ fn read_with_indexes(
     (pages1, pages2, schema): (Vec<Page>, Vec<Page>, ArrowSchema),
     expected: Box<dyn Array>,
 ) -> PolarsResult<()> {
-    let options = WriteOptions {
-        write_statistics: true,
-        compression: CompressionOptions::Uncompressed,
-        version: Version::V1,
-        data_pagesize_limit: None,
-    };
+    let options = WriteOptions::builder().build().unwrap();
 
     let to_compressed = |pages: Vec<Page>| {
         let encoded_pages = DynIter::new(pages.into_iter().map(Ok));
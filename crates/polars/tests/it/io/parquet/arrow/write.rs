@@ -44,17 +44,16 @@ fn round_trip_opt_stats(
     let field = Field::new("a1", array.data_type().clone(), true);
     let schema = ArrowSchema::from(vec![field]);
 
-    let options = WriteOptions {
-        write_statistics: true,
-        compression,
-        version,
-        data_pagesize_limit: None,
-    };
+    let options = WriteOptions::builder()
+        .version(version)
+        .compression(compression)
+        .build()
+        .unwrap();
 
     let iter = vec![RecordBatchT::try_new(vec![array.clone()])];
 
     let row_groups =
-        RowGroupIterator::try_new(iter.into_iter(), &schema, options, vec![encodings])?;
+        RowGroupIterator::try_new(iter.into_iter(), &schema, options.clone(), vec![encodings])?;
 
     let writer = Cursor::new(vec![]);
     let mut writer = FileWriter::try_new(writer, schema, options)?;
@@ -130,6 +129,45 @@ fn int64_required_delta() -> PolarsResult<()> {
     )
 }
 
+#[test]
+fn float64_optional_byte_stream_split() -> PolarsResult<()> {
+    round_trip(
+        "float64",
+        "nullable",
+        Version::V2,
+        CompressionOptions::Uncompressed,
+        vec![Encoding::ByteStreamSplit],
+    )
+}
+
+#[test]
+fn list_float64_byte_stream_split() -> PolarsResult<()> {
+    for inner_is_nullable in [false, true] {
+        let array = data((0..12).map(|x| x as f64 * 1.5), inner_is_nullable);
+        let field = Field::new("a1", array.data_type().clone(), true);
+        let schema = ArrowSchema::from(vec![field]);
+        let options = WriteOptions::builder().build()?;
+
+        let iter = vec![RecordBatchT::try_new(vec![array.clone()])];
+        let row_groups = RowGroupIterator::try_new(
+            iter.into_iter(),
+            &schema,
+            options.clone(),
+            vec![vec![Encoding::ByteStreamSplit]],
+        )?;
+        let mut writer = FileWriter::try_new(Cursor::new(vec![]), schema, options)?;
+        for group in row_groups {
+            writer.write(group?)?;
+        }
+        writer.end(None)?;
+        let data = writer.into_inner().into_inner();
+
+        let (result, _) = read_column(&mut Cursor::new(data), "a1")?;
+        assert_eq!(array.as_ref(), result.as_ref());
+    }
+    Ok(())
+}
+
 #[cfg(feature = "parquet")]
 #[test]
 fn int64_optional_v2_compressed() -> PolarsResult<()> {
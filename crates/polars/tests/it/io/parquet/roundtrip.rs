@@ -17,17 +17,16 @@ fn round_trip(
     let field = Field::new("a1", array.data_type().clone(), true);
     let schema = ArrowSchema::from(vec![field]);
 
-    let options = WriteOptions {
-        write_statistics: true,
-        compression,
-        version,
-        data_pagesize_limit: None,
-    };
+    let options = WriteOptions::builder()
+        .version(version)
+        .compression(compression)
+        .build()
+        .unwrap();
 
     let iter = vec![RecordBatchT::try_new(vec![array.clone()])];
 
     let row_groups =
-        RowGroupIterator::try_new(iter.into_iter(), &schema, options, vec![encodings])?;
+        RowGroupIterator::try_new(iter.into_iter(), &schema, options.clone(), vec![encodings])?;
 
     let writer = Cursor::new(vec![]);
     let mut writer = FileWriter::try_new(writer, schema.clone(), options)?;